@@ -0,0 +1,152 @@
+//! Renders 64 columns over a synthetic 4 GB address space while counting the `read_fn` calls and measuring the draw
+//! time of every frame, to make performance regressions across egui upgrades measurable.
+//!
+//! Run with `cargo run --release --example bench` for the interactive version, or add `-- --headless` to render a fixed
+//! amount of frames without a window and print a summary.
+use std::time::{Duration, Instant};
+
+use eframe::{Frame, NativeOptions};
+use egui::{Context, RawInput, Rect, Vec2};
+
+use egui_memory_editor::MemoryEditor;
+
+/// The size of the synthetic address space, 4 GB.
+#[cfg(target_pointer_width = "64")]
+const MEMORY_SIZE: usize = 1 << 32;
+/// The size of the synthetic address space, as large as fits in the address type on this target.
+#[cfg(not(target_pointer_width = "64"))]
+const MEMORY_SIZE: usize = usize::MAX;
+/// The amount of frames rendered in headless mode.
+const HEADLESS_FRAMES: usize = 500;
+
+pub fn main() {
+    if std::env::args().any(|arg| arg == "--headless") {
+        run_headless();
+        return;
+    }
+
+    let _ = eframe::run_native(
+        "Mem-Edit Benchmark",
+        NativeOptions::default(),
+        Box::new(|_cc| Ok(Box::new(Bench::default()))),
+    );
+}
+
+/// Produces the same pseudo-random value for an address every time, without storing 4 GB.
+#[inline]
+fn synthetic_read(address: usize) -> u8 {
+    let mut x = address as u64 ^ 0x9E37_79B9_7F4A_7C15;
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+    (x >> 56) as u8
+}
+
+/// The measurements of a single frame.
+#[derive(Copy, Clone, Debug, Default)]
+struct FrameStats {
+    reads: usize,
+    draw_time: Duration,
+}
+
+struct Bench {
+    mem_editor: MemoryEditor,
+    is_open: bool,
+    /// Whether to keep scrolling every frame, to measure the cost of new rows becoming visible.
+    is_auto_scrolling: bool,
+    next_goto: usize,
+    last_frame: FrameStats,
+    history: Vec<FrameStats>,
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        let mut mem_editor = MemoryEditor::new()
+            .with_address_range("Synthetic", 0..MEMORY_SIZE)
+            .with_window_title("Benchmark");
+        mem_editor.options.column_count = 64;
+
+        Bench {
+            mem_editor,
+            is_open: true,
+            is_auto_scrolling: false,
+            next_goto: 0,
+            last_frame: Default::default(),
+            history: Vec::new(),
+        }
+    }
+}
+
+impl Bench {
+    /// Draw the editor once, measuring the `read_fn` calls and the time it took.
+    fn draw(&mut self, ctx: &Context) {
+        if self.is_auto_scrolling {
+            self.next_goto = (self.next_goto + 0x1_0000) % MEMORY_SIZE;
+            self.mem_editor.queue_goto(self.next_goto);
+        }
+
+        let mut reads = 0;
+        let start = Instant::now();
+        self.mem_editor
+            .window_ui_read_only(ctx, &mut self.is_open, &mut reads, |reads, address| {
+                *reads += 1;
+                Some(synthetic_read(address))
+            });
+
+        self.last_frame = FrameStats {
+            reads,
+            draw_time: start.elapsed(),
+        };
+        self.history.push(self.last_frame);
+    }
+
+    fn stats_ui(&mut self, ui: &mut egui::Ui) {
+        ui.label(format!("read_fn calls last frame: {}", self.last_frame.reads));
+        ui.label(format!("Draw time last frame: {:.3?}", self.last_frame.draw_time));
+
+        let recent = &self.history[self.history.len().saturating_sub(120)..];
+        if let Some(max) = recent.iter().map(|stats| stats.draw_time).max() {
+            ui.label(format!("Max draw time (last 120 frames): {:.3?}", max));
+        }
+
+        ui.checkbox(&mut self.is_auto_scrolling, "Auto scroll");
+        egui::warn_if_debug_build(ui);
+    }
+}
+
+impl eframe::App for Bench {
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) {
+        egui::SidePanel::left("Stats").show(ctx, |ui| self.stats_ui(ui));
+        self.draw(ctx);
+        ctx.request_repaint();
+    }
+}
+
+/// Render a fixed amount of frames without a window, scrolling every frame, and print a summary.
+fn run_headless() {
+    let ctx = Context::default();
+    let mut bench = Bench {
+        is_auto_scrolling: true,
+        ..Default::default()
+    };
+
+    for frame in 0..HEADLESS_FRAMES {
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Default::default(), Vec2::new(1920.0, 1080.0))),
+            time: Some(frame as f64 / 60.0),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| bench.draw(ctx));
+    }
+
+    // The first frames lay out the window, and aren't representative.
+    let stats = &bench.history[10..];
+    let mut draw_times: Vec<Duration> = stats.iter().map(|stats| stats.draw_time).collect();
+    draw_times.sort();
+    let total_reads: usize = stats.iter().map(|stats| stats.reads).sum();
+
+    println!("Frames:          {}", stats.len());
+    println!("Reads per frame: {}", total_reads / stats.len());
+    println!("Median draw:     {:.3?}", draw_times[draw_times.len() / 2]);
+    println!("p99 draw:        {:.3?}", draw_times[draw_times.len() * 99 / 100]);
+    println!("Max draw:        {:.3?}", draw_times[draw_times.len() - 1]);
+}
@@ -1,7 +1,7 @@
 use eframe::{Frame, NativeOptions};
 use egui::Context;
 
-use egui_memory_editor::MemoryEditor;
+use egui_memory_editor::{read_dropped_file, MemoryEditor};
 
 use crate::frame_history::FrameHistory;
 
@@ -36,6 +36,8 @@ impl Default for App {
 
         // You can set the column count in the UI, but also here. There are a variety of options available in mem_editor.options
         mem_editor.options.column_count = 16;
+        // Any file dropped onto the editor is reported in its response, see `update`.
+        mem_editor.options.accept_dropped_files = true;
 
         App {
             mem_editor,
@@ -52,13 +54,28 @@ impl eframe::App for App {
 
         // This will automatically check for `mem_editor.options.is_open`, so no need to do that here.
         // The write function is optional, if you don't set it the UI will be in read-only mode.
-        self.mem_editor.window_ui(
+        let response = self.mem_editor.window_ui(
             ctx,
             &mut self.is_open,
             &mut self.memory,
-            |mem, address| mem.read_value(address).into(),
+            |mem, address| mem.read_value(address),
             |mem, address, val| mem.write_value(address, val),
         );
+
+        // Replace the memory with any dropped binary, the other ranges show `--` beyond its end.
+        for file in &response.dropped_files {
+            match read_dropped_file(file) {
+                Ok(bytes) => {
+                    let name = file
+                        .path
+                        .as_ref()
+                        .map_or(file.name.clone(), |path| path.display().to_string());
+                    self.memory.memory = bytes.to_vec();
+                    self.mem_editor.set_address_range(name, 0..bytes.len());
+                }
+                Err(e) => eprintln!("Failed to read the dropped file: {e}"),
+            }
+        }
         // If your memory changes between frames you'll need to re-render at whatever framerate you want.
         ctx.request_repaint();
     }
@@ -77,12 +94,14 @@ impl Default for Memory {
 }
 
 impl Memory {
-    pub fn read_value(&mut self, address: usize) -> u8 {
-        self.memory[address]
+    pub fn read_value(&mut self, address: usize) -> Option<u8> {
+        self.memory.get(address).copied()
     }
 
     pub fn write_value(&mut self, address: usize, val: u8) {
-        self.memory[address] = val
+        if let Some(value) = self.memory.get_mut(address) {
+            *value = val;
+        }
     }
 }
 
@@ -0,0 +1,153 @@
+//! The address input of the goto field, as a widget for use in the host's own toolbars.
+use std::ops::Range;
+
+use egui::{Key, Response, TextEdit, Ui};
+
+use crate::Address;
+
+/// Evaluate a sum of hex addresses, such as `FF00 + 10 - 0x2`, returns `None` on overflow or invalid terms.
+pub(crate) fn parse_address_expression(text: &str) -> Option<Address> {
+    let mut total: Address = 0;
+    let mut is_negative = false;
+    let mut term_start = 0;
+
+    // Splitting on the operators keeps their position, such that each term knows its sign.
+    for (index, operator) in text.match_indices(['+', '-']).chain([(text.len(), "")]) {
+        let term = crate::utilities::parse_address(&text[term_start..index])?;
+        total = if is_negative {
+            total.checked_sub(term)?
+        } else {
+            total.checked_add(term)?
+        };
+
+        is_negative = operator == "-";
+        term_start = index + operator.len();
+    }
+
+    Some(total)
+}
+
+/// Resolve the text of an address input within `address_space`.
+///
+/// For brevity the base address can be elided, e.g when using the range `0xFF00..0xFFFF` one can write `5` to go to
+/// `0xFF05`.
+pub(crate) fn resolve_address(text: &str, address_space: &Range<Address>) -> Option<Address> {
+    let address = parse_address_expression(text)?;
+
+    if address_space.contains(&address) {
+        Some(address)
+    } else {
+        address
+            .checked_add(address_space.start)
+            .filter(|address| address_space.contains(address))
+    }
+}
+
+/// The result of showing an [`AddressInput`].
+pub struct AddressInputResponse {
+    pub response: Response,
+    /// Whether enter was pressed this frame.
+    pub is_submitted: bool,
+    /// The address that was submitted this frame, `None` if nothing was submitted or the input was invalid.
+    pub address: Option<Address>,
+    /// The submitted address if it was outside the address space, [`AddressInputResponse::address`] is then only set
+    /// when clamping, see [`AddressInput::clamp`].
+    pub out_of_range: Option<Address>,
+}
+
+/// The text input of the goto field of the [`crate::MemoryEditor`], which parses hex addresses within an address
+/// space when enter is pressed.
+///
+/// Accepts hex addresses with or without a `0x` prefix, sums such as `FF00 + 10 - 2`, and offsets from the start of
+/// the address space. After a successful submit the text is replaced by the absolute address.
+///
+/// ```no_run
+/// # use egui_memory_editor::AddressInput;
+/// # let ui: &mut egui::Ui = todo!();
+/// # let mut text = String::new();
+/// if let Some(address) = AddressInput::new(&mut text, 0x8000..0x10000).show(ui).address {
+///     println!("Breakpoint at {address:#X}");
+/// }
+/// ```
+pub struct AddressInput<'a> {
+    text: &'a mut String,
+    address_space: Range<Address>,
+    hint_text: String,
+    desired_width: Option<f32>,
+    is_clamped: bool,
+}
+
+impl<'a> AddressInput<'a> {
+    /// An input for addresses in `address_space`, editing `text`.
+    pub fn new(text: &'a mut String, address_space: Range<Address>) -> Self {
+        AddressInput {
+            text,
+            address_space,
+            hint_text: "0000".to_string(),
+            desired_width: None,
+            is_clamped: false,
+        }
+    }
+
+    /// The text shown while the input is empty, `0000` by default.
+    pub fn hint_text(mut self, hint_text: impl Into<String>) -> Self {
+        self.hint_text = hint_text.into();
+        self
+    }
+
+    /// The width of the input, the default of [`TextEdit`] if unset.
+    pub fn desired_width(mut self, desired_width: f32) -> Self {
+        self.desired_width = Some(desired_width);
+        self
+    }
+
+    /// Whether addresses outside the address space resolve to its nearest bound, `false` by default.
+    pub fn clamp(mut self, is_clamped: bool) -> Self {
+        self.is_clamped = is_clamped;
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> AddressInputResponse {
+        let mut text_edit = TextEdit::singleline(self.text).hint_text(self.hint_text);
+        if let Some(width) = self.desired_width {
+            text_edit = text_edit.desired_width(width);
+        }
+        let response = ui.add(text_edit);
+
+        self.text
+            .retain(|c| c.is_ascii_hexdigit() || matches!(c, 'x' | 'X' | '+' | '-' | ' '));
+
+        // For some reason egui is triggering response.clicked() when we press enter at the moment
+        // (didn't used to do this). The additional check for not having enter pressed will need to stay until that is fixed.
+        if response.clicked() && !ui.input(|i| i.key_pressed(Key::Enter)) {
+            self.text.clear();
+        }
+
+        let is_submitted = response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+        let mut out_of_range = None;
+        let address = if is_submitted {
+            resolve_address(self.text, &self.address_space).or_else(|| {
+                out_of_range = parse_address_expression(self.text);
+                out_of_range
+                    .filter(|_| self.is_clamped && !self.address_space.is_empty())
+                    .map(|address| address.clamp(self.address_space.start, self.address_space.end - 1))
+            })
+        } else {
+            None
+        };
+
+        if let Some(address) = address {
+            *self.text = format!("{:X}", address);
+        }
+        if is_submitted {
+            response.surrender_focus();
+        }
+
+        AddressInputResponse {
+            response,
+            is_submitted,
+            address,
+            out_of_range,
+        }
+    }
+}
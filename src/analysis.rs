@@ -0,0 +1,250 @@
+//! Analysis of the contents of a range, such as counting the occurrences of a pattern or finding the longest run of
+//! a byte. Useful for locating free space in ROMs.
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use egui::{RichText, ScrollArea, Ui};
+
+use crate::labels::fill;
+use crate::task::ChunkedTask;
+use crate::{Address, MemoryEditor};
+
+/// A running or finished analysis of a range.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Analysis {
+    pub pattern: Vec<u8>,
+    /// The region the analysis was run against.
+    pub region: String,
+    pub addresses: Range<Address>,
+    /// The last `pattern.len()` bytes, used to match the pattern across chunk boundaries.
+    window: VecDeque<Option<u8>>,
+    /// The start addresses of all, possibly overlapping, occurrences of the pattern.
+    pub occurrences: Vec<Address>,
+    /// The run of the single byte pattern at the end of the processed addresses, as `(start, length)`.
+    current_run: Option<(Address, usize)>,
+    /// The longest run of the single byte pattern, as `(start, length)`.
+    pub longest_run: Option<(Address, usize)>,
+    pub task: Option<ChunkedTask>,
+}
+
+impl Analysis {
+    pub fn new(pattern: Vec<u8>, region: String, addresses: Range<Address>) -> Self {
+        Analysis {
+            pattern,
+            region,
+            task: Some(ChunkedTask::new(addresses.clone())),
+            addresses,
+            ..Default::default()
+        }
+    }
+
+    /// Analyse the next chunk of the range, if there is one.
+    pub fn step<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        budget: usize,
+    ) {
+        let Some(task) = self.task.as_mut() else {
+            return;
+        };
+        let chunk = task.next_chunk(budget);
+        let is_finished = task.is_finished();
+
+        for address in chunk.into_iter().flatten() {
+            let value = read_fn(mem, address);
+            self.feed(address, value);
+        }
+
+        if is_finished {
+            self.finish();
+        }
+    }
+
+    /// Finish the running analysis at once, see [`MemoryEditor::complete_tasks_with_slice`].
+    pub fn complete_with_slice(&mut self, memory: &[u8], base: Address) {
+        let Some(mut task) = self.task.take() else {
+            return;
+        };
+
+        for address in task.take_remaining() {
+            self.feed(address, crate::scan::read_slice(memory, base, address));
+        }
+
+        self.finish();
+    }
+
+    fn feed(&mut self, address: Address, value: Option<u8>) {
+        if self.pattern.is_empty() {
+            return;
+        }
+
+        self.window.push_back(value);
+        if self.window.len() > self.pattern.len() {
+            self.window.pop_front();
+        }
+
+        if self.window.len() == self.pattern.len()
+            && self
+                .window
+                .iter()
+                .zip(&self.pattern)
+                .all(|(&value, &byte)| value == Some(byte))
+        {
+            self.occurrences.push(address + 1 - self.pattern.len());
+        }
+
+        if let [byte] = self.pattern[..] {
+            if value == Some(byte) {
+                let run = self.current_run.get_or_insert((address, 0));
+                run.1 += 1;
+            } else {
+                self.end_run();
+            }
+        }
+    }
+
+    fn end_run(&mut self) {
+        if let Some(run) = self.current_run.take() {
+            if self.longest_run.is_none_or(|longest| run.1 > longest.1) {
+                self.longest_run = Some(run);
+            }
+        }
+    }
+
+    fn finish(&mut self) {
+        self.end_run();
+        self.task = None;
+    }
+}
+
+/// The UI state of the `Analysis` section.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct AnalysisState {
+    pub pattern_string: String,
+    pub is_selection_only: bool,
+    pub error: Option<String>,
+    pub analysis: Option<Analysis>,
+}
+
+impl MemoryEditor {
+    /// Draw the `Analysis` section, for counting pattern occurrences and locating the longest run of a byte.
+    pub(crate) fn draw_analysis(&mut self, ui: &mut Ui, current_address_range: &Range<Address>) {
+        egui::CollapsingHeader::new(&self.options.labels.analysis_title)
+            .default_open(false)
+            .show(ui, |ui| {
+                let selection = self.frame_data.selection().filter(|selection| selection.len() > 1);
+                let state = &mut self.analysis;
+                let labels = &self.options.labels;
+
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut state.pattern_string).hint_text(&labels.pattern_hint))
+                        .on_hover_text(&labels.pattern_hover);
+
+                    ui.add_enabled(
+                        selection.is_some(),
+                        egui::Checkbox::new(&mut state.is_selection_only, &labels.selection_only),
+                    );
+
+                    if ui.button(&labels.analyse).clicked() {
+                        let addresses = match &selection {
+                            Some(selection) if state.is_selection_only => selection.clone(),
+                            _ => current_address_range.clone(),
+                        };
+
+                        match crate::utilities::parse_hex_bytes(&state.pattern_string) {
+                            Some(pattern) if !pattern.is_empty() => {
+                                state.error = None;
+                                state.analysis = Some(Analysis::new(
+                                    pattern,
+                                    self.options.selected_address_range.clone(),
+                                    addresses,
+                                ));
+                            }
+                            _ => state.error = Some(labels.invalid_pattern.clone()),
+                        }
+                    }
+                });
+
+                if let Some(error) = &state.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                let Some(analysis) = state.analysis.as_mut() else {
+                    return;
+                };
+
+                if let Some(task) = &analysis.task {
+                    if task.draw_progress(ui, labels) {
+                        state.analysis = None;
+                    }
+                    return;
+                }
+
+                // Only show results for the region they were computed for.
+                if analysis.region != self.options.selected_address_range {
+                    return;
+                }
+
+                let mut goto = None;
+                let pattern = analysis
+                    .pattern
+                    .iter()
+                    .map(|byte| format!("{:02X}", byte))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                if let [byte] = analysis.pattern[..] {
+                    match analysis.longest_run {
+                        Some((start, length)) => {
+                            ui.horizontal(|ui| {
+                                ui.label(fill(
+                                    &labels.longest_run,
+                                    &[
+                                        ("byte", &format!("{:02X}", byte)),
+                                        ("length", &format!("{:#X}", length)),
+                                    ],
+                                ));
+                                if ui.link(format!("{:#X}", start)).clicked() {
+                                    goto = Some(start);
+                                }
+                            });
+                        }
+                        None => {
+                            ui.label(fill(&labels.no_run, &[("byte", &format!("{:02X}", byte))]));
+                        }
+                    }
+                }
+
+                ui.label(fill(
+                    &labels.occurrences,
+                    &[
+                        ("count", &analysis.occurrences.len()),
+                        ("pattern", &pattern),
+                        ("range", &format!("{:#X?}", analysis.addresses)),
+                    ],
+                ));
+
+                let value_font = self.options.value_font(ui.style());
+                let row_height = ui.fonts(|fonts| fonts.row_height(&value_font));
+
+                ScrollArea::vertical()
+                    .id_salt("analysis_occurrences")
+                    .max_height(row_height * 8.0)
+                    .show_rows(ui, row_height, analysis.occurrences.len(), |ui, rows| {
+                        for &address in &analysis.occurrences[rows] {
+                            let text = RichText::new(format!("{:#X}", address)).font(value_font.clone());
+
+                            if ui.link(text).clicked() {
+                                goto = Some(address);
+                            }
+                        }
+                    });
+
+                if let Some(address) = goto {
+                    self.frame_data
+                        .set_goto_address(address, current_address_range, self.column_count());
+                }
+            });
+    }
+}
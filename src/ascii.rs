@@ -0,0 +1,85 @@
+//! Caching of the decoded text of the ASCII sidebar, as most rows don't change between frames.
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::Address;
+
+/// The character a byte is displayed as in the ASCII sidebar, non-printable bytes are displayed as `.`.
+#[inline]
+pub(crate) fn ascii_character(value: u8) -> char {
+    printable_character(value).unwrap_or('.')
+}
+
+/// The character of a byte, if it's one of the printable ones shown in the ASCII sidebar.
+#[inline]
+pub(crate) fn printable_character(value: u8) -> Option<char> {
+    (32..128).contains(&value).then_some(value as char)
+}
+
+/// The decoded text of a single row, along with the bytes it was decoded from.
+#[derive(Clone, Debug, Default)]
+struct AsciiRow {
+    bytes: Vec<u8>,
+    text: String,
+    /// The [`crate::refresh::RefreshCache::generation`] the bytes were read in.
+    generation: Option<u64>,
+}
+
+/// The decoded rows of the ASCII sidebar, keyed by the start address of the row.
+///
+/// The text is only rebuilt if one of the bytes of its row changed. While refreshes are throttled the bytes aren't
+/// even read again, until the [`crate::refresh::RefreshCache`] forgets its values or the row is written to.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct AsciiRowCache {
+    rows: HashMap<Address, AsciiRow>,
+    generation: Option<u64>,
+}
+
+impl AsciiRowCache {
+    /// Prepare the cache for a new frame, with the current [`crate::refresh::RefreshCache::generation`].
+    pub fn start_frame(&mut self, generation: Option<u64>) {
+        self.generation = generation;
+    }
+
+    /// Returns the text of the row with `addresses`, and whether it could be reused from the previous frame.
+    pub fn row<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        addresses: Range<Address>,
+    ) -> (&str, bool) {
+        let row = self.rows.entry(addresses.start).or_default();
+        let mut is_changed = row.bytes.len() != addresses.len();
+        if !is_changed && self.generation.is_some() && row.generation == self.generation {
+            return (&row.text, true);
+        }
+        row.generation = self.generation;
+        row.bytes.resize(addresses.len(), 0);
+
+        for (byte, address) in row.bytes.iter_mut().zip(addresses) {
+            let value = read_fn(mem, address).unwrap_or(0);
+            is_changed |= *byte != value;
+            *byte = value;
+        }
+
+        if is_changed {
+            row.text = row.bytes.iter().copied().map(ascii_character).collect();
+        }
+
+        (&row.text, !is_changed)
+    }
+
+    /// Read the row containing `address` again, for when it was written to.
+    pub fn invalidate(&mut self, address: Address) {
+        for (start, row) in &mut self.rows {
+            if (*start..start + row.bytes.len()).contains(&address) {
+                row.generation = None;
+            }
+        }
+    }
+
+    /// Forget all rows which start outside of `addresses`, such that the cache only holds the visible rows.
+    pub fn retain(&mut self, addresses: &Range<Address>) {
+        self.rows.retain(|start, _| addresses.contains(start));
+    }
+}
@@ -0,0 +1,28 @@
+//! Bookmarked addresses, listed in the `Locations` section to quickly return to them.
+use std::collections::BTreeSet;
+
+use crate::{Address, MemoryEditor};
+
+impl MemoryEditor {
+    /// Returns all bookmarked addresses.
+    ///
+    /// Rows can be bookmarked from the context menu of their address, after which they're listed in the `Locations`
+    /// section.
+    pub fn bookmarks(&self) -> &BTreeSet<Address> {
+        &self.bookmarks
+    }
+
+    /// Bookmark or remove the bookmark of the provided address.
+    pub fn set_bookmark(&mut self, address: Address, is_bookmarked: bool) {
+        if is_bookmarked {
+            self.bookmarks.insert(address);
+        } else {
+            self.bookmarks.remove(&address);
+        }
+    }
+
+    /// Remove all bookmarks.
+    pub fn clear_bookmarks(&mut self) {
+        self.bookmarks.clear();
+    }
+}
@@ -0,0 +1,359 @@
+//! Validating and fixing checksum fields, such as those found in save files and ROM headers.
+use std::ops::Range;
+
+use egui::Ui;
+
+use crate::labels::fill;
+use crate::task::ChunkedTask;
+use crate::{Address, MemoryEditor};
+
+/// The algorithm used to calculate a checksum.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) enum ChecksumKind {
+    /// The wrapping sum of all bytes, stored as one byte.
+    #[default]
+    Sum8,
+    /// The wrapping sum of all bytes, stored as two bytes.
+    Sum16,
+    /// All bytes xor-ed together, stored as one byte.
+    Xor8,
+    /// The standard `CRC-32`, stored as four bytes.
+    Crc32,
+}
+
+impl ChecksumKind {
+    pub fn iter() -> impl Iterator<Item = ChecksumKind> {
+        vec![
+            ChecksumKind::Sum8,
+            ChecksumKind::Sum16,
+            ChecksumKind::Xor8,
+            ChecksumKind::Crc32,
+        ]
+        .into_iter()
+    }
+
+    /// The amount of bytes the checksum takes up in memory.
+    pub fn size(&self) -> usize {
+        match self {
+            ChecksumKind::Sum8 | ChecksumKind::Xor8 => 1,
+            ChecksumKind::Sum16 => 2,
+            ChecksumKind::Crc32 => 4,
+        }
+    }
+
+    /// Continue the checksum `value` of earlier bytes with `bytes`, starting from `0`.
+    pub fn update(&self, value: u32, bytes: &[u8]) -> u32 {
+        match self {
+            ChecksumKind::Sum8 | ChecksumKind::Sum16 => {
+                bytes.iter().fold(value, |sum, &byte| sum.wrapping_add(byte as u32))
+            }
+            ChecksumKind::Xor8 => bytes.iter().fold(value, |xor, &byte| xor ^ byte as u32),
+            ChecksumKind::Crc32 => crate::utilities::crc32_update(value, bytes),
+        }
+    }
+
+    /// Encode the checksum `value` as it would be stored in memory.
+    pub fn encode(&self, value: u32, is_big_endian: bool) -> Vec<u8> {
+        let size = self.size();
+
+        if is_big_endian {
+            value.to_be_bytes()[4 - size..].to_vec()
+        } else {
+            value.to_le_bytes()[..size].to_vec()
+        }
+    }
+}
+
+/// The outcome of the last validation.
+#[derive(Clone, Debug)]
+pub(crate) struct ChecksumResult {
+    pub location: Address,
+    pub expected: Vec<u8>,
+    pub stored: Vec<Option<u8>>,
+}
+
+impl ChecksumResult {
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.expected
+            .iter()
+            .zip(&self.stored)
+            .all(|(&expected, &stored)| Some(expected) == stored)
+    }
+}
+
+/// A running validation, which calculates the checksum of the data a chunk at a time.
+#[derive(Clone, Debug)]
+pub(crate) struct ChecksumCalculation {
+    /// The addresses of the stored checksum, which fit within the region.
+    location: Range<Address>,
+    kind: ChecksumKind,
+    is_big_endian: bool,
+    /// The checksum of the data read so far.
+    value: u32,
+    pub task: ChunkedTask,
+}
+
+impl ChecksumCalculation {
+    /// Checksum the next chunk of the data, and return the result once all data was read.
+    pub fn step<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        budget: usize,
+    ) -> Option<ChecksumResult> {
+        if let Some(chunk) = self.task.next_chunk(budget) {
+            let bytes: Vec<u8> = chunk.map(|address| read_fn(mem, address).unwrap_or(0)).collect();
+            self.value = self.kind.update(self.value, &bytes);
+        }
+
+        self.task
+            .is_finished()
+            .then(|| self.result(self.location.clone().map(|address| read_fn(mem, address)).collect()))
+    }
+
+    /// Finish the validation at once, see [`MemoryEditor::complete_tasks_with_slice`].
+    pub fn complete_with_slice(&mut self, memory: &[u8], base: Address) -> ChecksumResult {
+        // Bounded chunks, such that the data is never all copied at once.
+        while let Some(chunk) = self.task.next_chunk(0x10000) {
+            let bytes: Vec<u8> = chunk
+                .map(|address| crate::scan::read_slice(memory, base, address).unwrap_or(0))
+                .collect();
+            self.value = self.kind.update(self.value, &bytes);
+        }
+
+        let stored = self
+            .location
+            .clone()
+            .map(|address| crate::scan::read_slice(memory, base, address))
+            .collect();
+        self.result(stored)
+    }
+
+    fn result(&self, stored: Vec<Option<u8>>) -> ChecksumResult {
+        ChecksumResult {
+            location: self.location.start,
+            expected: self.kind.encode(self.value, self.is_big_endian),
+            stored,
+        }
+    }
+}
+
+/// The UI state of the `Checksum` section.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ChecksumState {
+    pub data_start_string: String,
+    pub data_end_string: String,
+    pub location_string: String,
+    pub kind: ChecksumKind,
+    pub is_big_endian: bool,
+    pub error: Option<String>,
+    pub calculation: Option<ChecksumCalculation>,
+    pub result: Option<ChecksumResult>,
+}
+
+impl ChecksumState {
+    /// Checksum the next chunk of the data of the running validation, if any. Returns whether it's still running.
+    pub fn step<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        budget: usize,
+    ) -> bool {
+        let Some(calculation) = self.calculation.as_mut() else {
+            return false;
+        };
+        self.result = calculation.step(mem, read_fn, budget);
+        if self.result.is_some() {
+            self.calculation = None;
+        }
+
+        self.calculation.is_some()
+    }
+
+    /// Finish the running validation at once, see [`MemoryEditor::complete_tasks_with_slice`].
+    pub fn complete_with_slice(&mut self, memory: &[u8], base: Address) {
+        if let Some(mut calculation) = self.calculation.take() {
+            self.result = Some(calculation.complete_with_slice(memory, base));
+        }
+    }
+}
+
+impl MemoryEditor {
+    /// Draw the `Checksum` section, where a checksum over a data range can be validated against the stored one and
+    /// optionally be fixed through the `write_fn`.
+    pub(crate) fn draw_checksum<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        current_address_range: &Range<Address>,
+        mem: &mut T,
+        read: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        write: &mut Option<impl FnMut(&mut T, Address, u8)>,
+    ) {
+        egui::CollapsingHeader::new(&self.options.labels.checksum_title)
+            .default_open(false)
+            .show(ui, |ui| {
+                let selection = self.frame_data.selection();
+                let state = &mut self.checksum;
+                let labels = &self.options.labels;
+
+                egui::Grid::new("checksum_grid").show(ui, |ui| {
+                    ui.label(&labels.checksum_data);
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut state.data_start_string)
+                                .hint_text(&labels.start_hint)
+                                .desired_width(80.0),
+                        );
+                        ui.label("..=");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut state.data_end_string)
+                                .hint_text(&labels.end_hint)
+                                .desired_width(80.0),
+                        );
+
+                        if ui
+                            .add_enabled(selection.is_some(), egui::Button::new(&labels.use_selection))
+                            .on_hover_text(&labels.checksum_use_selection_hover)
+                            .clicked()
+                        {
+                            if let Some(selection) = &selection {
+                                state.data_start_string = format!("{:X}", selection.start);
+                                state.data_end_string = format!("{:X}", selection.end - 1);
+                            }
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(&labels.checksum_location);
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut state.location_string)
+                                .hint_text(&labels.address_hint)
+                                .desired_width(80.0),
+                        );
+
+                        egui::ComboBox::from_id_salt("checksum_kind")
+                            .selected_text(format!("{:?}", state.kind))
+                            .show_ui(ui, |ui| {
+                                for kind in ChecksumKind::iter() {
+                                    ui.selectable_value(&mut state.kind, kind, format!("{:?}", kind));
+                                }
+                            });
+
+                        ui.add_enabled(
+                            state.kind.size() > 1,
+                            egui::Checkbox::new(&mut state.is_big_endian, &labels.big_endian),
+                        );
+                    });
+                    ui.end_row();
+                });
+
+                if ui.button(&labels.validate).clicked() {
+                    let data = crate::utilities::parse_address(&state.data_start_string)
+                        .zip(crate::utilities::parse_address(&state.data_end_string))
+                        .filter(|(start, end)| start <= end)
+                        .map(|(start, end)| start..end.saturating_add(1));
+                    let location = crate::utilities::parse_address(&state.location_string);
+                    let range = format!("{:#X?}", current_address_range);
+                    state.result = None;
+                    state.calculation = None;
+
+                    match (data, location) {
+                        (Some(data), Some(location))
+                            if data.start >= current_address_range.start && data.end <= current_address_range.end =>
+                        {
+                            let location_end = location.checked_add(state.kind.size());
+                            if location >= current_address_range.start
+                                && location_end.is_some_and(|end| end <= current_address_range.end)
+                            {
+                                state.error = None;
+                                state.calculation = Some(ChecksumCalculation {
+                                    location: location..location + state.kind.size(),
+                                    kind: state.kind,
+                                    is_big_endian: state.is_big_endian,
+                                    value: 0,
+                                    task: ChunkedTask::new(data),
+                                });
+                            } else {
+                                state.error = Some(fill(
+                                    &labels.checksum_out_of_range,
+                                    &[("address", &format!("{:#X}", location)), ("range", &range)],
+                                ));
+                            }
+                        }
+                        _ => {
+                            state.error = Some(fill(&labels.checksum_invalid_input, &[("range", &range)]));
+                        }
+                    }
+                }
+
+                if let Some(error) = &state.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                if let Some(calculation) = &state.calculation {
+                    if calculation.task.draw_progress(ui, labels) {
+                        state.calculation = None;
+                    }
+                    return;
+                }
+
+                let Some(result) = &state.result else {
+                    return;
+                };
+                let to_hex = |bytes: &[Option<u8>]| {
+                    bytes
+                        .iter()
+                        .map(|byte| byte.map_or_else(|| "??".to_string(), |byte| format!("{:02X}", byte)))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                };
+                let expected: Vec<Option<u8>> = result.expected.iter().copied().map(Some).collect();
+
+                ui.label(fill(&labels.checksum_expected, &[("value", &to_hex(&expected))]));
+                ui.label(fill(
+                    &labels.checksum_stored,
+                    &[
+                        ("address", &format!("{:#X}", result.location)),
+                        ("value", &to_hex(&result.stored)),
+                    ],
+                ));
+
+                if result.is_valid() {
+                    ui.colored_label(ui.visuals().text_color(), &labels.checksum_valid);
+                    return;
+                }
+
+                ui.colored_label(ui.visuals().warn_fg_color, &labels.checksum_mismatch);
+
+                let Some(write) = write.as_mut() else {
+                    return;
+                };
+
+                if ui.button(&labels.fix_checksum).clicked() {
+                    let result = state.result.take().unwrap();
+
+                    self.history.begin_group();
+                    for (address, value) in (result.location..).zip(result.expected.iter().copied()) {
+                        if current_address_range.contains(&address) {
+                            self.write_value(mem, read, write, address, value);
+                        }
+                    }
+                    self.history.end_group();
+
+                    // Read back the stored checksum, in case the write didn't stick.
+                    let stored = (result.location..)
+                        .zip(&result.expected)
+                        .map(|(address, _)| {
+                            current_address_range
+                                .contains(&address)
+                                .then(|| read(mem, address))
+                                .flatten()
+                        })
+                        .collect();
+                    self.checksum.result = Some(ChecksumResult { stored, ..result });
+                }
+            });
+    }
+}
@@ -0,0 +1,135 @@
+//! Buffered reads for memory sources which can hand out contiguous slices.
+//!
+//! The editor still reads one byte at a time, and passes `mem` mutably to every read, so a slice borrowed from it can't
+//! be kept between reads. Instead each slice is copied into a small buffer, from which the following reads are served
+//! until the next call. This only saves calls into the host, the bytes are copied and read as with any `read_fn`.
+use std::cell::RefCell;
+
+use egui::{Context, Ui};
+
+use crate::{Address, EditorResponse, MemoryEditor};
+
+/// The amount of bytes requested from the `read_chunk_fn` at once, enough for several rows of the main UI.
+const CHUNK_SIZE: usize = 0x100;
+
+/// A copy of the last chunk handed out by a `read_chunk_fn`, from which subsequent reads are served.
+#[derive(Debug, Default)]
+struct ChunkCache {
+    start: Address,
+    bytes: Vec<u8>,
+}
+
+impl ChunkCache {
+    fn read<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_chunk_fn: &mut impl for<'a> FnMut(&'a mut T, Address, usize) -> Option<&'a [u8]>,
+        address: Address,
+    ) -> Option<u8> {
+        if let Some(&value) = address
+            .checked_sub(self.start)
+            .and_then(|offset| self.bytes.get(offset))
+        {
+            return Some(value);
+        }
+
+        let chunk = read_chunk_fn(mem, address, CHUNK_SIZE)?;
+        self.start = address;
+        self.bytes.clear();
+        self.bytes.extend_from_slice(&chunk[..chunk.len().min(CHUNK_SIZE)]);
+        self.bytes.first().copied()
+    }
+
+    fn invalidate(&mut self) {
+        self.bytes.clear();
+    }
+}
+
+impl MemoryEditor {
+    /// Create a read-only window for a memory source which can hand out contiguous slices.
+    ///
+    /// Rather than asking the host for every byte, the editor requests chunks of memory and copies them into a buffer,
+    /// which is dropped at the end of the call. This saves the per-byte lookups of the host, such as a page table walk,
+    /// but isn't zero-copy: every chunk is copied once, and every byte is still read individually from the buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The `egui` context.
+    /// * `mem` - The memory from which to read.
+    /// * `read_chunk_fn` - Any closure which takes in a reference to the memory, an address, and a length, and returns
+    ///   the bytes starting at that address. The slice can be shorter than the requested length, for example at the end
+    ///   of a memory mapping. It can return `None`, or an empty slice, if the address can't be read.
+    pub fn window_ui_buffered_read_only<T: ?Sized>(
+        &mut self,
+        ctx: &Context,
+        is_open: &mut bool,
+        mem: &mut T,
+        mut read_chunk_fn: impl for<'a> FnMut(&'a mut T, Address, usize) -> Option<&'a [u8]>,
+    ) -> EditorResponse {
+        let mut cache = ChunkCache::default();
+        self.window_ui_read_only(ctx, is_open, mem, |mem, address| {
+            cache.read(mem, &mut read_chunk_fn, address)
+        })
+    }
+
+    /// Create a window for a memory source which can hand out contiguous slices.
+    ///
+    /// See [`Self::window_ui_buffered_read_only`] for the `read_chunk_fn`, and [`Self::window_ui`] for the `write_fn`.
+    pub fn window_ui_buffered<T: ?Sized>(
+        &mut self,
+        ctx: &Context,
+        is_open: &mut bool,
+        mem: &mut T,
+        mut read_chunk_fn: impl for<'a> FnMut(&'a mut T, Address, usize) -> Option<&'a [u8]>,
+        mut write_fn: impl FnMut(&mut T, Address, u8),
+    ) -> EditorResponse {
+        // Writes invalidate the chunk, as it would otherwise show the old values for the rest of the frame.
+        let cache = RefCell::new(ChunkCache::default());
+        self.window_ui(
+            ctx,
+            is_open,
+            mem,
+            |mem, address| cache.borrow_mut().read(mem, &mut read_chunk_fn, address),
+            |mem, address, value| {
+                cache.borrow_mut().invalidate();
+                write_fn(mem, address, value)
+            },
+        )
+    }
+
+    /// Draws the memory viewer for a memory source which can hand out contiguous slices.
+    ///
+    /// See [`Self::window_ui_buffered_read_only`] for the `read_chunk_fn`.
+    /// This is the read-only variant. See [`Self::draw_editor_contents_buffered`] for the read-write variant.
+    pub fn draw_editor_contents_buffered_read_only<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        mem: &mut T,
+        mut read_chunk_fn: impl for<'a> FnMut(&'a mut T, Address, usize) -> Option<&'a [u8]>,
+    ) -> EditorResponse {
+        let mut cache = ChunkCache::default();
+        self.draw_editor_contents_read_only(ui, mem, |mem, address| cache.read(mem, &mut read_chunk_fn, address))
+    }
+
+    /// Draws the memory editor for a memory source which can hand out contiguous slices.
+    ///
+    /// See [`Self::window_ui_buffered_read_only`] for the `read_chunk_fn`.
+    pub fn draw_editor_contents_buffered<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        mem: &mut T,
+        mut read_chunk_fn: impl for<'a> FnMut(&'a mut T, Address, usize) -> Option<&'a [u8]>,
+        mut write_fn: impl FnMut(&mut T, Address, u8),
+    ) -> EditorResponse {
+        let cache = RefCell::new(ChunkCache::default());
+        self.draw_editor_contents(
+            ui,
+            mem,
+            |mem, address| cache.borrow_mut().read(mem, &mut read_chunk_fn, address),
+            |mem, address, value| {
+                cache.borrow_mut().invalidate();
+                write_fn(mem, address, value)
+            },
+        )
+    }
+}
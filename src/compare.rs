@@ -0,0 +1,245 @@
+//! Comparing memory against a reference, such as a file on disk, to find corrupted or modified bytes.
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use egui::{RichText, ScrollArea, Ui};
+
+use crate::labels::fill;
+use crate::task::ChunkedTask;
+use crate::{Address, MemoryEditor};
+
+/// Reference data to compare the memory of a region against.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Comparison {
+    /// Where the reference was loaded from, purely for display purposes.
+    pub source_name: String,
+    pub reference: Vec<u8>,
+    /// The region the comparison was last run against, the reference is compared from its start address onward.
+    pub region: String,
+    /// The first address of the region the comparison was started for, the reference's first byte maps to it.
+    pub base: Address,
+    pub mismatches: BTreeSet<Address>,
+    pub task: Option<ChunkedTask>,
+}
+
+impl Comparison {
+    /// Start comparing the reference against `address_space`, starting at its first address.
+    pub fn start(&mut self, region: String, address_space: &Range<Address>) {
+        let end = address_space
+            .start
+            .saturating_add(self.reference.len())
+            .min(address_space.end);
+
+        self.region = region;
+        self.base = address_space.start;
+        self.mismatches.clear();
+        self.task = Some(ChunkedTask::new(address_space.start..end));
+    }
+
+    /// Compare the next chunk of the running comparison, if there is one.
+    pub fn step<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        budget: usize,
+    ) {
+        let Some(task) = self.task.as_mut() else {
+            return;
+        };
+
+        if let Some(chunk) = task.next_chunk(budget) {
+            let base = self.base;
+            let reference = &self.reference;
+            self.mismatches
+                .extend(chunk.filter(|&address| read_fn(mem, address) != Some(reference[address - base])));
+        }
+
+        if task.is_finished() {
+            self.task = None;
+        }
+    }
+
+    /// Finish the running comparison at once, see [`MemoryEditor::complete_tasks_with_slice`].
+    pub fn complete_with_slice(&mut self, memory: &[u8], base: Address) {
+        let Some(mut task) = self.task.take() else {
+            return;
+        };
+        let remaining = task.take_remaining();
+        let reference = &self.reference[remaining.start - self.base..remaining.end - self.base];
+
+        // Whatever part of the range lies outside the slice is unreadable, and thus always mismatches.
+        let readable = remaining.start.max(base)..remaining.end.min(base.saturating_add(memory.len()));
+        self.mismatches
+            .extend(remaining.clone().filter(|address| !readable.contains(address)));
+
+        if !readable.is_empty() {
+            let memory = &memory[readable.start - base..readable.end - base];
+            let reference = &reference[readable.start - remaining.start..readable.end - remaining.start];
+            self.mismatches.extend(
+                crate::scan::find_mismatches(memory, reference)
+                    .into_iter()
+                    .map(|offset| readable.start + offset),
+            );
+        }
+    }
+
+    #[inline]
+    pub fn is_mismatch(&self, address: Address) -> bool {
+        self.mismatches.contains(&address)
+    }
+}
+
+/// The UI state of the `Compare` section.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ComparisonState {
+    pub path_string: String,
+    pub error: Option<String>,
+    pub comparison: Option<Comparison>,
+}
+
+impl MemoryEditor {
+    /// Set the reference data the memory of the currently selected region is compared against in the `Compare`
+    /// section of the UI.
+    ///
+    /// This is an alternative to loading a file from disk through the UI, for example on platforms without a
+    /// filesystem. The `name` is purely used for display.
+    pub fn set_comparison_reference(&mut self, name: impl Into<String>, reference: Vec<u8>) {
+        self.comparison.error = None;
+        self.comparison.comparison = Some(Comparison {
+            source_name: name.into(),
+            reference,
+            ..Default::default()
+        });
+    }
+
+    /// Returns all mismatching addresses found by the last comparison, if any was made.
+    pub fn comparison_mismatches(&self) -> Option<&BTreeSet<Address>> {
+        self.comparison
+            .comparison
+            .as_ref()
+            .map(|comparison| &comparison.mismatches)
+    }
+
+    /// Draw the `Compare` section, allowing a reference file to be loaded and compared with the current region.
+    pub(crate) fn draw_comparison(&mut self, ui: &mut Ui, current_address_range: &Range<Address>) {
+        egui::CollapsingHeader::new(&self.options.labels.compare_title)
+            .default_open(false)
+            .show(ui, |ui| {
+                let state = &mut self.comparison;
+                let labels = &self.options.labels;
+
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut state.path_string).hint_text(&labels.reference_path_hint))
+                        .on_hover_text(&labels.reference_path_hover);
+
+                    if ui.button(&labels.load).clicked() {
+                        match std::fs::read(&state.path_string) {
+                            Ok(reference) => {
+                                state.error = None;
+                                state.comparison = Some(Comparison {
+                                    source_name: state.path_string.clone(),
+                                    reference,
+                                    ..Default::default()
+                                });
+                            }
+                            Err(e) => state.error = Some(fill(&labels.load_failed, &[("error", &e)])),
+                        }
+                    }
+                });
+
+                if let Some(error) = &state.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                let fill_diff_value = &mut self.options.fill_diff_value;
+                ui.horizontal(|ui| {
+                    let mut is_enabled = fill_diff_value.is_some();
+                    if ui
+                        .checkbox(&mut is_enabled, &labels.fill_diff)
+                        .on_hover_text(&labels.fill_diff_hover)
+                        .changed()
+                    {
+                        *fill_diff_value = is_enabled.then_some(0x00);
+                    }
+
+                    if let Some(value) = fill_diff_value {
+                        ui.selectable_value(value, 0x00, "00");
+                        ui.selectable_value(value, 0xFF, "FF");
+                        ui.add(egui::DragValue::new(value).hexadecimal(2, false, true));
+                    }
+                });
+
+                let Some(comparison) = state.comparison.as_mut() else {
+                    return;
+                };
+
+                let mut close = false;
+                ui.horizontal(|ui| {
+                    ui.label(fill(
+                        &labels.reference,
+                        &[
+                            ("name", &comparison.source_name),
+                            ("length", &comparison.reference.len()),
+                        ],
+                    ));
+
+                    if ui.button(&labels.compare).clicked() {
+                        comparison.start(self.options.selected_address_range.clone(), current_address_range);
+                    }
+
+                    close = ui.button(&labels.close).clicked();
+                });
+
+                if close {
+                    state.comparison = None;
+                    return;
+                }
+
+                if let Some(task) = &comparison.task {
+                    if task.draw_progress(ui, labels) {
+                        comparison.task = None;
+                    }
+                }
+
+                // Only show results for the region they were computed for.
+                if comparison.region != self.options.selected_address_range {
+                    return;
+                }
+
+                ui.label(fill(
+                    &labels.mismatches,
+                    &[("count", &comparison.mismatches.len()), ("region", &comparison.region)],
+                ));
+
+                let mut goto = None;
+                let value_font = self.options.value_font(ui.style());
+                let row_height = ui.fonts(|fonts| fonts.row_height(&value_font));
+
+                ScrollArea::vertical()
+                    .id_salt("comparison_mismatches")
+                    .max_height(row_height * 8.0)
+                    .show_rows(ui, row_height, comparison.mismatches.len(), |ui, rows| {
+                        for &address in comparison.mismatches.iter().skip(rows.start).take(rows.len()) {
+                            let expected = comparison.reference[address - comparison.base];
+                            let text = RichText::new(fill(
+                                &labels.mismatch_expected,
+                                &[
+                                    ("address", &format!("{:#X}", address)),
+                                    ("value", &format!("{:02X}", expected)),
+                                ],
+                            ))
+                            .font(value_font.clone());
+
+                            if ui.link(text).clicked() {
+                                goto = Some(address);
+                            }
+                        }
+                    });
+
+                if let Some(address) = goto {
+                    self.frame_data
+                        .set_goto_address(address, current_address_range, self.column_count());
+                }
+            });
+    }
+}
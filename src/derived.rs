@@ -0,0 +1,30 @@
+//! Columns of text the host derives from each row, such as decoded attributes, shown after the ASCII sidebar.
+use std::ops::Range;
+
+use egui::{RichText, Ui};
+
+use crate::{Address, MemoryEditor};
+
+impl MemoryEditor {
+    /// Draw the cell of the derived column at `index` for the row starting at `start_address`.
+    pub(crate) fn draw_derived_column<T: ?Sized>(
+        &self,
+        ui: &mut Ui,
+        index: usize,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        start_address: Address,
+        address_space: &Range<Address>,
+    ) {
+        let (name, column) = &self.derived_columns[index];
+        let mut read = |address: Address| {
+            address_space
+                .contains(&address)
+                .then(|| read_fn(mem, address))
+                .flatten()
+        };
+        let text = RichText::new(column(start_address, &mut read)).font(self.options.address_font(ui.style()));
+
+        ui.label(text).on_hover_text(name);
+    }
+}
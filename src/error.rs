@@ -0,0 +1,137 @@
+//! Errors for a misconfigured editor, such that invalid options show a warning or return an error instead of
+//! panicking.
+use std::ops::Range;
+
+use egui::{Context, Ui};
+
+use crate::labels::fill;
+use crate::{Address, EditorParts, EditorResponse, MemoryEditor};
+
+/// The reason the editor couldn't be drawn, see [`MemoryEditor::try_draw_editor_contents`] and
+/// [`MemoryEditor::try_window_ui`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MemoryEditorError {
+    /// No address range was added, see [`MemoryEditor::with_address_range`].
+    NoAddressRanges,
+    /// The selected address range doesn't contain any addresses, for example because its start lies after its end.
+    EmptyAddressRange { name: String, range: Range<Address> },
+}
+
+impl std::fmt::Display for MemoryEditorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemoryEditorError::NoAddressRanges => write!(f, "no address range was added"),
+            MemoryEditorError::EmptyAddressRange { name, range } => {
+                write!(
+                    f,
+                    "the address range `{}` ({:#X}..{:#X}) is empty",
+                    name, range.start, range.end
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for MemoryEditorError {}
+
+impl MemoryEditor {
+    /// Create a window and render the memory editor contents within, returning an error instead of drawing a warning
+    /// if it can't be drawn.
+    ///
+    /// See [`Self::window_ui`].
+    pub fn try_window_ui<T: ?Sized>(
+        &mut self,
+        ctx: &Context,
+        is_open: &mut bool,
+        mem: &mut T,
+        read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
+        write_fn: impl FnMut(&mut T, Address, u8),
+    ) -> Result<EditorResponse, MemoryEditorError> {
+        self.show_window(ctx, is_open, |editor, ui| {
+            editor.try_draw_editor_contents_impl(ui, mem, read_fn, Some(write_fn), EditorParts::All)
+        })
+        .unwrap_or_else(|| Ok(EditorResponse::default()))
+    }
+
+    /// Create a read-only window, returning an error instead of drawing a warning if it can't be drawn.
+    ///
+    /// See [`Self::window_ui_read_only`].
+    pub fn try_window_ui_read_only<T: ?Sized>(
+        &mut self,
+        ctx: &Context,
+        is_open: &mut bool,
+        mem: &mut T,
+        read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
+    ) -> Result<EditorResponse, MemoryEditorError> {
+        type DummyWriteFunction<T> = fn(&mut T, Address, u8);
+
+        self.show_window(ctx, is_open, |editor, ui| {
+            editor.try_draw_editor_contents_impl(ui, mem, read_fn, None::<DummyWriteFunction<T>>, EditorParts::All)
+        })
+        .unwrap_or_else(|| Ok(EditorResponse::default()))
+    }
+
+    /// Draws the memory editor, returning an error instead of drawing a warning if it can't be drawn.
+    ///
+    /// See [`Self::draw_editor_contents`].
+    pub fn try_draw_editor_contents<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        mem: &mut T,
+        read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
+        write_fn: impl FnMut(&mut T, Address, u8),
+    ) -> Result<EditorResponse, MemoryEditorError> {
+        self.try_draw_editor_contents_impl(ui, mem, read_fn, Some(write_fn), EditorParts::All)
+    }
+
+    /// Draws the memory viewer, returning an error instead of drawing a warning if it can't be drawn.
+    ///
+    /// See [`Self::draw_editor_contents_read_only`].
+    pub fn try_draw_editor_contents_read_only<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        mem: &mut T,
+        read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
+    ) -> Result<EditorResponse, MemoryEditorError> {
+        type DummyWriteFunction<T> = fn(&mut T, Address, u8);
+
+        self.try_draw_editor_contents_impl(ui, mem, read_fn, None::<DummyWriteFunction<T>>, EditorParts::All)
+    }
+
+    /// Repair the options which can be repaired, and return the selected address range if it can be drawn.
+    ///
+    /// A column count of `0` is clamped, and an unknown selected range, such as from restored options, falls back to
+    /// the first range.
+    pub(crate) fn validate_options(&mut self) -> Result<Range<Address>, MemoryEditorError> {
+        if self.options.column_count == 0 {
+            self.reset_layout();
+        }
+
+        if !self.address_ranges.contains_key(&self.options.selected_address_range) {
+            let (name, _) = self
+                .address_ranges
+                .iter()
+                .next()
+                .ok_or(MemoryEditorError::NoAddressRanges)?;
+            self.options.selected_address_range = name.clone();
+            self.reset_layout();
+        }
+
+        let name = &self.options.selected_address_range;
+        let range = self.address_ranges[name].clone();
+        if range.is_empty() {
+            return Err(MemoryEditorError::EmptyAddressRange {
+                name: name.clone(),
+                range,
+            });
+        }
+
+        Ok(range)
+    }
+
+    /// Draw a warning in place of the editor contents.
+    pub(crate) fn draw_error(&self, ui: &mut Ui, error: &MemoryEditorError) {
+        let text = fill(&self.options.labels.draw_error, &[("error", error)]);
+        ui.colored_label(ui.style().visuals.error_fg_color, text);
+    }
+}
@@ -0,0 +1,70 @@
+//! Feedback on input the editor rejected, such that failed edits aren't silently ignored.
+use egui::{Rect, Stroke, Ui, Vec2};
+
+use crate::{Address, MemoryEditor};
+
+/// How long the cell of a rejected input is outlined for, in seconds.
+const REJECTED_INPUT_SECONDS: f64 = 0.3;
+/// The furthest, in points, the outline moves sideways while shaking.
+const SHAKE_DISTANCE: f32 = 2.0;
+
+/// Input the editor rejected, passed to the callback of [`MemoryEditor::with_rejected_input_callback`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RejectedInput {
+    /// A character which isn't a hex digit was typed into the value at `address`.
+    InvalidCharacter { address: Address, character: char },
+    /// A write to `address` was dropped as the address isn't editable, see [`MemoryEditor::with_edit_permission`].
+    NotEditable { address: Address },
+    /// Text pasted at `address` wasn't a list of hex bytes, see [`crate::utilities::parse_pasted_bytes`].
+    InvalidPaste { address: Address },
+    /// Bytes pasted at `address` didn't fit in the region, in which case none of them are written.
+    OutOfRange { address: Address },
+}
+
+impl RejectedInput {
+    /// The address the rejected input was meant for.
+    pub fn address(&self) -> Address {
+        match *self {
+            RejectedInput::InvalidCharacter { address, .. }
+            | RejectedInput::NotEditable { address }
+            | RejectedInput::InvalidPaste { address }
+            | RejectedInput::OutOfRange { address } => address,
+        }
+    }
+}
+
+impl MemoryEditor {
+    /// Show the rejection on the cell of its address, and notify the host.
+    pub(crate) fn reject_input(&mut self, rejection: RejectedInput) {
+        if self.options.show_rejected_input {
+            let time = self.frame_data.last_frame_time.unwrap_or_default();
+            self.frame_data.rejected_input = Some((rejection.address(), time));
+        }
+
+        if let Some(callback) = &self.rejected_input_callback {
+            callback(rejection);
+        }
+    }
+}
+
+/// Outline the cell at `rect` with a brief shake if the input for its `address` was rejected recently.
+///
+/// `rejected_input` is the address and time of the last rejection.
+pub(crate) fn paint_rejected_input(ui: &Ui, rejected_input: Option<(Address, f64)>, address: Address, rect: Rect) {
+    let Some((rejected_address, time)) = rejected_input else {
+        return;
+    };
+    let elapsed = ui.input(|i| i.time) - time;
+    if rejected_address != address || elapsed >= REJECTED_INPUT_SECONDS {
+        return;
+    }
+
+    // A quick back and forth which settles down towards the end.
+    let remaining = (1.0 - elapsed / REJECTED_INPUT_SECONDS) as f32;
+    let offset = (elapsed * 60.0).sin() as f32 * SHAKE_DISTANCE * remaining;
+    let stroke = Stroke::new(1.0, ui.visuals().error_fg_color.gamma_multiply(remaining));
+    ui.painter()
+        .rect_stroke(rect.expand(1.0).translate(Vec2::new(offset, 0.0)), 2.0, stroke);
+    ui.ctx().request_repaint();
+}
@@ -0,0 +1,51 @@
+//! Files dropped onto the editor, see [`crate::option_data::MemoryEditorOptions::accept_dropped_files`].
+use std::sync::Arc;
+
+use egui::{DroppedFile, Rect, Stroke, Ui};
+
+use crate::MemoryEditor;
+
+/// Read the contents of a file dropped onto the editor, see [`crate::EditorResponse::dropped_files`].
+///
+/// Web backends provide the bytes directly, native backends only provide the path, which is then read from disk.
+pub fn read_dropped_file(file: &DroppedFile) -> std::io::Result<Arc<[u8]>> {
+    if let Some(bytes) = &file.bytes {
+        return Ok(bytes.clone());
+    }
+
+    match &file.path {
+        Some(path) => std::fs::read(path).map(Into::into),
+        None => Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("the dropped file `{}` has neither a path nor its contents", file.name),
+        )),
+    }
+}
+
+impl MemoryEditor {
+    /// Outline the editor while files are dragged over it, and return the files dropped onto `rect` this frame.
+    pub(crate) fn handle_dropped_files(&self, ui: &Ui, rect: Rect) -> Vec<DroppedFile> {
+        if !self.options.accept_dropped_files {
+            return Vec::new();
+        }
+
+        // Not every backend reports the pointer while dragging files, in which case any drop is accepted.
+        let (is_hovering, dropped_files) = ui.input(|i| {
+            let is_over_editor = i.pointer.hover_pos().is_none_or(|pos| rect.contains(pos));
+            let dropped_files = if is_over_editor {
+                i.raw.dropped_files.clone()
+            } else {
+                Vec::new()
+            };
+
+            (is_over_editor && !i.raw.hovered_files.is_empty(), dropped_files)
+        });
+
+        if is_hovering {
+            let stroke = Stroke::new(2.0, ui.visuals().selection.stroke.color);
+            ui.painter().rect_stroke(rect, ui.visuals().window_rounding, stroke);
+        }
+
+        dropped_files
+    }
+}
@@ -0,0 +1,237 @@
+//! Hashes of the selected bytes, for comparing data against other tools without leaving the editor.
+use std::ops::Range;
+
+use egui::Ui;
+
+use crate::labels::fill;
+use crate::task::ChunkedTask;
+use crate::{Address, MemoryEditor};
+
+/// The algorithms shown in the `Hashes` section.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum HashKind {
+    /// The `CRC-16/CCITT-FALSE`, see [`crate::utilities::crc16`].
+    Crc16,
+    /// The standard `CRC-32`, see [`crate::utilities::crc32`].
+    Crc32,
+    #[cfg(feature = "hashes")]
+    Md5,
+    #[cfg(feature = "hashes")]
+    Sha1,
+}
+
+impl HashKind {
+    pub fn iter() -> impl Iterator<Item = HashKind> {
+        vec![
+            HashKind::Crc16,
+            HashKind::Crc32,
+            #[cfg(feature = "hashes")]
+            HashKind::Md5,
+            #[cfg(feature = "hashes")]
+            HashKind::Sha1,
+        ]
+        .into_iter()
+    }
+
+    /// The name shown in front of the hash.
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashKind::Crc16 => "CRC-16",
+            HashKind::Crc32 => "CRC-32",
+            #[cfg(feature = "hashes")]
+            HashKind::Md5 => "MD5",
+            #[cfg(feature = "hashes")]
+            HashKind::Sha1 => "SHA-1",
+        }
+    }
+
+    fn hasher(&self) -> Hasher {
+        match self {
+            HashKind::Crc16 => Hasher::Crc16(crate::utilities::crc16(&[])),
+            HashKind::Crc32 => Hasher::Crc32(crate::utilities::crc32(&[])),
+            #[cfg(feature = "hashes")]
+            HashKind::Md5 => Hasher::Md5(<md5::Md5 as md5::Digest>::new()),
+            #[cfg(feature = "hashes")]
+            HashKind::Sha1 => Hasher::Sha1(<sha1::Sha1 as sha1::Digest>::new()),
+        }
+    }
+}
+
+/// The running state of a [`HashKind`], fed one chunk at a time.
+#[derive(Clone)]
+enum Hasher {
+    Crc16(u16),
+    Crc32(u32),
+    #[cfg(feature = "hashes")]
+    Md5(md5::Md5),
+    #[cfg(feature = "hashes")]
+    Sha1(sha1::Sha1),
+}
+
+impl Hasher {
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Hasher::Crc16(crc) => *crc = crate::utilities::crc16_update(*crc, bytes),
+            Hasher::Crc32(crc) => *crc = crate::utilities::crc32_update(*crc, bytes),
+            #[cfg(feature = "hashes")]
+            Hasher::Md5(md5) => md5::Digest::update(md5, bytes),
+            #[cfg(feature = "hashes")]
+            Hasher::Sha1(sha1) => sha1::Digest::update(sha1, bytes),
+        }
+    }
+
+    /// The hash as uppercase hex, with the CRCs as a number rather than their bytes.
+    fn finish(self) -> String {
+        match self {
+            Hasher::Crc16(crc) => format!("{:04X}", crc),
+            Hasher::Crc32(crc) => format!("{:08X}", crc),
+            #[cfg(feature = "hashes")]
+            Hasher::Md5(md5) => format!("{:X}", md5::Digest::finalize(md5)),
+            #[cfg(feature = "hashes")]
+            Hasher::Sha1(sha1) => format!("{:X}", sha1::Digest::finalize(sha1)),
+        }
+    }
+}
+
+/// The hashes of the bytes in `addresses`, calculated a chunk at a time as the selection can be large.
+#[derive(Clone)]
+pub(crate) struct HashCalculation {
+    pub addresses: Range<Address>,
+    /// The amount of addresses which couldn't be read, and were hashed as `0`.
+    pub unreadable: usize,
+    hashers: Vec<(HashKind, Hasher)>,
+    /// The finished hashes, empty while the task is running.
+    pub hashes: Vec<(HashKind, String)>,
+    pub task: Option<ChunkedTask>,
+}
+
+impl HashCalculation {
+    pub fn new(addresses: Range<Address>) -> Self {
+        HashCalculation {
+            task: Some(ChunkedTask::new(addresses.clone())),
+            addresses,
+            unreadable: 0,
+            hashers: HashKind::iter().map(|kind| (kind, kind.hasher())).collect(),
+            hashes: Vec::new(),
+        }
+    }
+
+    /// Hash the next chunk of the range, if there is one.
+    pub fn step<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        budget: usize,
+    ) {
+        let Some(task) = self.task.as_mut() else {
+            return;
+        };
+        let chunk = task.next_chunk(budget);
+        let is_finished = task.is_finished();
+
+        if let Some(chunk) = chunk {
+            self.feed(chunk.map(|address| read_fn(mem, address)));
+        }
+
+        if is_finished {
+            self.finish();
+        }
+    }
+
+    /// Finish the running calculation at once, see [`MemoryEditor::complete_tasks_with_slice`].
+    pub fn complete_with_slice(&mut self, memory: &[u8], base: Address) {
+        let Some(mut task) = self.task.take() else {
+            return;
+        };
+
+        // Bounded chunks, such that the bytes are never all copied at once.
+        while let Some(chunk) = task.next_chunk(0x10000) {
+            self.feed(chunk.map(|address| crate::scan::read_slice(memory, base, address)));
+        }
+
+        self.finish();
+    }
+
+    fn feed(&mut self, values: impl Iterator<Item = Option<u8>>) {
+        let bytes: Vec<u8> = values
+            .map(|value| {
+                value.unwrap_or_else(|| {
+                    self.unreadable += 1;
+                    0
+                })
+            })
+            .collect();
+
+        for (_, hasher) in &mut self.hashers {
+            hasher.update(&bytes);
+        }
+    }
+
+    fn finish(&mut self) {
+        self.hashes = std::mem::take(&mut self.hashers)
+            .into_iter()
+            .map(|(kind, hasher)| (kind, hasher.finish()))
+            .collect();
+        self.task = None;
+    }
+}
+
+impl MemoryEditor {
+    /// Draw the `Hashes` section, where the hashes of the selected bytes can be calculated and copied.
+    pub(crate) fn draw_hashes(&mut self, ui: &mut Ui, current_address_range: &Range<Address>) {
+        let labels = &self.options.labels;
+
+        egui::CollapsingHeader::new(&labels.hashes_title)
+            .default_open(false)
+            .show(ui, |ui| {
+                let selection = self.frame_data.selection().map(|selection| {
+                    selection.start.max(current_address_range.start)..selection.end.min(current_address_range.end)
+                });
+
+                let is_calculated = ui
+                    .add_enabled(selection.is_some(), egui::Button::new(&labels.calculate_hashes))
+                    .on_hover_text(&labels.calculate_hashes_hover)
+                    .clicked();
+                if let Some(addresses) = selection.filter(|_| is_calculated) {
+                    self.hashes = Some(HashCalculation::new(addresses));
+                }
+
+                let Some(calculation) = &self.hashes else {
+                    ui.label(&labels.no_hashes);
+                    return;
+                };
+
+                if let Some(task) = &calculation.task {
+                    if task.draw_progress(ui, labels) {
+                        self.hashes = None;
+                    }
+                    return;
+                }
+
+                ui.label(fill(
+                    &labels.hashes_summary,
+                    &[
+                        ("count", &format!("{:#X}", calculation.addresses.len())),
+                        ("range", &format!("{:#X?}", calculation.addresses)),
+                    ],
+                ));
+                if calculation.unreadable > 0 {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        fill(&labels.hashes_unreadable, &[("count", &calculation.unreadable)]),
+                    );
+                }
+
+                egui::Grid::new("hashes_grid").show(ui, |ui| {
+                    for (kind, hash) in &calculation.hashes {
+                        ui.label(kind.name());
+                        ui.monospace(hash);
+                        if ui.small_button("📋").on_hover_text(&labels.copy_hash_hover).clicked() {
+                            ui.ctx().copy_text(hash.clone());
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+}
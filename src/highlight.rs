@@ -0,0 +1,85 @@
+//! Highlights pushed by the host, such as the address of the last write, which can optionally expire.
+use std::ops::Range;
+use std::time::Duration;
+
+use egui::{Color32, Context};
+
+use crate::{Address, MemoryEditor};
+
+#[derive(Clone, Debug)]
+pub(crate) struct HostHighlight {
+    pub addresses: Range<Address>,
+    pub colour: Color32,
+    pub time_to_live: Option<Duration>,
+    /// The `egui` time at which the highlight expires, determined on the first frame it's shown.
+    pub expires_at: Option<f64>,
+    /// The current opacity of the highlight, used to fade out expiring highlights.
+    pub opacity: f32,
+}
+
+impl MemoryEditor {
+    /// Highlight the provided addresses with a background colour until removed with [`Self::clear_highlights`].
+    pub fn add_highlight(&mut self, addresses: Range<Address>, colour: Color32) {
+        self.highlights.push(HostHighlight {
+            addresses,
+            colour,
+            time_to_live: None,
+            expires_at: None,
+            opacity: 1.0,
+        });
+    }
+
+    /// Highlight the provided addresses with a background colour, which fades out over `time_to_live`.
+    ///
+    /// Useful for transient events, such as the last address written to by the emulated CPU.
+    pub fn add_expiring_highlight(&mut self, addresses: Range<Address>, colour: Color32, time_to_live: Duration) {
+        self.highlights.push(HostHighlight {
+            addresses,
+            colour,
+            time_to_live: Some(time_to_live),
+            expires_at: None,
+            opacity: 1.0,
+        });
+    }
+
+    /// Remove all highlights added with [`Self::add_highlight`] and [`Self::add_expiring_highlight`].
+    pub fn clear_highlights(&mut self) {
+        self.highlights.clear();
+    }
+
+    /// Fade out expiring highlights, and remove the ones which have expired.
+    pub(crate) fn update_highlights(&mut self, ctx: &Context) {
+        let now = ctx.input(|i| i.time);
+        let mut is_fading = false;
+
+        self.highlights.retain_mut(|highlight| {
+            let Some(time_to_live) = highlight.time_to_live else {
+                return true;
+            };
+            let time_to_live = time_to_live.as_secs_f64();
+            let expires_at = *highlight.expires_at.get_or_insert(now + time_to_live);
+
+            if time_to_live > 0.0 {
+                highlight.opacity = ((expires_at - now) / time_to_live).clamp(0.0, 1.0) as f32;
+            }
+            is_fading = true;
+
+            now < expires_at
+        });
+
+        if is_fading {
+            ctx.request_repaint();
+        }
+    }
+
+    /// The background colour of the host highlight covering `address`, if there is one.
+    ///
+    /// Later highlights take precedence over earlier ones.
+    pub(crate) fn host_highlight_colour(highlights: &[HostHighlight], address: Address) -> Option<Color32> {
+        highlights
+            .iter()
+            .rev()
+            .find(|highlight| highlight.addresses.contains(&address))
+            .map(|highlight| highlight.colour.gamma_multiply(highlight.opacity))
+    }
+}
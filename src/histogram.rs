@@ -0,0 +1,131 @@
+//! A histogram of the byte values in a range, which quickly reveals whether data is text, code, or compressed.
+use std::ops::Range;
+
+use egui::Ui;
+use egui_plot::{Bar, BarChart, Plot};
+
+use crate::labels::fill;
+use crate::task::ChunkedTask;
+use crate::utilities::entropy;
+use crate::{Address, MemoryEditor};
+
+/// The byte frequencies of a range, counted a chunk at a time. Once counted the range is counted again, such that the
+/// histogram follows changes to the memory.
+#[derive(Clone, Debug)]
+pub(crate) struct Histogram {
+    pub addresses: Range<Address>,
+    /// The counts of the last finished pass, `None` during the first one.
+    pub counts: Option<[usize; 256]>,
+    /// The counts of the running pass.
+    counting: [usize; 256],
+    pub task: ChunkedTask,
+}
+
+impl Histogram {
+    pub fn new(addresses: Range<Address>) -> Self {
+        Histogram {
+            task: ChunkedTask::new(addresses.clone()),
+            addresses,
+            counts: None,
+            counting: [0; 256],
+        }
+    }
+
+    /// Count the next chunk of the range, unreadable addresses are skipped.
+    pub fn step<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        budget: usize,
+    ) {
+        if let Some(chunk) = self.task.next_chunk(budget) {
+            for value in chunk.filter_map(|address| read_fn(mem, address)) {
+                self.counting[value as usize] += 1;
+            }
+        }
+
+        if self.task.is_finished() {
+            self.finish_pass();
+        }
+    }
+
+    /// Count the rest of the range at once, see [`MemoryEditor::complete_tasks_with_slice`].
+    pub fn complete_with_slice(&mut self, memory: &[u8], base: Address) {
+        let remaining = self.task.take_remaining();
+        for value in remaining.filter_map(|address| crate::scan::read_slice(memory, base, address)) {
+            self.counting[value as usize] += 1;
+        }
+
+        self.finish_pass();
+    }
+
+    fn finish_pass(&mut self) {
+        self.counts = Some(std::mem::replace(&mut self.counting, [0; 256]));
+        self.task = ChunkedTask::new(self.addresses.clone());
+    }
+}
+
+impl MemoryEditor {
+    /// Draw the `Histogram` section, showing the frequency of every byte value in the selection, or the visible
+    /// range if at most one byte is selected.
+    ///
+    /// The bytes are counted by [`Histogram`] while the section is open.
+    pub(crate) fn draw_histogram(&mut self, ui: &mut Ui, current_address_range: &Range<Address>) {
+        let labels = &self.options.labels;
+
+        let response = egui::CollapsingHeader::new(&labels.histogram_title)
+            .default_open(false)
+            .show(ui, |ui| {
+                let (source, addresses) = match self.frame_data.selection().filter(|selection| selection.len() > 1) {
+                    Some(selection) => (&labels.histogram_selection, selection),
+                    None => (&labels.histogram_visible_range, self.visible.addresses.clone()),
+                };
+                let addresses =
+                    addresses.start.max(current_address_range.start)..addresses.end.min(current_address_range.end);
+
+                let histogram = match &mut self.histogram {
+                    Some(histogram) if histogram.addresses == addresses => histogram,
+                    histogram => histogram.insert(Histogram::new(addresses.clone())),
+                };
+                let Some(counts) = &histogram.counts else {
+                    ui.add(egui::ProgressBar::new(histogram.task.progress()).show_percentage());
+                    return;
+                };
+
+                ui.label(fill(
+                    &labels.histogram_summary,
+                    &[
+                        ("count", &format!("{:#X}", addresses.len())),
+                        ("source", source),
+                        ("range", &format!("{:#X?}", addresses)),
+                        ("entropy", &format!("{:.2}", entropy(counts))),
+                    ],
+                ));
+
+                let bars = counts
+                    .iter()
+                    .enumerate()
+                    .map(|(value, &count)| {
+                        Bar::new(value as f64, count as f64)
+                            .width(1.0)
+                            .name(format!("{:02X}", value))
+                    })
+                    .collect();
+
+                Plot::new("byte_histogram")
+                    .height(120.0)
+                    .allow_scroll(false)
+                    .allow_drag(false)
+                    .allow_zoom(false)
+                    .include_x(0.0)
+                    .include_x(255.0)
+                    .include_y(0.0)
+                    .show(ui, |plot_ui| plot_ui.bar_chart(BarChart::new(bars)));
+            });
+
+        // Nothing is counted while the section is collapsed.
+        if response.body_returned.is_none() {
+            self.histogram = None;
+        }
+    }
+}
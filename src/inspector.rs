@@ -0,0 +1,91 @@
+//! A standalone version of the data preview, interpreting a few bytes as every supported number type.
+use egui::{Id, Response, RichText, Ui, Widget};
+
+use crate::option_data::{DataFormatType, DataPreviewOptions, Endianness};
+use crate::Address;
+
+/// The most bytes any [`DataFormatType`] needs.
+const MAX_BYTES: usize = 8;
+
+/// Shows the value of some bytes as every [`DataFormatType`], like the `Data preview` of the editor, for use outside
+/// of it, such as in a register panel.
+///
+/// Formats which need more bytes than provided, or which include an unreadable byte, show `--`.
+///
+/// ```no_run
+/// # use egui_memory_editor::DataInspector;
+/// # use egui_memory_editor::option_data::Endianness;
+/// # let ui: &mut egui::Ui = todo!();
+/// let register = 0xDEADBEEFu32;
+/// ui.add(DataInspector::new(&register.to_le_bytes()).endianness(Endianness::Little).id_salt("EAX"));
+/// ```
+pub struct DataInspector {
+    values: Vec<Option<u8>>,
+    endianness: Endianness,
+    id_salt: Id,
+}
+
+impl DataInspector {
+    /// Interpret `bytes`, only the first eight bytes are used.
+    pub fn new(bytes: &[u8]) -> Self {
+        Self::from_values(bytes.iter().copied().map(Some))
+    }
+
+    /// Interpret the bytes starting at `address`, read through `read_fn` like the memory of the editor.
+    pub fn from_read_fn<T: ?Sized>(
+        mem: &mut T,
+        mut read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
+        address: Address,
+    ) -> Self {
+        Self::from_values((address..address.saturating_add(MAX_BYTES)).map(|address| read_fn(mem, address)))
+    }
+
+    fn from_values(values: impl IntoIterator<Item = Option<u8>>) -> Self {
+        DataInspector {
+            values: values.into_iter().take(MAX_BYTES).collect(),
+            endianness: DataPreviewOptions::default().selected_endianness,
+            id_salt: Id::new("data_inspector"),
+        }
+    }
+
+    /// The byte order to interpret the bytes with, little endian by default.
+    pub fn endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Has to be unique when several inspectors are shown in the same `Ui`.
+    pub fn id_salt(mut self, id_salt: impl std::hash::Hash) -> Self {
+        self.id_salt = Id::new(id_salt);
+        self
+    }
+}
+
+impl Widget for DataInspector {
+    fn ui(self, ui: &mut Ui) -> Response {
+        egui::Grid::new(self.id_salt)
+            .striped(true)
+            .show(ui, |ui| {
+                for format in DataFormatType::iter() {
+                    let bytes: Option<Vec<u8>> = self
+                        .values
+                        .get(..format.bytes_to_read())
+                        .and_then(|values| values.iter().copied().collect());
+                    let value = bytes
+                        .and_then(|bytes| {
+                            let data_preview = DataPreviewOptions {
+                                selected_endianness: self.endianness,
+                                selected_data_format: format,
+                            };
+                            crate::utilities::bytes_to_value_string(data_preview, &bytes)
+                        })
+                        .unwrap_or_else(|| "--".to_string());
+
+                    ui.label(format!("{:?}", format));
+                    ui.label(RichText::new(value).monospace());
+                    ui.end_row();
+                }
+            })
+            .response
+    }
+}
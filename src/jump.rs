@@ -0,0 +1,167 @@
+//! A heuristic jump past uniform padding to the next byte which looks like real content.
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use crate::summary::RegionSummary;
+use crate::task::ChunkedTask;
+use crate::{Address, MemoryEditor};
+
+/// A running search for the next interesting byte: one which differs from both of its neighbours and from the most
+/// common byte of its region, the likely filler value.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct InterestingByteJump {
+    /// The region the jump was started in.
+    pub region: String,
+    pub address_space: Range<Address>,
+    /// The last three addresses and their values, the middle one is the candidate.
+    window: VecDeque<(Address, Option<u8>)>,
+    pub found: Option<Address>,
+    pub task: Option<ChunkedTask>,
+}
+
+impl InterestingByteJump {
+    /// Search for the first interesting byte after `from`.
+    pub fn new(region: String, address_space: Range<Address>, from: Address) -> Self {
+        InterestingByteJump {
+            region,
+            task: Some(ChunkedTask::new(from..address_space.end)),
+            address_space,
+            ..Default::default()
+        }
+    }
+
+    /// Search the next chunk, once the summary of the region determined its filler value.
+    fn step<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        budget: usize,
+        filler: Option<u8>,
+    ) {
+        let Some(task) = self.task.as_mut() else {
+            return;
+        };
+        let chunk = task.next_chunk(budget);
+
+        for address in chunk.into_iter().flatten() {
+            let value = read_fn(mem, address);
+            if self.feed(address, value, filler) {
+                break;
+            }
+        }
+
+        if self.found.is_some() || self.task.as_ref().is_some_and(|task| task.is_finished()) {
+            self.task = None;
+        }
+    }
+
+    /// Finish the search at once, see [`MemoryEditor::complete_tasks_with_slice`].
+    fn complete_with_slice(&mut self, memory: &[u8], base: Address, filler: Option<u8>) {
+        let Some(mut task) = self.task.take() else {
+            return;
+        };
+
+        for address in task.take_remaining() {
+            if self.feed(address, crate::scan::read_slice(memory, base, address), filler) {
+                break;
+            }
+        }
+    }
+
+    /// Returns `true` once an interesting byte was found.
+    fn feed(&mut self, address: Address, value: Option<u8>, filler: Option<u8>) -> bool {
+        self.window.push_back((address, value));
+        if self.window.len() > 3 {
+            self.window.pop_front();
+        }
+
+        if let [(_, left), (candidate, Some(value)), (_, right)] = self.window.make_contiguous() {
+            if Some(*value) != filler && Some(*value) != *left && Some(*value) != *right {
+                self.found = Some(*candidate);
+            }
+        }
+
+        self.found.is_some()
+    }
+}
+
+impl MemoryEditor {
+    /// Start jumping to the next interesting byte after the selection, or after the start of the visible range.
+    pub(crate) fn start_interesting_byte_jump(&mut self, current_address_range: &Range<Address>) {
+        let from = self
+            .frame_data
+            .view
+            .selected_highlight_address
+            .unwrap_or(self.visible.addresses.start)
+            .max(current_address_range.start);
+
+        self.frame_data.interesting_byte_jump = Some(InterestingByteJump::new(
+            self.options.selected_address_range.clone(),
+            current_address_range.clone(),
+            from,
+        ));
+    }
+
+    /// Advance the running jump, first summarising its region if that wasn't done yet. Returns whether it's still
+    /// running.
+    pub(crate) fn step_interesting_byte_jump<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        budget: usize,
+    ) -> bool {
+        let Some(jump) = self.frame_data.interesting_byte_jump.as_mut() else {
+            return false;
+        };
+        let summary = self
+            .region_summaries
+            .entry(jump.region.clone())
+            .or_insert_with(|| RegionSummary::new(&jump.address_space));
+
+        if summary.task.is_some() {
+            summary.step(mem, read_fn, budget);
+        } else {
+            jump.step(mem, read_fn, budget, summary.dominant_value());
+        }
+
+        self.finish_interesting_byte_jump()
+    }
+
+    /// Finish the running jump at once, see [`MemoryEditor::complete_tasks_with_slice`].
+    pub(crate) fn complete_interesting_byte_jump_with_slice(&mut self, memory: &[u8], base: Address) {
+        let Some(jump) = self.frame_data.interesting_byte_jump.as_mut() else {
+            return;
+        };
+        let summary = self
+            .region_summaries
+            .entry(jump.region.clone())
+            .or_insert_with(|| RegionSummary::new(&jump.address_space));
+
+        summary.complete_with_slice(memory, base);
+        jump.complete_with_slice(memory, base, summary.dominant_value());
+
+        self.finish_interesting_byte_jump();
+    }
+
+    /// Go to the found byte once the jump is done, returns whether it's still running.
+    fn finish_interesting_byte_jump(&mut self) -> bool {
+        let Some(jump) = self
+            .frame_data
+            .interesting_byte_jump
+            .take_if(|jump| jump.task.is_none())
+        else {
+            return self.frame_data.interesting_byte_jump.is_some();
+        };
+
+        // The result is meaningless once the user switched to another region.
+        if let Some(address) = jump
+            .found
+            .filter(|_| jump.region == self.options.selected_address_range)
+        {
+            self.frame_data
+                .set_goto_address(address, &jump.address_space, self.column_count());
+        }
+
+        false
+    }
+}
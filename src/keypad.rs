@@ -0,0 +1,55 @@
+//! The optional on-screen keypad, for editing values on touch devices and controller-driven frontends.
+use egui::{Align2, Order, Rect, RichText, Ui};
+
+use crate::MemoryEditor;
+
+impl MemoryEditor {
+    /// Draw a `0`-`F` keypad over the bottom right corner of the grid while a value is being edited, see
+    /// [`crate::option_data::MemoryEditorOptions::show_keypad`].
+    ///
+    /// Pressed digits are added to the edited value as if they were typed.
+    pub(crate) fn draw_keypad(&mut self, ui: &mut Ui, viewport: Rect) {
+        self.frame_data.view.keypad_rect = None;
+        if !self.options.show_keypad || self.frame_data.view.selected_edit_address.is_none() {
+            return;
+        }
+
+        // Leave the scrollbar uncovered, such that it can still be dragged.
+        let scroll = &ui.spacing().scroll;
+        let right = viewport.right() - scroll.bar_width - scroll.bar_outer_margin * 2.0;
+        let font = self.options.value_font(ui.style());
+        let mut pressed = None;
+
+        let response = egui::Area::new(ui.id().with("keypad"))
+            .order(Order::Foreground)
+            .pivot(Align2::RIGHT_BOTTOM)
+            .fixed_pos(egui::pos2(right, viewport.bottom()))
+            .show(ui.ctx(), |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    egui::Grid::new("keypad").show(ui, |ui| {
+                        for row in 0..4 {
+                            for digit in row * 4..row * 4 + 4 {
+                                let text = RichText::new(format!("{:X}", digit)).font(font.clone());
+                                if ui.button(text).clicked() {
+                                    pressed = Some(digit);
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+        self.frame_data.view.keypad_rect = Some(response.response.rect);
+
+        if let Some(digit) = pressed {
+            let frame_data = &mut self.frame_data;
+            frame_data
+                .view
+                .selected_edit_address_string
+                .push_str(&format!("{:X}", digit));
+            // Pressing the keypad took the focus from the edited value.
+            frame_data.view.selected_edit_address_request_focus = true;
+            ui.ctx().request_repaint();
+        }
+    }
+}
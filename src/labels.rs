@@ -0,0 +1,565 @@
+//! The user-facing strings of the UI, which can be overridden to translate the editor.
+use std::fmt::{Display, Write};
+
+/// All user-facing strings of the UI, see [`crate::option_data::MemoryEditorOptions::labels`].
+///
+/// Some strings contain placeholders in braces, such as `{address}`, which are replaced with the relevant value when
+/// the string is displayed. Placeholders can be moved around freely. The documentation of a field lists the ones it
+/// supports, fields which don't list any have none.
+///
+/// The names of the cell formats, data formats, endianness and checksum kinds are technical terms, and aren't included.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Labels {
+    // Shared
+    pub cancel: String,
+    pub use_selection: String,
+    /// The hint of address inputs.
+    pub address_hint: String,
+    /// Shown instead of the editor if it can't be drawn, see [`crate::MemoryEditorError`]. Placeholders: `{error}`.
+    pub draw_error: String,
+
+    // Main options
+    pub options_title: String,
+    pub region: String,
+    pub recompute_summary_hover: String,
+    /// Placeholders: `{zero}`, `{ff}`, `{entropy}`, all already formatted.
+    pub region_summary: String,
+    /// Appended to [`Labels::region_summary`] if some bytes couldn't be read. Placeholders: `{count}`.
+    pub region_summary_unreadable: String,
+    /// Shown in front of the column count.
+    pub columns: String,
+    pub display_base: String,
+    pub display_base_hint: String,
+    pub display_base_hover: String,
+    pub goto_hover: String,
+    /// Placeholders: `{range}`, the current address range.
+    pub goto: String,
+    /// Placeholders: `{address}`, `{range}`, the current address range.
+    pub goto_out_of_range: String,
+    pub goto_invalid: String,
+    pub copy_link_hover: String,
+    pub interesting_byte_hover: String,
+    pub find: String,
+    pub find_hint: String,
+    pub find_hover: String,
+    pub show_ascii: String,
+    /// Shown while the ASCII sidebar is hidden.
+    pub enable_ascii_hover: String,
+    /// Shown while the ASCII sidebar is visible.
+    pub disable_ascii_hover: String,
+    pub zero_colour: String,
+    pub zero_colour_hover: String,
+    pub keypad: String,
+    pub keypad_hover: String,
+    pub read_only: String,
+    pub read_only_hover: String,
+    pub cells: String,
+    pub cells_hover: String,
+    pub profile: String,
+    pub profile_hover: String,
+    /// The name of the current layout if it doesn't match any profile.
+    pub custom_profile: String,
+    pub remove_profile_hover: String,
+    pub profile_name_hint: String,
+    pub save_profile: String,
+
+    // Data preview
+    pub data_preview_title: String,
+    pub endianness: String,
+    pub endianness_hover: String,
+    pub data_format: String,
+    pub data_format_hover: String,
+    /// Explains how to select values.
+    pub selection_hover: String,
+    /// Placeholders: `{address}`.
+    pub value_at: String,
+    /// Placeholders: `{length}`, `{format}`, `{needed}`.
+    pub selection_too_short: String,
+    /// Placeholders: `{address}`.
+    pub previous_value_at: String,
+    /// Placeholders: `{address}`.
+    pub next_value_at: String,
+    /// Shown in front of [`Labels::no_value`] when nothing is selected.
+    pub value: String,
+    pub no_value: String,
+    pub find_value_hint: String,
+    pub find_value_hover: String,
+    pub find_value: String,
+    /// Shown in front of the rescan buttons.
+    pub rescan: String,
+    pub rescan_hover: String,
+    pub increased: String,
+    pub decreased: String,
+    pub unchanged: String,
+    pub changed: String,
+
+    // Pending patch
+    pub pending_patch_title: String,
+    /// Placeholders: `{count}`, `{differences}`.
+    pub pending_patch_summary: String,
+    /// Shown after applying a patch of which some writes weren't allowed. Placeholders: `{count}`.
+    pub skipped_patch_writes: String,
+    pub apply: String,
+    pub apply_hover: String,
+    pub discard: String,
+
+    // Edit history
+    pub edit_history_title: String,
+    /// Placeholders: `{writes}`, `{addresses}`.
+    pub edit_history_summary: String,
+    pub copy_as_list: String,
+    pub copy_as_list_hover: String,
+    pub clear: String,
+    pub clear_history_hover: String,
+
+    // Template
+    pub template_title: String,
+    /// Shown between the template selection and its base address.
+    pub template_at: String,
+    pub template_use_selection_hover: String,
+    /// Placeholders: `{range}`, the current address range.
+    pub template_base_hint: String,
+    pub out_of_range: String,
+    /// Placeholders: `{value}`, `{format}`.
+    pub invalid_value: String,
+    pub edit_field_hover: String,
+
+    // Compare
+    pub compare_title: String,
+    pub reference_path_hint: String,
+    pub reference_path_hover: String,
+    pub load: String,
+    /// Placeholders: `{error}`.
+    pub load_failed: String,
+    /// Placeholders: `{name}`, `{length}`.
+    pub reference: String,
+    pub compare: String,
+    pub close: String,
+    /// Placeholders: `{count}`, `{region}`.
+    pub mismatches: String,
+    /// Placeholders: `{address}`, `{value}`.
+    pub mismatch_expected: String,
+    pub fill_diff: String,
+    pub fill_diff_hover: String,
+
+    // Checksum
+    pub checksum_title: String,
+    pub checksum_data: String,
+    pub start_hint: String,
+    pub end_hint: String,
+    pub checksum_use_selection_hover: String,
+    pub checksum_location: String,
+    pub big_endian: String,
+    pub validate: String,
+    /// Placeholders: `{range}`, the current address range.
+    pub checksum_invalid_input: String,
+    /// Shown when the stored checksum doesn't fit within the region. Placeholders: `{address}`, `{range}`.
+    pub checksum_out_of_range: String,
+    /// Placeholders: `{value}`.
+    pub checksum_expected: String,
+    /// Placeholders: `{address}`, `{value}`.
+    pub checksum_stored: String,
+    pub checksum_valid: String,
+    pub checksum_mismatch: String,
+    pub fix_checksum: String,
+
+    // Hashes
+    pub hashes_title: String,
+    pub calculate_hashes: String,
+    pub calculate_hashes_hover: String,
+    pub no_hashes: String,
+    /// Placeholders: `{count}`, `{range}`.
+    pub hashes_summary: String,
+    /// Placeholders: `{count}`.
+    pub hashes_unreadable: String,
+    pub copy_hash_hover: String,
+
+    // Analysis
+    pub analysis_title: String,
+    pub pattern_hint: String,
+    pub pattern_hover: String,
+    pub selection_only: String,
+    pub analyse: String,
+    pub invalid_pattern: String,
+    /// Followed by a link to the start of the run. Placeholders: `{byte}`, `{length}`.
+    pub longest_run: String,
+    /// Placeholders: `{byte}`.
+    pub no_run: String,
+    /// Placeholders: `{count}`, `{pattern}`, `{range}`.
+    pub occurrences: String,
+
+    // Search
+    pub search_title: String,
+    pub search_hint: String,
+    pub search_hover: String,
+    pub search_text: String,
+    pub search_hex: String,
+    pub search_hex_hover: String,
+    pub invalid_search_pattern: String,
+    pub case_insensitive: String,
+    /// Only shown with the `regex` feature.
+    pub regex: String,
+    /// Only shown with the `regex` feature.
+    pub regex_hover: String,
+    pub search: String,
+    /// Placeholders: `{error}`.
+    pub invalid_regex: String,
+    /// Placeholders: `{count}`, `{query}`, `{range}`.
+    pub search_matches: String,
+    /// The position of the active match. Placeholders: `{index}`, `{count}`.
+    pub search_position: String,
+    /// Placeholders: `{query}`.
+    pub no_matches: String,
+    /// The header of the list of matches.
+    pub search_results: String,
+    pub all_regions: String,
+    pub all_regions_hover: String,
+    /// The header of the matches of another region. Placeholders: `{region}`, `{count}`.
+    pub region_matches: String,
+    pub previous_match: String,
+    pub next_match: String,
+    pub replacement_hint: String,
+    pub replacement_hover: String,
+    pub replace: String,
+    pub replace_all: String,
+    pub invalid_replacement: String,
+    /// Shown when replacements didn't fit before the end of the searched range, and were cut off. Placeholders:
+    /// `{count}`.
+    pub truncated_replacements: String,
+
+    // Locations
+    pub locations_title: String,
+    /// Used as a filter and next to every location of its kind.
+    pub location_search_match: String,
+    /// Used as a filter and next to every location of its kind.
+    pub location_marker: String,
+    /// Used as a filter and next to every location of its kind.
+    pub location_bookmark: String,
+    /// Used as a filter and next to every location of its kind.
+    pub location_mismatch: String,
+    /// Used as a filter and next to every location of its kind.
+    pub location_modified: String,
+    pub no_locations: String,
+    /// Placeholders: `{limit}`.
+    pub locations_truncated: String,
+
+    // Histogram
+    pub histogram_title: String,
+    /// Used as the `{source}` of [`Labels::histogram_summary`].
+    pub histogram_selection: String,
+    /// Used as the `{source}` of [`Labels::histogram_summary`].
+    pub histogram_visible_range: String,
+    /// Placeholders: `{count}`, `{source}`, `{range}`, `{entropy}`.
+    pub histogram_summary: String,
+
+    // Write confirmation
+    /// Placeholders: `{value}`, `{address}`.
+    pub confirm_write: String,
+    /// Placeholders: `{count}`, `{range}`.
+    pub confirm_writes: String,
+    /// Placeholders: `{count}`, `{ranges}`, `{start}`, `{end}`.
+    pub confirm_scattered_writes: String,
+    /// Placeholders: `{count}`.
+    pub more_writes: String,
+    pub write: String,
+    /// See [`crate::option_data::MemoryEditorOptions::writes_per_frame`]. Placeholders: `{count}`.
+    pub queued_writes: String,
+    /// See [`crate::option_data::MemoryEditorOptions::max_copy_bytes`]. Placeholders: `{count}`, `{limit}`.
+    pub selection_too_large: String,
+
+    // Grid
+    /// The context menu entry of a value, copying the selected bytes as space separated hex.
+    pub copy_hex: String,
+    /// The context menu entry of a value, copying the selected bytes as they're shown in the ASCII sidebar.
+    pub copy_text: String,
+    /// The context menu entry of an address.
+    pub copy_row: String,
+    /// The context menu entry of an address or a value, copying the absolute address padded like the
+    /// address column.
+    pub copy_address: String,
+    /// The context menu entry of an address which isn't bookmarked.
+    pub bookmark_row: String,
+    /// The context menu entry of an address which is bookmarked.
+    pub remove_bookmark: String,
+    /// The context menu entry of an address, see [`crate::option_data::MemoryEditorOptions::display_base`].
+    pub set_display_base: String,
+    /// The context menu entry of the address which is the display base.
+    pub clear_display_base: String,
+    /// The first visible address, see [`crate::option_data::MemoryEditorOptions::show_scroll_position`].
+    /// Placeholders: `{address}`, `{percentage}`.
+    pub scroll_position: String,
+}
+
+impl Default for Labels {
+    fn default() -> Self {
+        Labels {
+            cancel: "Cancel".to_string(),
+            use_selection: "Use selection".to_string(),
+            address_hint: "address".to_string(),
+            draw_error: "⚠ The memory editor can't be drawn: {error}".to_string(),
+
+            options_title: "🛠 Options".to_string(),
+            region: "Region:".to_string(),
+            recompute_summary_hover: "Recompute the region summary".to_string(),
+            region_summary: "{zero}% 00, {ff}% FF, entropy {entropy}".to_string(),
+            region_summary_unreadable: ", {count} unreadable".to_string(),
+            columns: "Columns: ".to_string(),
+            display_base: "Display base".to_string(),
+            display_base_hint: "None".to_string(),
+            display_base_hover:
+                "Show the addresses as offsets from this address, such as the load address of a binary.\n\
+                Submit an empty field to show the addresses themselves again."
+                    .to_string(),
+            goto_hover: "Goto an address, format: \n\
+                * An address like `0xAA` can be written as `AA`\n\
+                * Offset from the base address, if the base is `0xFF00` then one can enter `5` to go to `0xFF05`\n\
+                * Sums of addresses, like `FF00 + 10 - 2`\n\
+                Press enter to move to the address"
+                .to_string(),
+            goto: "Goto: {range}".to_string(),
+            goto_out_of_range: "Address {address} is outside {range}".to_string(),
+            goto_invalid: "Not a valid address".to_string(),
+            copy_link_hover: "Copy a link to the selected address".to_string(),
+            interesting_byte_hover:
+                "Jump to the next byte which differs from both of its neighbours and from the most \
+                                     common byte of the region, to skip past padding"
+                    .to_string(),
+            find: "Find in the region".to_string(),
+            find_hint: "PLAYER".to_string(),
+            find_hover: "Search the region like the `Search` section, press enter again to go to the next match"
+                .to_string(),
+            show_ascii: "Show ASCII".to_string(),
+            enable_ascii_hover: "Enable the ASCII representation view".to_string(),
+            disable_ascii_hover: "Disable the ASCII representation view".to_string(),
+            zero_colour: "Custom zero colour".to_string(),
+            zero_colour_hover: "If enabled memory values of '0x00' will be coloured differently".to_string(),
+            keypad: "Keypad".to_string(),
+            keypad_hover: "Show a keypad to enter digits while editing a value, for when there is no keyboard"
+                .to_string(),
+            read_only: "🔒 Read-only".to_string(),
+            read_only_hover: "Temporarily prevent any edits, useful while browsing live memory".to_string(),
+            cells: "Cells".to_string(),
+            cells_hover: "Select how the value of every byte is displayed".to_string(),
+            profile: "Profile".to_string(),
+            profile_hover: "Switch between saved layouts, such as the column count and cell format".to_string(),
+            custom_profile: "Custom".to_string(),
+            remove_profile_hover: "Remove this profile".to_string(),
+            profile_name_hint: "Profile name".to_string(),
+            save_profile: "Save current".to_string(),
+
+            data_preview_title: "⛃ Data Preview".to_string(),
+            endianness: "Endianness".to_string(),
+            endianness_hover: "Select the endianness of the data".to_string(),
+            data_format: "Format".to_string(),
+            data_format_hover: "Select the number type for data interpretation".to_string(),
+            selection_hover: "Right click a value in the UI to select it, right click again to unselect.\n\
+                Shift + right click to select multiple values"
+                .to_string(),
+            value_at: "Value at {address} (decimal): ".to_string(),
+            selection_too_short: "Selection of {length} byte(s) is too short for {format}, which needs {needed}"
+                .to_string(),
+            previous_value_at: "Previous at {address}: ".to_string(),
+            next_value_at: "Next at {address}: ".to_string(),
+            value: "Value (decimal): ".to_string(),
+            no_value: "None".to_string(),
+            find_value_hint: "1000".to_string(),
+            find_value_hover: "Search the region for addresses whose bytes decode to this value, \
+                in the selected endianness and format"
+                .to_string(),
+            find_value: "Find value".to_string(),
+            rescan: "Keep values which:".to_string(),
+            rescan_hover: "Read the matches again, keeping those whose value changed this way since the last scan"
+                .to_string(),
+            increased: "Increased".to_string(),
+            decreased: "Decreased".to_string(),
+            unchanged: "Stayed equal".to_string(),
+            changed: "Changed".to_string(),
+
+            pending_patch_title: "🩹 Pending Patch".to_string(),
+            pending_patch_summary: "Writes {count} bytes, of which {differences} differ from the current memory"
+                .to_string(),
+            skipped_patch_writes:
+                "{count} writes of the applied patch were skipped, as their addresses aren't editable".to_string(),
+            apply: "Apply".to_string(),
+            apply_hover: "Write all bytes of the patch to memory".to_string(),
+            discard: "Discard".to_string(),
+
+            edit_history_title: "✏ Edit History".to_string(),
+            edit_history_summary: "{writes} writes to {addresses} addresses".to_string(),
+            copy_as_list: "Copy as list".to_string(),
+            copy_as_list_hover: "Copy all changed addresses and their values as `0xADDRESS: VALUE` lines".to_string(),
+            clear: "Clear".to_string(),
+            clear_history_hover: "Forget all recorded writes".to_string(),
+
+            template_title: "📋 Template".to_string(),
+            template_at: "at".to_string(),
+            template_use_selection_hover: "Start the structure at the currently selected address".to_string(),
+            template_base_hint: "Enter a hex base address within {range}".to_string(),
+            out_of_range: "Out of range".to_string(),
+            invalid_value: "`{value}` is not a valid {format}".to_string(),
+            edit_field_hover: "Click to edit, press enter to write the value".to_string(),
+
+            compare_title: "🔍 Compare".to_string(),
+            reference_path_hint: "path/to/reference.bin".to_string(),
+            reference_path_hover: "The file to compare the current region against, starting at its first address"
+                .to_string(),
+            load: "Load".to_string(),
+            load_failed: "Failed to load file: {error}".to_string(),
+            reference: "Reference: {name} ({length} bytes)".to_string(),
+            compare: "Compare".to_string(),
+            close: "Close".to_string(),
+            mismatches: "{count} mismatching bytes in `{region}`".to_string(),
+            mismatch_expected: "{address}: expected {value}".to_string(),
+            fill_diff: "Highlight bytes differing from".to_string(),
+            fill_diff_hover: "Highlight every byte which isn't the fill value, showing which parts of freshly \
+                              initialized or erased memory were touched"
+                .to_string(),
+
+            checksum_title: "🔢 Checksum".to_string(),
+            checksum_data: "Data:".to_string(),
+            start_hint: "start".to_string(),
+            end_hint: "end".to_string(),
+            checksum_use_selection_hover: "Use the currently selected bytes as the data range".to_string(),
+            checksum_location: "Stored at:".to_string(),
+            big_endian: "Big endian".to_string(),
+            validate: "Validate".to_string(),
+            checksum_invalid_input: "The data range and location should be hex addresses within {range}".to_string(),
+            checksum_out_of_range: "The checksum at {address} is out of range, it should fit within {range}"
+                .to_string(),
+            checksum_expected: "Expected: {value}".to_string(),
+            checksum_stored: "Stored at {address}: {value}".to_string(),
+            checksum_valid: "✔ The checksum is valid".to_string(),
+            checksum_mismatch: "✖ The checksum doesn't match".to_string(),
+            fix_checksum: "Fix checksum".to_string(),
+            hashes_title: "#️⃣ Hashes".to_string(),
+            calculate_hashes: "Hash selection".to_string(),
+            calculate_hashes_hover: "Calculate the hashes of the currently selected bytes. MD5 and SHA-1 require the \
+                `hashes` feature"
+                .to_string(),
+            no_hashes: "Select bytes to hash them".to_string(),
+            hashes_summary: "Hashes of {count} bytes at {range}".to_string(),
+            hashes_unreadable: "{count} unreadable bytes were hashed as 00".to_string(),
+            copy_hash_hover: "Copy to the clipboard".to_string(),
+
+            analysis_title: "📊 Analysis".to_string(),
+            pattern_hint: "00 or DE AD BE EF".to_string(),
+            pattern_hover: "The hex bytes to look for, the longest run is found for single byte patterns".to_string(),
+            selection_only: "Selection only".to_string(),
+            analyse: "Analyse".to_string(),
+            invalid_pattern: "The pattern should consist of hex bytes, like `DE AD`".to_string(),
+            longest_run: "Longest run of {byte}: {length} bytes at".to_string(),
+            no_run: "No run of {byte} found".to_string(),
+            occurrences: "{count} occurrences of `{pattern}` in {range}".to_string(),
+
+            search_title: "🔍 Search".to_string(),
+            search_hint: "Text or hex bytes to find".to_string(),
+            search_hover:
+                "The text as shown in the ASCII sidebar, where non-printable bytes are shown as `.`, or hex bytes \
+                           like `DE AD` in the hex mode"
+                    .to_string(),
+            search_text: "Text".to_string(),
+            search_hex: "Hex".to_string(),
+            search_hex_hover: "The hex bytes to look for, where `??` matches any byte, like `48 8B ?? ?? E8`"
+                .to_string(),
+            invalid_search_pattern:
+                "The pattern should consist of hex bytes and `??` wildcards, with at least one byte".to_string(),
+            case_insensitive: "Ignore case".to_string(),
+            regex: "Regex".to_string(),
+            regex_hover: "Match the query as a regular expression over the decoded text, matches longer than 4096 \
+                          characters can be cut short"
+                .to_string(),
+            search: "Search".to_string(),
+            invalid_regex: "Invalid regex: {error}".to_string(),
+            search_matches: "{count} matches of `{query}` in {range}".to_string(),
+            search_position: "Match {index} of {count}".to_string(),
+            no_matches: "No matches of `{query}`".to_string(),
+            search_results: "Matches".to_string(),
+            all_regions: "All regions".to_string(),
+            all_regions_hover: "Also search every other region, for both the query and the value search of the \
+                `Data Preview`"
+                .to_string(),
+            region_matches: "{region}: {count} matches".to_string(),
+            previous_match: "Previous match".to_string(),
+            next_match: "Next match".to_string(),
+            replacement_hint: "Replacement".to_string(),
+            replacement_hover: "Written over the start of the matches, interpreted like the query: as ASCII text, \
+                as hex bytes where `??` keeps the byte of the match, or as a value"
+                .to_string(),
+            replace: "Replace".to_string(),
+            replace_all: "Replace all".to_string(),
+            invalid_replacement: "The replacement should be of the same kind as the query".to_string(),
+            truncated_replacements: "{count} of the replaced matches were cut off at the end of the searched range"
+                .to_string(),
+
+            locations_title: "📍 Locations".to_string(),
+            location_search_match: "Search match".to_string(),
+            location_marker: "Marker".to_string(),
+            location_bookmark: "Bookmark".to_string(),
+            location_mismatch: "Mismatch".to_string(),
+            location_modified: "Modified".to_string(),
+            no_locations: "No locations in this region".to_string(),
+            locations_truncated: "Only the first {limit} locations of each kind are listed".to_string(),
+
+            histogram_title: "📈 Histogram".to_string(),
+            histogram_selection: "selection".to_string(),
+            histogram_visible_range: "visible range".to_string(),
+            histogram_summary: "{count} bytes of the {source} ({range}), entropy: {entropy} bits per byte".to_string(),
+
+            confirm_write: "Write {value} to {address}?".to_string(),
+            confirm_writes: "Write {count} bytes to {range}?".to_string(),
+            confirm_scattered_writes: "Write {count} bytes to {ranges} separate ranges between {start} and {end}?"
+                .to_string(),
+            more_writes: "... and {count} more".to_string(),
+            write: "Write".to_string(),
+            queued_writes: "{count} writes queued".to_string(),
+            selection_too_large: "The selection of {count} bytes is too large to copy, the limit is {limit}"
+                .to_string(),
+
+            copy_hex: "Copy as hex".to_string(),
+            copy_text: "Copy as text".to_string(),
+            copy_row: "Copy row".to_string(),
+            copy_address: "Copy address".to_string(),
+            bookmark_row: "Bookmark row".to_string(),
+            remove_bookmark: "Remove bookmark".to_string(),
+            set_display_base: "Set as display base".to_string(),
+            clear_display_base: "Clear display base".to_string(),
+            scroll_position: "{address} ({percentage}%)".to_string(),
+        }
+    }
+}
+
+/// Replace the `{name}` placeholders in `template` with their values.
+///
+/// The template is scanned once, so braces within the values are never mistaken for placeholders. Unknown
+/// placeholders are kept as they are.
+pub(crate) fn fill(template: &str, values: &[(&str, &dyn Display)]) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        text.push_str(&rest[..open]);
+        let placeholder = &rest[open..];
+        let value = placeholder.find('}').and_then(|close| {
+            Some((
+                close,
+                values.iter().find(|(name, _)| *name == &placeholder[1..close])?.1,
+            ))
+        });
+
+        match value {
+            Some((close, value)) => {
+                let _ = write!(text, "{}", value);
+                rest = &placeholder[close + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = &placeholder[1..];
+            }
+        }
+    }
+
+    text.push_str(rest);
+    text
+}
@@ -0,0 +1,307 @@
+//! Column math for the main UI, used to adapt the layout to the available width.
+use egui::{Context, FontId, Rect, Ui, Vec2};
+
+use crate::option_data::LayoutDirection;
+use crate::MemoryEditor;
+
+/// The horizontal spacing between the columns of the main grid (address, byte groups, ASCII sidebar).
+pub(crate) const GRID_COLUMN_SPACING: f32 = 15.0;
+/// The horizontal spacing between individual bytes in the main grid.
+pub(crate) const CELL_SPACING: f32 = 3.0;
+/// The spacing on either side of the separator in front of the ASCII sidebar.
+pub(crate) const ASCII_SEPARATOR_SPACING: f32 = 3.0;
+
+/// The most columns the main grid can display, the column count is clamped to `1..=MAX_COLUMN_COUNT`.
+pub(crate) const MAX_COLUMN_COUNT: usize = 64;
+
+/// The characters values are formatted with, see [`crate::option_data::CellFormat::format`].
+const VALUE_CHARACTERS: &str = "0123456789ABCDEF";
+
+/// A column of the main grid, in the order they're drawn left to right.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum GridColumn {
+    MarkerGutter,
+    Address,
+    Values,
+    Ascii,
+    Labels,
+    /// The host's derived column at this index, see [`MemoryEditor::with_derived_column`].
+    Derived(usize),
+}
+
+/// The measured layout of the main grid in the last frame.
+///
+/// Useful for aligning custom widgets drawn next to the editor, such as a breakpoint gutter, with the grid.
+/// See [`MemoryEditor::layout_metrics`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LayoutMetrics {
+    /// The height of a single row, excluding the spacing between rows.
+    pub row_height: f32,
+    /// The vertical distance between the tops of two consecutive rows.
+    pub row_pitch: f32,
+    /// The amount of bytes rendered per row, which can differ from the configured column count.
+    pub bytes_per_row: usize,
+    /// Whether the ASCII sidebar was rendered.
+    pub is_ascii_shown: bool,
+    /// The screen-space rectangle of the rendered rows of the grid.
+    pub grid_rect: Rect,
+    /// The screen-space rectangle of the scroll area containing the grid, rows outside of it are clipped.
+    pub viewport_rect: Rect,
+    /// The index of the first rendered row relative to the start of the region, its top lies at `grid_rect.top()`.
+    pub first_row: usize,
+}
+
+impl Default for LayoutMetrics {
+    fn default() -> Self {
+        LayoutMetrics {
+            row_height: 0.0,
+            row_pitch: 0.0,
+            bytes_per_row: 0,
+            is_ascii_shown: false,
+            grid_rect: Rect::NOTHING,
+            viewport_rect: Rect::NOTHING,
+            first_row: 0,
+        }
+    }
+}
+
+impl LayoutMetrics {
+    /// The screen-space vertical position of the top of `row`, relative to the start of the region.
+    pub fn row_top(&self, row: usize) -> f32 {
+        self.grid_rect.top() + (row as f32 - self.first_row as f32) * self.row_pitch
+    }
+}
+
+impl MemoryEditor {
+    /// Returns the measured layout of the main grid in the last frame.
+    pub fn layout_metrics(&self) -> &LayoutMetrics {
+        &self.layout_metrics
+    }
+
+    /// The amount of columns currently displayed, which can be less than [`crate::option_data::MemoryEditorOptions::column_count`]
+    /// if [`crate::option_data::MemoryEditorOptions::auto_reduce_columns`] is enabled.
+    #[inline]
+    pub(crate) fn column_count(&self) -> usize {
+        if self.frame_data.view.effective_column_count == 0 {
+            self.screen_column_count()
+        } else {
+            self.frame_data.view.effective_column_count
+        }
+    }
+
+    /// [`crate::option_data::MemoryEditorOptions::column_count`], limited to what fits on the screen this frame.
+    fn screen_column_count(&self) -> usize {
+        match self.frame_data.view.screen_column_limit {
+            0 => self.options.column_count,
+            limit => self.options.column_count.min(limit),
+        }
+    }
+
+    /// Whether the ASCII sidebar is currently displayed, taking
+    /// [`crate::option_data::MemoryEditorOptions::auto_hide_ascii`] into account.
+    #[inline]
+    pub(crate) fn is_ascii_shown(&self) -> bool {
+        self.options.show_ascii && !self.frame_data.view.is_ascii_auto_hidden
+    }
+
+    /// The columns of the main grid in the order they should be drawn, taking
+    /// [`crate::option_data::MemoryEditorOptions::layout_direction`] into account.
+    pub(crate) fn grid_columns(&self, show_ascii: bool, show_labels: bool) -> Vec<GridColumn> {
+        let mut columns = Vec::with_capacity(5 + self.derived_columns.len());
+
+        if self.options.show_marker_gutter {
+            columns.push(GridColumn::MarkerGutter);
+        }
+        columns.extend([GridColumn::Address, GridColumn::Values]);
+        if show_ascii {
+            columns.push(GridColumn::Ascii);
+        }
+        if show_labels {
+            columns.push(GridColumn::Labels);
+        }
+        columns.extend((0..self.derived_columns.len()).map(GridColumn::Derived));
+
+        if self.options.layout_direction == LayoutDirection::RightToLeft {
+            columns.reverse();
+        }
+
+        columns
+    }
+
+    /// Give every value and ASCII character a fixed size for this frame if the font isn't monospace, as the cells
+    /// would otherwise no longer line up between rows.
+    pub(crate) fn update_cell_sizes(&mut self, ctx: &Context, line_height: f32) {
+        let style = ctx.style();
+        let (value_width, is_value_monospace) = self.value_glyph_width(ctx, &self.options.value_font(&style));
+        let (ascii_width, is_ascii_monospace) = ascii_glyph_width(ctx, &self.options.ascii_font(&style));
+        let digits = self.options.cell_format.digits() as f32;
+
+        self.frame_data.view.value_cell_size =
+            (!is_value_monospace).then(|| Vec2::new(digits * value_width, line_height));
+        self.frame_data.view.ascii_cell_size = (!is_ascii_monospace).then(|| Vec2::new(ascii_width, line_height));
+    }
+
+    /// The width of the widest character a value can be displayed with, and whether all of them are equally wide.
+    fn value_glyph_width(&self, ctx: &Context, font_id: &FontId) -> (f32, bool) {
+        let characters = VALUE_CHARACTERS.chars().chain(self.options.none_display_value.chars());
+        widest_glyph(ctx, font_id, characters)
+    }
+
+    /// The amount of hex digits needed to display the largest address of the selected region.
+    pub(crate) fn address_characters(&self) -> usize {
+        self.address_ranges
+            .get(&self.options.selected_address_range)
+            .map_or(1, |range| format!("{:X}", range.end.saturating_sub(1)).chars().count())
+    }
+
+    /// Limit the columns displayed this frame to what fits on the screen, so increasing the column count can't grow
+    /// the window beyond the screen.
+    ///
+    /// [`crate::option_data::MemoryEditorOptions::column_count`] itself is left alone, so the configured amount is
+    /// shown again once the screen is large enough.
+    pub(crate) fn clamp_column_count_to_screen(&mut self, ctx: &Context) {
+        let style = ctx.style();
+        let scroll = &style.spacing.scroll;
+        // The window's margins and the scroll bar aren't part of the grid.
+        let overhead =
+            style.spacing.window_margin.sum().x + scroll.bar_width + scroll.bar_inner_margin + scroll.bar_outer_margin;
+        let screen_width = ctx.screen_rect().width() - overhead;
+        let address_characters = self.address_characters();
+        let show_ascii = self.options.show_ascii && !self.options.auto_hide_ascii;
+
+        self.frame_data.view.screen_column_limit = 0;
+        if self.required_width(ctx, address_characters, self.options.column_count, show_ascii) <= screen_width {
+            return;
+        }
+
+        self.frame_data.view.screen_column_limit = (1..self.options.column_count)
+            .rev()
+            .find(|&columns| self.required_width(ctx, address_characters, columns, show_ascii) <= screen_width)
+            .unwrap_or(1);
+    }
+
+    /// The width of the widest layout of whole columns which fits in `available_width`.
+    ///
+    /// Used to snap the window to column boundaries, rather than clipping a column halfway.
+    pub(crate) fn snapped_width(&self, ctx: &Context, available_width: f32) -> f32 {
+        let address_characters = self.address_characters();
+        let show_ascii = self.is_ascii_shown();
+
+        (1..=self.column_count())
+            .rev()
+            .map(|columns| self.required_width(ctx, address_characters, columns, show_ascii))
+            .find(|&width| width <= available_width)
+            .unwrap_or(available_width)
+    }
+
+    /// Forget the layout determined for the previous frame, for when the options it was based on changed.
+    pub(crate) fn reset_layout(&mut self) {
+        self.options.column_count = self.options.column_count.clamp(1, MAX_COLUMN_COUNT);
+        self.frame_data.view.effective_column_count = 0;
+        self.frame_data.view.is_ascii_auto_hidden = false;
+        self.ascii_rows = Default::default();
+    }
+
+    /// Determine the amount of columns and whether to show the ASCII sidebar for this frame, based on the
+    /// available width.
+    pub(crate) fn update_responsive_layout(&mut self, ui: &Ui, address_characters: usize) {
+        let available_width = self
+            .frame_data
+            .view
+            .window_available_width
+            .take()
+            .unwrap_or_else(|| ui.available_width());
+        let options = &self.options;
+        let fits = |columns: usize, show_ascii: bool| {
+            self.required_width(ui.ctx(), address_characters, columns, show_ascii) <= available_width
+        };
+        let max_column_count = self.screen_column_count();
+
+        let mut show_ascii = options.show_ascii;
+        if show_ascii && options.auto_hide_ascii && !fits(max_column_count, true) {
+            show_ascii = false;
+        }
+
+        let mut column_count = max_column_count;
+        if options.auto_reduce_columns {
+            column_count = (1..=max_column_count)
+                .rev()
+                .find(|&columns| fits(columns, show_ascii))
+                .unwrap_or(1);
+        }
+
+        self.frame_data.view.is_ascii_auto_hidden = options.show_ascii && !show_ascii;
+        self.frame_data.view.effective_column_count = column_count;
+    }
+
+    /// Estimate the width the main grid would need for the given layout.
+    pub(crate) fn required_width(
+        &self,
+        ctx: &Context,
+        address_characters: usize,
+        columns: usize,
+        show_ascii: bool,
+    ) -> f32 {
+        let options = &self.options;
+        let style = ctx.style();
+        let address_font = options.address_font(&style);
+        let value_font = options.value_font(&style);
+        let ascii_font = options.ascii_font(&style);
+
+        // `0x` + digits + `:`
+        let address_glyph_width = widest_glyph(ctx, &address_font, VALUE_CHARACTERS.chars().chain(['x', ':'])).0;
+        let address_width = (address_characters + 3) as f32 * address_glyph_width;
+
+        let format = options.cell_format;
+        let byte_width = (format.sub_cells() * format.digits()) as f32 * self.value_glyph_width(ctx, &value_font).0
+            + (format.sub_cells() - 1) as f32 * format.sub_cell_spacing();
+        let groups = columns.div_ceil(format.bytes_per_group());
+        let values_width = columns as f32 * byte_width
+            + (columns - groups) as f32 * CELL_SPACING
+            + groups as f32 * GRID_COLUMN_SPACING;
+
+        let ascii_width = if show_ascii {
+            GRID_COLUMN_SPACING
+                + 2.0 * ASCII_SEPARATOR_SPACING
+                + CELL_SPACING
+                + columns as f32 * ascii_glyph_width(ctx, &ascii_font).0
+        } else {
+            0.0
+        };
+
+        let gutter_width = if options.show_marker_gutter {
+            let line_height = ctx.fonts(|fonts| {
+                fonts
+                    .row_height(&address_font)
+                    .max(fonts.row_height(&value_font))
+                    .max(fonts.row_height(&ascii_font))
+            });
+            line_height + GRID_COLUMN_SPACING
+        } else {
+            0.0
+        };
+
+        gutter_width + address_width + values_width + ascii_width
+    }
+}
+
+/// The width of the widest printable ASCII character, and whether all of them are equally wide.
+fn ascii_glyph_width(ctx: &Context, font_id: &FontId) -> (f32, bool) {
+    widest_glyph(ctx, font_id, (32..128u8).map(char::from))
+}
+
+/// The width of the widest of `characters`, and whether all of them are equally wide.
+///
+/// Measuring every character, rather than just `0`, keeps the column math correct for proportional fonts, or CJK fonts
+/// whose glyphs for some characters are wider than others.
+fn widest_glyph(ctx: &Context, font_id: &FontId, characters: impl Iterator<Item = char>) -> (f32, bool) {
+    ctx.fonts(|fonts| {
+        let (narrowest, widest) = characters
+            .map(|character| fonts.glyph_width(font_id, character))
+            .fold((f32::INFINITY, 0.0f32), |(narrowest, widest), width| {
+                (narrowest.min(width), widest.max(width))
+            });
+
+        (widest, widest - narrowest < 0.01)
+    })
+}
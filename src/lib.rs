@@ -4,20 +4,160 @@
 //! Primarily intended for emulation development.
 //!
 //! Look at [`MemoryEditor`] to get started.
-use std::collections::BTreeMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::ops::Range;
-
-use egui::{Context, Label, Margin, RichText, ScrollArea, Sense, TextEdit, TextWrapMode, Ui, Vec2, Widget, Window};
-
-use crate::option_data::{BetweenFrameData, MemoryEditorOptions};
-
+use std::sync::Arc;
+
+use egui::{
+    Context, FontId, Label, Margin, PointerButton, Rect, RichText, ScrollArea, Sense, TextEdit, TextWrapMode, Ui, Vec2,
+    Widget, Window,
+};
+
+pub use crate::address_input::{AddressInput, AddressInputResponse};
+use crate::analysis::AnalysisState;
+use crate::ascii::{ascii_character, printable_character, AsciiRowCache};
+use crate::checksum::ChecksumState;
+use crate::compare::ComparisonState;
+pub use crate::error::MemoryEditorError;
+pub use crate::feedback::RejectedInput;
+pub use crate::file_drop::read_dropped_file;
+use crate::hash::HashCalculation;
+use crate::highlight::HostHighlight;
+#[cfg(feature = "histogram")]
+use crate::histogram::Histogram;
+pub use crate::inspector::DataInspector;
+use crate::labels::fill;
+pub use crate::layout::LayoutMetrics;
+use crate::layout::{GridColumn, ASCII_SEPARATOR_SPACING, CELL_SPACING, GRID_COLUMN_SPACING};
+use crate::locations::LocationsState;
+pub use crate::metrics::MemoryEditorMetrics;
+use crate::option_data::{BetweenFrameData, CellFormat, LayoutDirection, LayoutProfile, MemoryEditorOptions};
+use crate::patch::{EditHistory, Patch, PendingPatch};
+use crate::quick_mark::QUICK_MARK_COUNT;
+use crate::recording::Recorder;
+use crate::refresh::RefreshCache;
+pub use crate::response::EditorResponse;
+pub use crate::row::MemoryRow;
+use crate::search::SearchState;
+use crate::summary::RegionSummary;
+pub use crate::sync::EditorSync;
+use crate::template::{StructTemplate, TemplateState};
+pub use crate::view::MemoryEditorView;
+
+mod address_input;
+mod analysis;
+mod ascii;
+mod bookmark;
+mod checksum;
+mod chunk;
+mod compare;
+mod derived;
+mod error;
+mod feedback;
+mod file_drop;
+mod hash;
+mod highlight;
+#[cfg(feature = "histogram")]
+mod histogram;
+mod inspector;
+mod jump;
+mod keypad;
+mod labels;
+mod layout;
+mod link;
+mod locations;
+mod marker;
+mod metrics;
 pub mod option_data;
 mod option_ui;
-mod utilities;
+pub mod patch;
+mod quick_mark;
+pub mod recording;
+mod refresh;
+mod response;
+mod row;
+mod scan;
+mod search;
+mod summary;
+mod symbol;
+mod sync;
+mod task;
+pub mod template;
+pub mod utilities;
+mod view;
 
 /// A memory address that should be read from/written to.
 pub type Address = usize;
 
+/// A callback which receives the range of addresses predicted to become visible soon.
+///
+/// See [`MemoryEditor::with_prefetch_callback`].
+pub type PrefetchCallback = Arc<dyn Fn(Range<Address>) + Send + Sync>;
+
+/// A callback which returns the symbol (label) name of the symbol starting at an address, if there is one.
+///
+/// See [`MemoryEditor::with_symbol_provider`].
+pub type SymbolProvider = Arc<dyn Fn(Address) -> Option<String> + Send + Sync>;
+
+/// A callback which returns the text of a derived column for the row starting at an address, given a function reading
+/// the memory of the current region.
+///
+/// See [`MemoryEditor::with_derived_column`].
+pub type DerivedColumn = Arc<dyn Fn(Address, &mut dyn FnMut(Address) -> Option<u8>) -> String + Send + Sync>;
+
+/// A callback which decides whether an address may currently be edited through the UI, given a function reading the
+/// memory of the current region.
+///
+/// See [`MemoryEditor::with_edit_permission`].
+pub type EditPermission = Arc<dyn Fn(Address, &mut dyn FnMut(Address) -> Option<u8>) -> bool + Send + Sync>;
+
+/// A callback which is notified of input the editor rejected, for example to show a toast.
+///
+/// See [`MemoryEditor::with_rejected_input_callback`].
+pub type RejectedInputCallback = Arc<dyn Fn(RejectedInput) + Send + Sync>;
+
+/// Everything there is to know about the part of the memory visible in the last frame.
+///
+/// See [`MemoryEditor::visible_range_details`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VisibleRange {
+    /// The name of the address range (region) being displayed.
+    pub region: String,
+    /// The visible addresses, the end may lie beyond the region if the last row isn't completely filled.
+    pub addresses: Range<Address>,
+    /// The indices of the visible rows relative to the start of the region, `rows.end` is exclusive.
+    pub rows: Range<usize>,
+    /// Whether the visible range changed since the previous frame.
+    pub is_scrolling: bool,
+}
+
+/// How far ahead, in seconds, the predicted visible range looks based on the current scroll velocity.
+const PREFETCH_LOOKAHEAD_SECONDS: f64 = 0.25;
+/// The duration of the scroll animation when [`MemoryEditorOptions::animate_goto`] is enabled.
+const GOTO_ANIMATION_SECONDS: f64 = 0.15;
+
+/// The parts of the editor to draw, see [`MemoryEditor::draw_options_only`] and [`MemoryEditor::draw_grid_only`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum EditorParts {
+    All,
+    Options,
+    Grid,
+}
+
+/// The entries of the context menu of an address, see [`MemoryEditor::draw_grid`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum AddressMenuAction {
+    CopyAddress,
+    CopyRow,
+    ToggleBookmark,
+    ToggleDisplayBase,
+}
+
+/// The name of the address range registered automatically by the slice methods, such as
+/// [`MemoryEditor::window_ui_slice`].
+pub const SLICE_RANGE_NAME: &str = "Memory";
+
 /// The main struct for the editor window.
 /// This should persist between frames as it keeps track of quite a bit of state.
 #[derive(Clone)]
@@ -34,7 +174,56 @@ pub struct MemoryEditor {
     /// Data for layout between frames, rather hacky.
     frame_data: BetweenFrameData,
     /// The visible range of addresses from the last frame.
-    visible_range: Range<Address>,
+    visible: VisibleRange,
+    /// All writes made through the UI this session.
+    history: EditHistory,
+    /// The reference data to compare memory against, and the results of the last comparison.
+    comparison: ComparisonState,
+    /// The checksum being validated in the `Checksum` section.
+    checksum: ChecksumState,
+    /// The last hashes calculated in the `Hashes` section.
+    hashes: Option<HashCalculation>,
+    /// The byte frequencies of the `Histogram` section, while it's open.
+    #[cfg(feature = "histogram")]
+    histogram: Option<Histogram>,
+    /// The pattern analysis of the `Analysis` section.
+    analysis: AnalysisState,
+    /// The text search of the `Search` section, whose matches are highlighted.
+    search: SearchState,
+    /// The filters of the `Locations` section.
+    locations: LocationsState,
+    /// Templates registered by the host, shown in the `Template` section.
+    templates: Vec<StructTemplate>,
+    template: TemplateState,
+    /// The byte statistics of every region, see [`MemoryEditorOptions::show_region_summary`].
+    region_summaries: HashMap<String, RegionSummary>,
+    /// The range of addresses expected to be visible soon, based on the scroll velocity.
+    predicted_visible_range: Range<Address>,
+    /// Called every frame with the `predicted_visible_range`.
+    prefetch_callback: Option<PrefetchCallback>,
+    /// Highlights added by the host.
+    highlights: Vec<HostHighlight>,
+    /// The measured layout of the last frame.
+    layout_metrics: LayoutMetrics,
+    metrics: MemoryEditorMetrics,
+    ascii_rows: AsciiRowCache,
+    refresh: RefreshCache,
+    /// Addresses marked in the marker gutter, such as memory breakpoints.
+    markers: BTreeSet<Address>,
+    /// Addresses bookmarked from the context menu of the address column.
+    bookmarks: BTreeSet<Address>,
+    /// The region and address of every quick mark, see [`MemoryEditor::quick_mark`].
+    quick_marks: [Option<(String, Address)>; QUICK_MARK_COUNT],
+    /// Looks up the symbol names shown in the labels column.
+    symbol_provider: Option<SymbolProvider>,
+    /// The columns shown after the ASCII sidebar, with their names.
+    derived_columns: Vec<(String, DerivedColumn)>,
+    /// Decides which addresses may be edited, all of them if `None`.
+    edit_permission: Option<EditPermission>,
+    /// Notified of every rejected input.
+    rejected_input_callback: Option<RejectedInputCallback>,
+    /// The running recording of user actions, if any.
+    recorder: Option<Recorder>,
 }
 
 impl MemoryEditor {
@@ -59,7 +248,34 @@ impl MemoryEditor {
             address_ranges: BTreeMap::new(),
             options: Default::default(),
             frame_data: Default::default(),
-            visible_range: Default::default(),
+            visible: Default::default(),
+            history: Default::default(),
+            comparison: Default::default(),
+            checksum: Default::default(),
+            hashes: None,
+            #[cfg(feature = "histogram")]
+            histogram: None,
+            analysis: Default::default(),
+            search: Default::default(),
+            locations: Default::default(),
+            templates: Vec::new(),
+            template: Default::default(),
+            region_summaries: HashMap::new(),
+            predicted_visible_range: Default::default(),
+            prefetch_callback: None,
+            highlights: Vec::new(),
+            layout_metrics: Default::default(),
+            metrics: Default::default(),
+            ascii_rows: Default::default(),
+            refresh: Default::default(),
+            markers: BTreeSet::new(),
+            bookmarks: BTreeSet::new(),
+            quick_marks: Default::default(),
+            symbol_provider: None,
+            derived_columns: Vec::new(),
+            edit_permission: None,
+            rejected_input_callback: None,
+            recorder: None,
         }
     }
 
@@ -67,7 +283,118 @@ impl MemoryEditor {
     ///
     /// Can be useful for asynchronous memory querying.
     pub fn visible_range(&self) -> &Range<Address> {
-        &self.visible_range
+        &self.visible.addresses
+    }
+
+    /// Returns the visible range of the last frame, including the region and rows it covers.
+    ///
+    /// See also [`Self::visible_range`].
+    pub fn visible_range_details(&self) -> &VisibleRange {
+        &self.visible
+    }
+
+    /// Returns the contiguous selection as a range from its lowest to its highest address, or `None` if nothing is
+    /// selected.
+    ///
+    /// A single selected value is a range of one address. Addresses selected individually, or as a rectangle with
+    /// `Alt`+drag, aren't contiguous and are returned by [`Self::scattered_selection`] instead.
+    pub fn selection(&self) -> Option<Range<Address>> {
+        self.frame_data.selection()
+    }
+
+    /// Returns the individually selected addresses, which are added and removed with Ctrl+click, or selected as a
+    /// rectangle with `Alt`+drag.
+    ///
+    /// Selecting an address without Ctrl clears this selection.
+    pub fn scattered_selection(&self) -> &BTreeSet<Address> {
+        &self.frame_data.view.scattered_selection
+    }
+
+    /// Returns the individually selected addresses, allowing the host to add or remove addresses.
+    pub fn scattered_selection_mut(&mut self) -> &mut BTreeSet<Address> {
+        &mut self.frame_data.view.scattered_selection
+    }
+
+    /// Returns the range of addresses predicted to be visible shortly, based on the current scroll velocity.
+    ///
+    /// When not scrolling this is the same as [`Self::visible_range`].
+    /// Asynchronous hosts can use this to prefetch memory ahead of scrolling, see also [`Self::with_prefetch_callback`].
+    pub fn predicted_visible_range(&self) -> &Range<Address> {
+        &self.predicted_visible_range
+    }
+
+    /// Returns all writes made through the UI this session, which can be exported as a patch.
+    ///
+    /// See [`patch::EditHistory`] for the available formats.
+    pub fn edit_history(&self) -> &EditHistory {
+        &self.history
+    }
+
+    /// Returns all writes made through the UI this session, allowing them to be cleared.
+    pub fn edit_history_mut(&mut self) -> &mut EditHistory {
+        &mut self.history
+    }
+
+    /// Jump to, and highlight, the provided address on the next frame.
+    ///
+    /// This can be called before the first frame is drawn. If the address lies outside the currently selected
+    /// region the editor will first switch to a region which contains it, if there is none the goto is ignored.
+    pub fn queue_goto(&mut self, address: Address) {
+        self.frame_data.view.queued_goto = Some(address);
+    }
+
+    /// Open the editor scrolled to `address` rather than the start of the region, without selecting it.
+    ///
+    /// Like [`Self::queue_goto`] the editor first switches to a region which contains the address. If the editor
+    /// was already drawn it scrolls there on the next frame.
+    #[must_use]
+    pub fn with_initial_address(mut self, address: Address) -> Self {
+        self.set_initial_address(address);
+        self
+    }
+
+    /// Open the editor scrolled to `address`, see [`Self::with_initial_address`].
+    pub fn set_initial_address(&mut self, address: Address) {
+        self.frame_data.view.initial_address = Some(address);
+    }
+
+    /// Perform the goto queued with [`Self::queue_goto`], and the scroll to the initial address, if any.
+    fn process_queued_goto(&mut self) {
+        if let Some(address) = self.frame_data.view.initial_address.take() {
+            if let Some(range) = self.switch_to_region_containing(address) {
+                self.frame_data.view.scroll_line = Some((address - range.start) / self.column_count());
+            }
+        }
+
+        let Some(address) = self.frame_data.view.queued_goto.take() else {
+            return;
+        };
+
+        if let Some(range) = self.switch_to_region_containing(address) {
+            self.frame_data.set_goto_address(address, &range, self.column_count());
+        }
+    }
+
+    /// Select the region containing `address`, preferring the current one, and return its range.
+    fn switch_to_region_containing(&mut self, address: Address) -> Option<Range<Address>> {
+        let (name, range) = self
+            .address_ranges
+            .get_key_value(&self.options.selected_address_range)
+            .filter(|(_, range)| range.contains(&address))
+            .or_else(|| self.address_ranges.iter().find(|(_, range)| range.contains(&address)))?;
+
+        self.options.selected_address_range = name.clone();
+        self.frame_data.switch_region_selection(name);
+        Some(range.clone())
+    }
+
+    /// Queue a patch to be previewed in the UI, where the user can choose to apply or discard it.
+    ///
+    /// The offsets in the patch are relative to `base`, so a patch for a ROM mapped at `0x8000` should use `0x8000`.
+    /// Applying the patch only works when a `write_fn` is provided, replaces any patch that was still pending.
+    pub fn set_pending_patch(&mut self, patch: Patch, base: Address) {
+        self.frame_data.pending_patch = Some(PendingPatch::new(patch, base));
+        self.frame_data.skipped_patch_writes = 0;
     }
 
     /// Create a read-only window and render the memory editor contents within.
@@ -80,19 +407,19 @@ impl MemoryEditor {
     /// * `ctx` - The `egui` context.
     /// * `mem` - The memory from which to read.
     /// * `read_fn` - Any closure which takes in a reference to the memory and an address and returns a `u8` value. It can
-    /// return `None` if the data at the specified address is not available for whatever reason. This will then be rendered
-    /// as `--` (See [`MemoryEditorOptions::none_display_value`])
+    ///   return `None` if the data at the specified address is not available for whatever reason. This will then be rendered
+    ///   as `--` (See [`MemoryEditorOptions::none_display_value`])
     pub fn window_ui_read_only<T: ?Sized>(
         &mut self,
         ctx: &Context,
         is_open: &mut bool,
         mem: &mut T,
         read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
-    ) {
+    ) -> EditorResponse {
         // This needs to exist due to the fact we want to use generics, and `Option` needs to know the size of its contents.
         type DummyWriteFunction<T> = fn(&mut T, Address, u8);
 
-        self.window_ui_impl(ctx, is_open, mem, read_fn, None::<DummyWriteFunction<T>>);
+        self.window_ui_impl(ctx, is_open, mem, read_fn, None::<DummyWriteFunction<T>>)
     }
 
     /// Create a window and render the memory editor contents within.
@@ -105,8 +432,8 @@ impl MemoryEditor {
     /// * `ctx` - The `egui` context.
     /// * `mem` - The memory from which to read.
     /// * `read_fn` - Any closure which takes in a reference to the memory and an address and returns a `u8` value. It can
-    /// return `None` if the data at the specified address is not available for whatever reason. This will then be rendered
-    /// as `--` (See [`MemoryEditorOptions::none_display_value`])
+    ///   return `None` if the data at the specified address is not available for whatever reason. This will then be rendered
+    ///   as `--` (See [`MemoryEditorOptions::none_display_value`])
     /// * `write_fn` - Any closure which can take a reference to the memory, an address, and the value to write.
     pub fn window_ui<T: ?Sized>(
         &mut self,
@@ -115,8 +442,26 @@ impl MemoryEditor {
         mem: &mut T,
         read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
         write_fn: impl FnMut(&mut T, Address, u8),
-    ) {
-        self.window_ui_impl(ctx, is_open, mem, read_fn, Some(write_fn));
+    ) -> EditorResponse {
+        self.window_ui_impl(ctx, is_open, mem, read_fn, Some(write_fn))
+    }
+
+    /// Create a read-only window for a plain slice of memory.
+    ///
+    /// If no address ranges were added a range covering the whole slice is registered automatically, and the slice
+    /// fast-paths of [`Self::complete_tasks_with_slice`] are used.
+    pub fn window_ui_slice_read_only(&mut self, ctx: &Context, is_open: &mut bool, memory: &[u8]) -> EditorResponse {
+        self.prepare_slice(memory);
+        self.window_ui_read_only(ctx, is_open, &mut &*memory, |mem, address| mem.get(address).copied())
+    }
+
+    /// Create a window for a plain slice of memory, which can be edited in the UI.
+    ///
+    /// If no address ranges were added a range covering the whole slice is registered automatically, and the slice
+    /// fast-paths of [`Self::complete_tasks_with_slice`] are used.
+    pub fn window_ui_slice(&mut self, ctx: &Context, is_open: &mut bool, memory: &mut [u8]) -> EditorResponse {
+        self.prepare_slice(memory);
+        self.window_ui(ctx, is_open, memory, slice_read, slice_write)
     }
 
     fn window_ui_impl<T: ?Sized>(
@@ -126,16 +471,48 @@ impl MemoryEditor {
         mem: &mut T,
         read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
         write_fn: Option<impl FnMut(&mut T, Address, u8)>,
-    ) {
-        Window::new(self.window_name.clone())
+    ) -> EditorResponse {
+        self.show_window(ctx, is_open, |editor, ui| {
+            editor.draw_editor_contents_impl(ui, mem, read_fn, write_fn, EditorParts::All)
+        })
+        .unwrap_or_default()
+    }
+
+    /// Show the editor window with the contents drawn by `draw`, returns `None` if the window is closed or collapsed.
+    fn show_window<R>(
+        &mut self,
+        ctx: &Context,
+        is_open: &mut bool,
+        draw: impl FnOnce(&mut Self, &mut Ui) -> R,
+    ) -> Option<R> {
+        self.clamp_column_count_to_screen(ctx);
+
+        let mut window = Window::new(self.window_name.clone())
             .open(is_open)
             .hscroll(false)
             .vscroll(false)
-            .resizable(true)
-            .show(ctx, |ui| {
-                self.shrink_window_ui(ui);
-                self.draw_editor_contents_impl(ui, mem, read_fn, write_fn);
-            });
+            .resizable(true);
+        // Only used if `egui` doesn't remember the window itself.
+        if let Some(rect) = self.options.window_rect {
+            window = window.default_pos(rect.min).default_size(rect.size());
+        }
+
+        let response = window.show(ctx, |ui| {
+            // Shrinking limits the available width to the previous frame's, the responsive layout needs the real one.
+            self.frame_data.view.window_available_width = Some(ui.available_width());
+            self.shrink_window_ui(ui);
+            let inner = draw(self, ui);
+            (inner, ui.min_rect().size())
+        });
+
+        self.options.is_window_open = *is_open;
+        let response = response?;
+        // `default_size` excludes the title bar and margins, storing the outer size would grow the window every restore.
+        let (inner, content_size) = response.inner.unzip();
+        if let Some(content_size) = content_size {
+            self.options.window_rect = Some(Rect::from_min_size(response.response.rect.min, content_size));
+        }
+        inner
     }
 
     /// Draws the actual memory viewer/editor.
@@ -150,11 +527,11 @@ impl MemoryEditor {
         ui: &mut Ui,
         mem: &mut T,
         read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
-    ) {
+    ) -> EditorResponse {
         // This needs to exist due to the fact we want to use generics, and `Option` needs to know the size of its contents.
         type DummyWriteFunction<T> = fn(&mut T, Address, u8);
 
-        self.draw_editor_contents_impl(ui, mem, read_fn, None::<DummyWriteFunction<T>>);
+        self.draw_editor_contents_impl(ui, mem, read_fn, None::<DummyWriteFunction<T>>, EditorParts::All)
     }
 
     /// Draws the actual memory viewer/editor.
@@ -170,84 +547,475 @@ impl MemoryEditor {
         mem: &mut T,
         read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
         write_fn: impl FnMut(&mut T, Address, u8),
-    ) {
-        self.draw_editor_contents_impl(ui, mem, read_fn, Some(write_fn));
+    ) -> EditorResponse {
+        self.draw_editor_contents_impl(ui, mem, read_fn, Some(write_fn), EditorParts::All)
+    }
+
+    /// Draws only the options of the editor, such as the region selection and the collapsible tools.
+    ///
+    /// Together with [`Self::draw_grid_only`] this allows placing the options in a different container than the
+    /// memory grid, such as a side panel. Both should be passed the same memory every frame.
+    ///
+    /// This is the read-only variant. See [`Self::draw_options_only`] for the read-write variant.
+    pub fn draw_options_only_read_only<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        mem: &mut T,
+        read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
+    ) -> EditorResponse {
+        type DummyWriteFunction<T> = fn(&mut T, Address, u8);
+
+        self.draw_editor_contents_impl(ui, mem, read_fn, None::<DummyWriteFunction<T>>, EditorParts::Options)
+    }
+
+    /// Draws only the options of the editor, see [`Self::draw_options_only_read_only`].
+    pub fn draw_options_only<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        mem: &mut T,
+        read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
+        write_fn: impl FnMut(&mut T, Address, u8),
+    ) -> EditorResponse {
+        self.draw_editor_contents_impl(ui, mem, read_fn, Some(write_fn), EditorParts::Options)
+    }
+
+    /// Draws only the memory grid of the editor, along with any writes waiting for confirmation.
+    ///
+    /// See [`Self::draw_options_only_read_only`] for drawing the options elsewhere.
+    ///
+    /// This is the read-only variant. See [`Self::draw_grid_only`] for the read-write variant.
+    pub fn draw_grid_only_read_only<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        mem: &mut T,
+        read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
+    ) -> EditorResponse {
+        type DummyWriteFunction<T> = fn(&mut T, Address, u8);
+
+        self.draw_editor_contents_impl(ui, mem, read_fn, None::<DummyWriteFunction<T>>, EditorParts::Grid)
+    }
+
+    /// Draws only the memory grid of the editor, see [`Self::draw_grid_only_read_only`].
+    pub fn draw_grid_only<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        mem: &mut T,
+        read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
+        write_fn: impl FnMut(&mut T, Address, u8),
+    ) -> EditorResponse {
+        self.draw_editor_contents_impl(ui, mem, read_fn, Some(write_fn), EditorParts::Grid)
+    }
+
+    /// Draws the memory viewer for a plain slice of memory.
+    ///
+    /// This is the read-only variant. See [`Self::draw_editor_contents_slice`] for the read-write variant.
+    pub fn draw_editor_contents_slice_read_only(&mut self, ui: &mut Ui, memory: &[u8]) -> EditorResponse {
+        self.prepare_slice(memory);
+        self.draw_editor_contents_read_only(ui, &mut &*memory, |mem, address| mem.get(address).copied())
+    }
+
+    /// Draws the memory editor for a plain slice of memory.
+    ///
+    /// See [`Self::window_ui_slice`] for how the address range is determined.
+    pub fn draw_editor_contents_slice(&mut self, ui: &mut Ui, memory: &mut [u8]) -> EditorResponse {
+        self.prepare_slice(memory);
+        self.draw_editor_contents(ui, memory, slice_read, slice_write)
+    }
+
+    /// Register the address range for the slice if needed, and finish any running tasks with the slice fast-path.
+    fn prepare_slice(&mut self, memory: &[u8]) {
+        if self.address_ranges.is_empty() || self.frame_data.is_slice_range_registered {
+            self.set_address_range(SLICE_RANGE_NAME, 0..memory.len());
+            self.frame_data.is_slice_range_registered = true;
+        }
+
+        self.complete_tasks_with_slice(memory, 0);
     }
 
     fn draw_editor_contents_impl<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        mem: &mut T,
+        read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
+        write_fn: Option<impl FnMut(&mut T, Address, u8)>,
+        parts: EditorParts,
+    ) -> EditorResponse {
+        self.try_draw_editor_contents_impl(ui, mem, read_fn, write_fn, parts)
+            .unwrap_or_else(|error| {
+                self.draw_error(ui, &error);
+                EditorResponse::default()
+            })
+    }
+
+    fn try_draw_editor_contents_impl<T: ?Sized>(
         &mut self,
         ui: &mut Ui,
         mem: &mut T,
         mut read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
-        mut write_fn: Option<impl FnMut(&mut T, Address, u8)>,
-    ) {
-        assert!(
-            !self.address_ranges.is_empty(),
-            "At least one address range needs to be added to render the contents!"
-        );
+        write_fn: Option<impl FnMut(&mut T, Address, u8)>,
+        parts: EditorParts,
+    ) -> Result<EditorResponse, MemoryEditorError> {
+        let address_space = self.validate_options()?;
+
+        let mut response = EditorResponse::default();
+        #[cfg(not(target_arch = "wasm32"))]
+        let start_time = std::time::Instant::now();
+        let time = ui.input(|i| i.time);
+        // The options and grid can be drawn separately, the work which should only happen once a frame is tied to
+        // the first of them.
+        let is_new_frame = self.frame_data.last_frame_time.replace(time) != Some(time);
+        if is_new_frame {
+            self.metrics = MemoryEditorMetrics::default();
+        }
+        let widgets_before = metrics::widget_count(ui);
+        let reads = Cell::new(0);
+        let mut read_fn = |mem: &mut T, address: Address| {
+            reads.set(reads.get() + 1);
+            read_fn(mem, address)
+        };
+
+        let refresh = RefCell::new(std::mem::take(&mut self.refresh));
+        if let Some(until_refresh) =
+            refresh
+                .borrow_mut()
+                .start_frame(time, self.options.refresh_interval, &self.visible.addresses)
+        {
+            ui.ctx().request_repaint_after(until_refresh);
+        }
+        self.ascii_rows.start_frame(refresh.borrow().generation());
+        let lookups = Cell::new(0);
+        let mut read_fn = |mem: &mut T, address: Address| {
+            lookups.set(lookups.get() + 1);
+            refresh.borrow_mut().read(mem, &mut read_fn, address)
+        };
+        let write_fn = write_fn.map(|mut write_fn| {
+            let refresh = &refresh;
+            move |mem: &mut T, address: Address, value: u8| {
+                refresh.borrow_mut().invalidate(address);
+                write_fn(mem, address, value)
+            }
+        });
+
+        // While locked the editor behaves exactly as if no `write_fn` was provided.
+        // Whether the values can be edited is up to the grid, if it's drawn separately from the options.
+        if parts != EditorParts::Options {
+            self.frame_data.has_write_fn = write_fn.is_some();
+        }
+        let mut write_fn = write_fn.filter(|_| !self.options.is_write_locked);
+        if parts != EditorParts::Options && write_fn.is_none() {
+            self.frame_data.view.selected_edit_address = None;
+        }
+
+        self.start_recorder_frame(time);
+        if let Some(write_fn) = write_fn.as_mut() {
+            if is_new_frame {
+                self.flush_queued_writes(ui.ctx(), mem, write_fn);
+            }
+            self.perform_replayed_writes(mem, &mut read_fn, write_fn);
+        }
+
+        self.frame_data
+            .switch_region_selection(&self.options.selected_address_range);
+        self.process_queued_goto();
+        self.update_highlights(ui.ctx());
+        if is_new_frame {
+            self.step_tasks(ui.ctx(), mem, &mut read_fn);
+        }
+
+        if parts != EditorParts::Grid {
+            self.draw_options_area(ui, &address_space, mem, &mut read_fn, &mut write_fn);
+            // The region might have been switched in the options.
+            self.frame_data
+                .switch_region_selection(&self.options.selected_address_range);
+        }
+
+        let result = if parts == EditorParts::Options {
+            Ok(())
+        } else {
+            if !self.frame_data.unconfirmed_writes.is_empty() {
+                self.draw_write_confirmation(ui, mem, &mut read_fn, &mut write_fn);
+            }
+            let count = self.frame_data.queued_write_count();
+            if count > 0 {
+                let text = fill(&self.options.labels.queued_writes, &[("count", &count)]);
+                ui.colored_label(ui.visuals().warn_fg_color, text);
+            }
+            // The warning lasts until the selection changes.
+            match self.frame_data.view.oversized_copy {
+                Some((ref selection, count)) if *selection == self.frame_data.selection() => {
+                    let text = fill(
+                        &self.options.labels.selection_too_large,
+                        &[
+                            ("count", &format!("{:#X}", count)),
+                            ("limit", &format!("{:#X}", self.options.max_copy_bytes)),
+                        ],
+                    );
+                    ui.colored_label(ui.visuals().warn_fg_color, text);
+                }
+                Some(_) => self.frame_data.view.oversized_copy = None,
+                None => {}
+            }
+
+            if parts == EditorParts::All {
+                ui.separator();
+            }
+
+            // Validate again, the newly selected region could be empty.
+            self.validate_options().map(|address_space| {
+                self.draw_grid(ui, mem, &mut read_fn, &mut write_fn, address_space, &mut response);
+            })
+        };
 
-        self.draw_options_area(ui, mem, &mut read_fn);
+        response.dropped_files = self.handle_dropped_files(ui, ui.min_rect());
+        self.finish_recorder_frame();
+
+        self.refresh = refresh.into_inner();
+        self.metrics.reads += reads.get();
+        self.metrics.cached_reads += lookups.get() - reads.get();
+        self.metrics.widgets += metrics::widget_count(ui).saturating_sub(widgets_before);
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let draw_time = self.metrics.draw_time.unwrap_or_default();
+            self.metrics.draw_time = Some(draw_time + start_time.elapsed());
+        }
 
-        ui.separator();
+        result.map(|_| response)
+    }
 
+    /// Draw the grid of memory values, see [`Self::draw_grid_only`].
+    fn draw_grid<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        mem: &mut T,
+        mut read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
+        write_fn: &mut Option<impl FnMut(&mut T, Address, u8)>,
+        address_space: Range<Address>,
+        response: &mut EditorResponse,
+    ) {
         let MemoryEditorOptions {
-            show_ascii,
-            column_count,
             address_text_colour,
-            highlight_text_colour,
             selected_address_range,
-            memory_editor_address_text_style,
             ..
         } = self.options.clone();
 
+        let address_font = self.options.address_font(ui.style());
         let line_height = self.get_line_height(ui);
-        let address_space = self.address_ranges.get(&selected_address_range).unwrap().clone();
-        // This is janky, but can't think of a better way.
-        let address_characters = format!("{:X}", address_space.end - 1).chars().count();
-        let max_lines = (address_space.len() + column_count - 1) / column_count; // div_ceil
+        let address_characters = self.address_characters();
+
+        self.update_responsive_layout(ui, address_characters);
+        let column_count = self.column_count();
+        let show_ascii = self.is_ascii_shown();
+        let show_labels = self.options.show_label_column && self.symbol_provider.is_some();
+        let grid_columns = self.grid_columns(show_ascii, show_labels);
+        self.update_cell_sizes(ui.ctx(), line_height);
+        let max_lines = address_space.len().div_ceil(column_count);
 
         // For when we're editing memory, don't use the `Response` object as that would screw over downward scrolling.
         self.handle_keyboard_edit_input(&address_space, ui.ctx());
+        if matches!(self.frame_data.view.selected_edit_address, Some(address) if !self.is_editable(mem, &mut read_fn, address))
+        {
+            self.frame_data.set_selected_edit_address(None, &address_space);
+        }
+        self.handle_select_all_shortcut(ui.ctx(), &address_space);
+        self.handle_copy_shortcut(ui.ctx(), mem, &mut read_fn);
+        if let Some(write_fn) = write_fn.as_mut() {
+            self.handle_undo_shortcut(ui.ctx(), mem, &mut read_fn, write_fn);
+            self.handle_paste(ui.ctx(), mem, &mut read_fn, write_fn, &address_space);
+        }
+        self.handle_find_shortcuts(ui.ctx(), &address_space);
+        self.handle_quick_mark_shortcuts(ui.ctx(), &address_space);
+        if !ui.input(|i| i.pointer.primary_down()) {
+            self.frame_data.view.is_drag_selecting = false;
+            self.frame_data.view.rectangle_anchor = None;
+        }
 
         let mut scroll = ScrollArea::vertical()
-            .id_salt(selected_address_range)
+            .id_salt(&selected_address_range)
             .max_height(f32::INFINITY)
             .auto_shrink([false, true]);
 
+        // Restore where we left off when switching back to a region.
+        if self.frame_data.view.previous_region != selected_address_range {
+            if let Some(&offset) = self.frame_data.view.region_scroll_offsets.get(&selected_address_range) {
+                scroll = scroll.vertical_scroll_offset(offset);
+            }
+            self.frame_data.view.previous_region = selected_address_range.clone();
+        }
+
         // Scroll to the goto area address line.
-        if let Some(line) = self.frame_data.goto_address_line.take() {
+        if let Some(line) = self.frame_data.view.goto_address_line.take() {
             let new_offset = (line_height + ui.spacing().item_spacing.y) * (line as f32);
-            scroll = scroll.vertical_scroll_offset(new_offset);
+
+            if self.options.animate_goto {
+                let current_offset = self
+                    .frame_data
+                    .view
+                    .region_scroll_offsets
+                    .get(&selected_address_range)
+                    .copied()
+                    .unwrap_or_default();
+                let now = ui.input(|i| i.time);
+                self.frame_data.view.goto_animation = Some((current_offset, new_offset, now));
+            } else {
+                self.frame_data.view.goto_animation = None;
+                scroll = scroll.vertical_scroll_offset(new_offset);
+            }
+        }
+
+        // Scrolls which aren't gotos, such as from an `EditorSync`, are never animated.
+        if let Some(line) = self.frame_data.view.scroll_line.take() {
+            self.frame_data.view.goto_animation = None;
+            scroll = scroll.vertical_scroll_offset((line_height + ui.spacing().item_spacing.y) * (line as f32));
         }
 
-        scroll.show_rows(ui, line_height, max_lines, |ui, line_range| {
+        if let Some((from, to, start_time)) = self.frame_data.view.goto_animation {
+            let progress = ((ui.input(|i| i.time) - start_time) / GOTO_ANIMATION_SECONDS).min(1.0) as f32;
+            // Ease out, to decelerate towards the target.
+            let eased = 1.0 - (1.0 - progress).powi(3);
+            scroll = scroll.vertical_scroll_offset(egui::lerp(from..=to, eased));
+
+            if progress >= 1.0 {
+                self.frame_data.view.goto_animation = None;
+            } else {
+                ui.ctx().request_repaint();
+            }
+        }
+
+        let output = scroll.show_rows(ui, line_height, max_lines, |ui, line_range| {
             // Persist the visible range for future queries.
             let start_address_range = address_space.start + (line_range.start * column_count);
             let end_address_range = address_space.start + (line_range.end * column_count);
-            self.visible_range = start_address_range..end_address_range;
+            let addresses = start_address_range..end_address_range;
+            if self.visible.addresses != addresses || self.visible.region != self.options.selected_address_range {
+                response.visible_range_changed = Some(addresses.clone());
+            }
+            self.visible = VisibleRange {
+                region: self.options.selected_address_range.clone(),
+                is_scrolling: self.visible.addresses != addresses,
+                addresses,
+                rows: line_range.clone(),
+            };
+            self.update_predicted_visible_range(ui.input(|i| i.time), &address_space);
 
-            egui::Grid::new("mem_edit_grid")
+            let grid_response = egui::Grid::new("mem_edit_grid")
                 .striped(true)
-                .spacing(Vec2::new(15.0, ui.style().spacing.item_spacing.y))
+                .spacing(Vec2::new(GRID_COLUMN_SPACING, ui.style().spacing.item_spacing.y))
                 .show(ui, |ui| {
                     ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
-                    ui.style_mut().spacing.item_spacing.x = 3.0;
+                    ui.style_mut().spacing.item_spacing.x = CELL_SPACING;
+
+                    let highlight_colour = self.frame_data.highlight_colour(&self.options);
 
                     for start_row in line_range.clone() {
                         let start_address = address_space.start + (start_row * column_count);
                         let line_range = start_address..start_address + column_count;
-                        let highlight_in_range = matches!(self.frame_data.selected_highlight_address, Some(address) if line_range.contains(&address));
-
-                        let start_text = RichText::new(format!("0x{:01$X}:", start_address, address_characters))
-                            .color(if highlight_in_range { highlight_text_colour } else { address_text_colour })
-                            .text_style(memory_editor_address_text_style.clone());
+                        let highlight_in_range = matches!(self.frame_data.view.selected_highlight_address, Some(address) if line_range.contains(&address));
 
-                        ui.label(start_text);
+                        self.metrics.rows_drawn += 1;
+                        self.metrics.bytes_drawn += line_range.end.min(address_space.end) - start_address;
 
-                        self.draw_memory_values(ui, mem, &mut read_fn, &mut write_fn, start_address, &address_space);
-
-                        if show_ascii {
-                            self.draw_ascii_sidebar(ui, mem, &mut read_fn, start_address, &address_space);
+                        for column in &grid_columns {
+                            match column {
+                                GridColumn::MarkerGutter => {
+                                    if self.draw_marker_gutter(ui, line_height, &line_range) {
+                                        response.toggled_markers.push(start_address);
+                                    }
+                                }
+                                GridColumn::Address => {
+                                    let display_base = self.options.display_base;
+                                    let start_text = RichText::new(row::address_text(
+                                        start_address,
+                                        address_characters,
+                                        display_base,
+                                    ))
+                                    .color(if highlight_in_range { highlight_colour } else { address_text_colour })
+                                    .font(address_font.clone());
+                                    let response = ui.add(Label::new(start_text).sense(Sense::click()));
+
+                                    let mut action = None;
+                                    response.context_menu(|ui| {
+                                        let labels = &self.options.labels;
+                                        let is_bookmarked = self.bookmarks.contains(&start_address);
+                                        let is_base = display_base == Some(start_address);
+                                        let entries = [
+                                            (&labels.copy_address, AddressMenuAction::CopyAddress),
+                                            (&labels.copy_row, AddressMenuAction::CopyRow),
+                                            (
+                                                if is_bookmarked { &labels.remove_bookmark } else { &labels.bookmark_row },
+                                                AddressMenuAction::ToggleBookmark,
+                                            ),
+                                            (
+                                                if is_base { &labels.clear_display_base } else { &labels.set_display_base },
+                                                AddressMenuAction::ToggleDisplayBase,
+                                            ),
+                                        ];
+
+                                        for (label, entry) in entries {
+                                            if ui.button(label).clicked() {
+                                                action = Some(entry);
+                                                ui.close_menu();
+                                            }
+                                        }
+                                    });
+
+                                    match action {
+                                        Some(AddressMenuAction::CopyAddress) => {
+                                            let text =
+                                                row::copied_address_text(start_address, address_characters, display_base);
+                                            ui.ctx().copy_text(text);
+                                        }
+                                        Some(AddressMenuAction::CopyRow) => {
+                                            let values: Vec<Option<u8>> = (line_range.start
+                                                ..line_range.end.min(address_space.end))
+                                                .map(|address| read_fn(mem, address))
+                                                .collect();
+                                            let text = row::row_text(
+                                                &self.options,
+                                                start_address,
+                                                address_characters,
+                                                &values,
+                                                show_ascii,
+                                            );
+                                            ui.ctx().copy_text(text);
+                                        }
+                                        Some(AddressMenuAction::ToggleBookmark) => {
+                                            let is_bookmarked = self.bookmarks.contains(&start_address);
+                                            self.set_bookmark(start_address, !is_bookmarked);
+                                        }
+                                        Some(AddressMenuAction::ToggleDisplayBase) => {
+                                            self.options.display_base =
+                                                (display_base != Some(start_address)).then_some(start_address);
+                                        }
+                                        None => {}
+                                    }
+                                }
+                                GridColumn::Values => {
+                                    self.draw_memory_values(
+                                        ui,
+                                        mem,
+                                        &mut read_fn,
+                                        write_fn,
+                                        start_address,
+                                        &address_space,
+                                    );
+                                }
+                                GridColumn::Ascii => {
+                                    self.draw_ascii_sidebar(ui, mem, &mut read_fn, start_address, &address_space);
+                                }
+                                GridColumn::Labels => {
+                                    self.draw_label_column(ui, line_range.clone(), &address_space);
+                                }
+                                GridColumn::Derived(index) => {
+                                    self.draw_derived_column(
+                                        ui,
+                                        *index,
+                                        mem,
+                                        &mut read_fn,
+                                        start_address,
+                                        &address_space,
+                                    );
+                                }
+                            }
                         }
 
                         ui.end_row();
@@ -255,7 +1023,62 @@ impl MemoryEditor {
                 });
             // After we've drawn the area we want to resize to we want to save this size for the next frame.
             // In case it has become smaller we'll shrink the window.
-            self.frame_data.previous_frame_editor_width = ui.min_rect().width();
+            self.frame_data.view.previous_frame_editor_width = ui.min_rect().width();
+
+            self.layout_metrics = LayoutMetrics {
+                row_height: line_height,
+                row_pitch: line_height + ui.spacing().item_spacing.y,
+                bytes_per_row: column_count,
+                is_ascii_shown: show_ascii,
+                grid_rect: grid_response.response.rect,
+                viewport_rect: ui.clip_rect(),
+                first_row: line_range.start,
+            };
+        });
+
+        self.frame_data
+            .view
+            .region_scroll_offsets
+            .insert(selected_address_range, output.state.offset.y);
+        self.ascii_rows.retain(&self.visible.addresses);
+
+        if self.options.show_scrollbar_ticks {
+            self.draw_scrollbar_ticks(ui, output.inner_rect, &address_space);
+        }
+        if self.options.show_scroll_position {
+            self.draw_scroll_position(ui, output.inner_rect, &address_space);
+        }
+        if write_fn.is_some() {
+            self.draw_keypad(ui, output.inner_rect);
+        }
+    }
+
+    /// Draw the address of the first visible row over the top right corner of the grid, see
+    /// [`MemoryEditorOptions::show_scroll_position`].
+    fn draw_scroll_position(&self, ui: &mut Ui, viewport: egui::Rect, address_space: &Range<Address>) {
+        let start = self.visible.addresses.start;
+        let percentage = (start - address_space.start) as f64 / address_space.len().max(1) as f64 * 100.0;
+        let text = fill(
+            &self.options.labels.scroll_position,
+            &[
+                ("address", &format!("{:#X}", start)),
+                ("percentage", &format!("{:.0}", percentage)),
+            ],
+        );
+
+        // Leave the scrollbar uncovered, such that it can still be dragged.
+        let scroll = &ui.spacing().scroll;
+        let right = viewport.right() - scroll.bar_width - scroll.bar_outer_margin * 2.0;
+        let rect = egui::Rect::from_min_max(viewport.left_top(), egui::pos2(right, viewport.bottom()));
+        // A child doesn't move the cursor of the parent, and thus floats over the grid.
+        let mut overlay = ui.new_child(
+            egui::UiBuilder::new()
+                .max_rect(rect)
+                .layout(egui::Layout::right_to_left(egui::Align::Min)),
+        );
+
+        egui::Frame::popup(overlay.style()).show(&mut overlay, |ui| {
+            ui.label(RichText::new(text).font(self.options.address_font(ui.style())));
         });
     }
 
@@ -268,19 +1091,47 @@ impl MemoryEditor {
         start_address: Address,
         address_space: &Range<Address>,
     ) {
+        let column_count = self.column_count();
         let frame_data = &mut self.frame_data;
         let options = &self.options;
-        let mut read_only = frame_data.selected_edit_address.is_none() || write_fn.is_none();
-
-        // div_ceil
-        for grid_column in 0..(options.column_count + 7) / 8 {
-            let start_address = start_address + 8 * grid_column;
-
-            // Each grid column is 8 bytes, where each byte is one 'sub-column'.
+        let highlights = &self.highlights;
+        let mut write_request = None;
+        let mut scattered_write_request = None;
+        let mut is_copy_hex_requested = false;
+        let mut is_copy_text_requested = false;
+        let mut copy_address_request = None;
+        let mut rejection = None;
+        // Pressing the keypad takes the focus from the edited value, which shouldn't stop the editing.
+        let is_using_keypad = frame_data
+            .view
+            .keypad_rect
+            .zip(ui.input(|i| i.pointer.interact_pos()))
+            .is_some_and(|(keypad, pointer)| keypad.contains(pointer));
+        let comparison = self
+            .comparison
+            .comparison
+            .as_ref()
+            .filter(|comparison| comparison.region == options.selected_address_range);
+        let search = self
+            .search
+            .search
+            .as_ref()
+            .filter(|search| search.task.is_none() && search.region == options.selected_address_range);
+        let mut read_only = frame_data.view.selected_edit_address.is_none() || write_fn.is_none();
+        let edit_permission = &self.edit_permission;
+
+        let cell_format = options.cell_format;
+        let bytes_per_group = cell_format.bytes_per_group();
+        let value_font = options.value_font(ui.style());
+
+        for grid_column in 0..column_count.div_ceil(bytes_per_group) {
+            let start_address = start_address + bytes_per_group * grid_column;
+
+            // Each grid column is a group of bytes, usually 8, where each byte is one 'sub-column'.
             ui.horizontal(|ui| {
-                let column_count = (options.column_count - 8 * grid_column).min(8);
+                let group_size = (column_count - bytes_per_group * grid_column).min(bytes_per_group);
 
-                for column_index in 0..column_count {
+                for column_index in 0..group_size {
                     let memory_address = start_address + column_index;
 
                     if !address_space.contains(&memory_address) {
@@ -288,152 +1139,868 @@ impl MemoryEditor {
                     }
 
                     let mem_val: Option<u8> = read_fn(mem, memory_address);
-                    // If the read function can't read for whatever reason we'll just assume some temporary `--` value.
-                    let label_text = match mem_val {
-                        Some(val) => format!("{:02X}", val),
-                        None => options.none_display_value.clone(),
-                    };
-
-                    // Memory Value Labels
-                    if !read_only
-                        && matches!(frame_data.selected_edit_address, Some(address) if address == memory_address)
-                    {
-                        // For Editing
-                        let response = ui.add(
-                            TextEdit::singleline(&mut frame_data.selected_edit_address_string)
-                                .desired_width(frame_data.previous_frame_text_edit_size)
-                                .margin(Margin::symmetric(0., 0.))
-                                .font(options.memory_editor_text_style.clone())
-                                .hint_text(label_text)
-                                .id_source(frame_data.selected_edit_address),
-                        );
-
-                        if frame_data.selected_edit_address_request_focus {
-                            frame_data.selected_edit_address_request_focus = false;
-                            response.request_focus();
-                        }
 
-                        // Filter out any non Hex-Digit, there doesn't seem to be a method in TextEdit for this.
-                        frame_data
-                            .selected_edit_address_string
-                            .retain(|c| c.is_ascii_hexdigit());
+                    row::value_cells(ui, cell_format, |ui, sub_cell| {
+                        // Memory Value Labels
+                        if !read_only
+                            && matches!(frame_data.view.selected_edit_address, Some(address) if address == memory_address)
+                            && frame_data.view.selected_edit_sub_cell == sub_cell
+                        {
+                            // For Editing
+                            let response = ui.add(
+                                TextEdit::singleline(&mut frame_data.view.selected_edit_address_string)
+                                    .desired_width(frame_data.view.previous_frame_text_edit_size)
+                                    .margin(Margin::symmetric(0., 0.))
+                                    .font(value_font.clone())
+                                    .hint_text(row::value_text(options, mem_val, sub_cell))
+                                    .id_source(frame_data.view.selected_edit_address),
+                            );
+
+                            if frame_data.view.selected_edit_address_request_focus {
+                                frame_data.view.selected_edit_address_request_focus = false;
+                                response.request_focus();
+                            }
 
-                        // Don't want more than 2 digits
-                        if frame_data.selected_edit_address_string.chars().count() >= 2 {
-                            let next_address = memory_address + 1;
-                            let new_value = u8::from_str_radix(&frame_data.selected_edit_address_string[0..2], 16);
+                            // Filter out any non Hex-Digit, there doesn't seem to be a method in TextEdit for this.
+                            let edit_string = &mut frame_data.view.selected_edit_address_string;
+                            if let Some(character) = edit_string.chars().find(|c| !c.is_ascii_hexdigit()) {
+                                rejection = Some(RejectedInput::InvalidCharacter {
+                                    address: memory_address,
+                                    character,
+                                });
+                                edit_string.retain(|c| c.is_ascii_hexdigit());
+                            }
+                            feedback::paint_rejected_input(
+                                ui,
+                                frame_data.rejected_input,
+                                memory_address,
+                                response.rect,
+                            );
+
+                            // Don't want more digits than the cell can display
+                            let digits = cell_format.digits();
+                            if frame_data.view.selected_edit_address_string.chars().count() >= digits {
+                                let new_value =
+                                    u8::from_str_radix(&frame_data.view.selected_edit_address_string[0..digits], 16);
+
+                                if let Ok(value) = new_value {
+                                    if frame_data.view.scattered_selection.contains(&memory_address) {
+                                        // Moving on to the next address below clears the selection, so copy it now.
+                                        let addresses = frame_data.view.scattered_selection.clone();
+                                        scattered_write_request = Some((addresses, sub_cell, value));
+                                    } else {
+                                        let value = cell_format.apply(mem_val.unwrap_or(0), sub_cell, value);
+                                        write_request = Some((memory_address, value));
+                                    }
+                                }
 
-                            if let Ok(value) = new_value {
-                                if let Some(write_fns) = write_fn.as_mut() {
-                                    write_fns(mem, memory_address, value);
+                                if sub_cell + 1 < cell_format.sub_cells() {
+                                    frame_data.set_selected_edit_cell(memory_address, sub_cell + 1, address_space);
+                                } else {
+                                    frame_data.set_selected_edit_address(Some(memory_address + 1), address_space);
+                                }
+                            } else if !response.has_focus() && !is_using_keypad {
+                                // We use has_focus() instead of response.inner.lost_focus() due to the latter
+                                // having a bug where it doesn't detect if it lost focus when you scroll.
+                                frame_data.set_selected_edit_address(None, address_space);
+                                read_only = true;
+                            }
+                        } else {
+                            // Read-only values.
+                            let mut text = row::value_cell_text(options, mem_val, sub_cell, &value_font, ui.style());
+
+                            if let Some((colour, background)) =
+                                search.and_then(|search| search.match_colours(memory_address, options))
+                            {
+                                text = text.background_color(background);
+                                if let Some(colour) = colour {
+                                    text = text.color(colour);
                                 }
                             }
 
-                            frame_data.set_selected_edit_address(Some(next_address), address_space);
-                        } else if !response.has_focus() {
-                            // We use has_focus() instead of response.inner.lost_focus() due to the latter
-                            // having a bug where it doesn't detect if it lost focus when you scroll.
-                            frame_data.set_selected_edit_address(None, address_space);
-                            read_only = true;
-                        }
-                    } else {
-                        // Read-only values.
-                        let mut text = RichText::new(label_text).text_style(options.memory_editor_text_style.clone());
+                            if frame_data.should_highlight(memory_address) {
+                                text = text.color(frame_data.highlight_colour(options));
+                            }
 
-                        if options.show_zero_colour && (matches!(mem_val, Some(val) if val == 0) || mem_val.is_none()) {
-                            text = text.color(options.zero_colour);
-                        } else {
-                            text = text.color(ui.style().visuals.text_color());
-                        };
+                            if frame_data
+                                .should_subtle_highlight(memory_address, options.data_preview.selected_data_format)
+                            {
+                                text = text.background_color(ui.style().visuals.code_bg_color);
+                            }
 
-                        if frame_data.should_highlight(memory_address) {
-                            text = text.color(options.highlight_text_colour);
-                        }
+                            if let Some(colour) = Self::host_highlight_colour(highlights, memory_address) {
+                                text = text.background_color(colour);
+                            }
 
-                        if frame_data.should_subtle_highlight(memory_address, options.data_preview.selected_data_format)
-                        {
-                            text = text.background_color(ui.style().visuals.code_bg_color);
-                        }
+                            if comparison.is_some_and(|comparison| comparison.is_mismatch(memory_address)) {
+                                text = text.color(ui.style().visuals.error_fg_color);
+                            }
 
-                        let response = Label::new(text).sense(Sense::click()).ui(ui);
-                        // For use with the `Edit` widget, keep track of the size of ordinary display to keep column jitter at bay
-                        frame_data.previous_frame_text_edit_size = response.rect.width();
+                            if mem_val
+                                .zip(options.fill_diff_value)
+                                .is_some_and(|(value, fill)| value != fill)
+                            {
+                                text = text.color(ui.style().visuals.error_fg_color);
+                            }
 
-                        // Right click always selects.
-                        if response.secondary_clicked() {
-                            frame_data.set_highlight_address(memory_address);
-                        }
+                            if matches!(&frame_data.pending_patch, Some(patch) if patch.contains(memory_address)) {
+                                text = text.color(ui.style().visuals.warn_fg_color);
+                            }
+
+                            if frame_data.queued_value(memory_address).is_some() {
+                                text = text.color(ui.style().visuals.warn_fg_color);
+                            }
+
+                            let label = Label::new(text).sense(Sense::click_and_drag());
+                            let response = match frame_data.view.value_cell_size {
+                                Some(size) => ui.add_sized(size, label),
+                                None => label.ui(ui),
+                            };
+                            // For use with the `Edit` widget, keep track of the size of ordinary display to keep column jitter at bay
+                            frame_data.view.previous_frame_text_edit_size = response.rect.width();
+                            feedback::paint_rejected_input(
+                                ui,
+                                frame_data.rejected_input,
+                                memory_address,
+                                response.rect,
+                            );
+
+                            // Dragging selects every value from where the drag started up to the one under the pointer,
+                            // holding Alt selects the same columns in every row instead.
+                            if response.drag_started_by(PointerButton::Primary) {
+                                if ui.input(|i| i.modifiers.alt) {
+                                    frame_data.start_rectangle_selection(memory_address);
+                                } else {
+                                    frame_data.start_drag_selection(memory_address);
+                                }
+                            } else if frame_data.view.is_drag_selecting && response.contains_pointer() {
+                                if frame_data.view.rectangle_anchor.is_some() {
+                                    frame_data.extend_rectangle_selection(memory_address, address_space, column_count);
+                                } else {
+                                    frame_data.extend_selection(memory_address);
+                                }
+                            }
+
+                            // Right click selects, holding shift extends the current selection. Values which are
+                            // already selected keep the selection, such that the context menu can copy all of it.
+                            if response.secondary_clicked() {
+                                if ui.input(|i| i.modifiers.shift) {
+                                    frame_data.extend_selection(memory_address);
+                                } else if !frame_data.should_highlight(memory_address) {
+                                    frame_data.set_highlight_address(memory_address);
+                                }
+                            }
 
-                        // Left click depends on read only mode.
-                        if response.clicked() {
-                            if write_fn.is_some() {
-                                frame_data.set_selected_edit_address(Some(memory_address), address_space);
-                            } else {
-                                frame_data.set_highlight_address(memory_address);
+                            response.context_menu(|ui| {
+                                if ui.button(&options.labels.copy_hex).clicked() {
+                                    is_copy_hex_requested = true;
+                                    ui.close_menu();
+                                }
+                                if ui.button(&options.labels.copy_text).clicked() {
+                                    is_copy_text_requested = true;
+                                    ui.close_menu();
+                                }
+                                if ui.button(&options.labels.copy_address).clicked() {
+                                    copy_address_request = Some(memory_address);
+                                    ui.close_menu();
+                                }
+                            });
+
+                            // Ctrl+click toggles the address in the scattered selection and starts editing it,
+                            // Shift+click extends the current selection, otherwise left click depends on read only mode.
+                            let is_extending =
+                                ui.input(|i| i.modifiers.shift) && frame_data.view.selected_highlight_address.is_some();
+                            if response.clicked() && ui.input(|i| i.modifiers.command) {
+                                if frame_data.view.scattered_selection.remove(&memory_address) {
+                                    frame_data.view.selected_edit_address = None;
+                                } else {
+                                    frame_data.view.scattered_selection.insert(memory_address);
+                                    if write_fn.is_some()
+                                        && is_edit_permitted(edit_permission, memory_address, &mut |address| {
+                                            read_fn(mem, address)
+                                        })
+                                    {
+                                        frame_data.set_selected_edit_cell(memory_address, sub_cell, address_space);
+                                    }
+                                }
+                            } else if response.clicked() && is_extending {
+                                frame_data.view.selected_edit_address = None;
+                                frame_data.extend_selection(memory_address);
+                            } else if response.clicked() {
+                                let is_editable = write_fn.is_some()
+                                    && is_edit_permitted(edit_permission, memory_address, &mut |address| {
+                                        read_fn(mem, address)
+                                    });
+
+                                if is_editable && cell_format.is_toggle() {
+                                    let value = cell_format.toggle(mem_val.unwrap_or(0), sub_cell);
+                                    write_request = Some((memory_address, value));
+                                } else if is_editable {
+                                    frame_data.set_selected_edit_cell(memory_address, sub_cell, address_space);
+                                } else {
+                                    frame_data.set_highlight_address(memory_address);
+                                }
                             }
                         }
-                    }
+                    });
                 }
             });
         }
+
+        if let Some(rejection) = rejection {
+            self.reject_input(rejection);
+        }
+
+        if is_copy_hex_requested && self.is_selection_copyable() {
+            let text = self.selection_hex(mem, read_fn);
+            ui.ctx().copy_text(text);
+        } else if is_copy_text_requested && self.is_selection_copyable() {
+            let text = self.selection_text(mem, read_fn);
+            ui.ctx().copy_text(text);
+        } else if let Some(address) = copy_address_request {
+            let text = row::copied_address_text(address, self.address_characters(), self.options.display_base);
+            ui.ctx().copy_text(text);
+        }
+
+        if let (Some((address, value)), Some(write_fn)) = (write_request, write_fn.as_mut()) {
+            self.write_value(mem, read_fn, write_fn, address, value);
+        }
+
+        // Apply the typed digits to every byte of the scattered selection, keeping their other sub-cells intact.
+        if let (Some((addresses, sub_cell, cell_value)), Some(write_fn)) = (scattered_write_request, write_fn.as_mut())
+        {
+            self.history.begin_group();
+            for address in addresses.into_iter().filter(|address| address_space.contains(address)) {
+                let value = cell_format.apply(read_fn(mem, address).unwrap_or(0), sub_cell, cell_value);
+                self.write_value(mem, read_fn, write_fn, address, value);
+            }
+            self.history.end_group();
+        }
     }
 
-    fn draw_ascii_sidebar<T: ?Sized>(
-        &mut self,
-        ui: &mut Ui,
+    /// Whether the UI may edit `address`, see [`Self::with_edit_permission`].
+    pub fn is_editable<T: ?Sized>(
+        &self,
         mem: &mut T,
         read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
-        start_address: Address,
-        address_space: &Range<Address>,
+        address: Address,
+    ) -> bool {
+        is_edit_permitted(&self.edit_permission, address, &mut |address| read_fn(mem, address))
+    }
+
+    /// Write `bytes` to consecutive addresses starting at `start`, as if they were written through the UI.
+    ///
+    /// The writes are recorded in the [`Self::edit_history`] and respect [`Self::with_edit_permission`],
+    /// [`MemoryEditorOptions::confirm_writes`] and [`MemoryEditorOptions::writes_per_frame`], which makes this the
+    /// way to route bulk operations of the host, such as loading a save state, through the editor.
+    ///
+    /// # Arguments
+    ///
+    /// * `mem` - The memory to write to.
+    /// * `read_fn` - Reads the values before they're overwritten, for the [`Self::edit_history`].
+    /// * `write_fn` - Any closure which can take a reference to the memory, an address, and the value to write.
+    pub fn apply_bytes<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        write_fn: &mut impl FnMut(&mut T, Address, u8),
+        start: Address,
+        bytes: &[u8],
+    ) {
+        self.history.begin_group();
+        for (address, &value) in (start..).zip(bytes) {
+            self.write_value(mem, read_fn, write_fn, address, value);
+        }
+        self.history.end_group();
+    }
+
+    /// Write a single value to memory, every write done by the UI should go through here.
+    ///
+    /// Writes to addresses which aren't editable are ignored.
+    /// With [`MemoryEditorOptions::confirm_writes`] enabled the write is queued until the user confirms it.
+    pub(crate) fn write_value<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        write_fn: &mut impl FnMut(&mut T, Address, u8),
+        address: Address,
+        value: u8,
+    ) {
+        if !self.is_editable(mem, read_fn, address) {
+            self.reject_input(RejectedInput::NotEditable { address });
+            return;
+        }
+
+        if self.options.confirm_writes {
+            self.frame_data.unconfirmed_writes.push((address, value));
+        } else {
+            self.commit_write(mem, read_fn, write_fn, address, value);
+        }
+    }
+
+    /// Write a single value to memory without asking for confirmation, only for writes the user already confirmed.
+    ///
+    /// Once [`MemoryEditorOptions::writes_per_frame`] writes were made this frame the write is queued instead.
+    pub(crate) fn commit_write<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        write_fn: &mut impl FnMut(&mut T, Address, u8),
+        address: Address,
+        value: u8,
+    ) {
+        // A queued write to the same address is what will be overwritten, rather than what's currently in memory.
+        let previous = match self.frame_data.queued_value(address) {
+            Some(queued_value) => Some(queued_value),
+            None => read_fn(mem, address),
+        };
+        self.history.record(address, previous, value);
+        self.record_write(address, value);
+        self.write_or_queue(mem, write_fn, address, value);
+    }
+
+    /// Write hex bytes pasted while an address is selected, starting at the selection, see
+    /// [`utilities::parse_pasted_bytes`] for the accepted formats.
+    ///
+    /// Pastes which don't fit in the region are rejected as a whole.
+    fn handle_paste<T: ?Sized>(
+        &mut self,
+        ctx: &Context,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        write_fn: &mut impl FnMut(&mut T, Address, u8),
+        address_space: &Range<Address>,
+    ) {
+        let Some(selection) = self.frame_data.selection() else {
+            return;
+        };
+
+        // Don't steal the paste from other text fields, the memory cell being edited gets the whole paste instead.
+        if ctx.wants_keyboard_input() && self.frame_data.view.selected_edit_address.is_none() {
+            return;
+        }
+
+        // Take the paste out of the input, such that the memory cell being edited doesn't see it as typed text.
+        let pasted = ctx.input_mut(|i| {
+            let index = i.events.iter().position(|e| matches!(e, egui::Event::Paste(_)))?;
+            match i.events.remove(index) {
+                egui::Event::Paste(text) => Some(text),
+                _ => None,
+            }
+        });
+        let Some(pasted) = pasted else {
+            return;
+        };
+
+        let start = selection.start;
+        let Some(bytes) = utilities::parse_pasted_bytes(&pasted) else {
+            self.reject_input(RejectedInput::InvalidPaste { address: start });
+            return;
+        };
+        let Some(end) = start.checked_add(bytes.len()).filter(|&end| end <= address_space.end) else {
+            self.reject_input(RejectedInput::OutOfRange { address: start });
+            return;
+        };
+
+        self.frame_data.set_selected_edit_address(None, address_space);
+        self.frame_data.select_range(start..end);
+        self.apply_bytes(mem, read_fn, write_fn, start, &bytes);
+    }
+
+    /// Revert the last group of writes in the [`Self::edit_history`], such as a whole paste, on the undo shortcut.
+    ///
+    /// The reverting writes are edits like any other: they're checked, confirmed, recorded and form a group of their
+    /// own.
+    fn handle_undo_shortcut<T: ?Sized>(
+        &mut self,
+        ctx: &Context,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        write_fn: &mut impl FnMut(&mut T, Address, u8),
+    ) {
+        // Don't steal the undo from other text fields, the memory cell being edited is fine as it only holds digits.
+        if ctx.wants_keyboard_input() && self.frame_data.view.selected_edit_address.is_none() {
+            return;
+        }
+
+        if !ctx.input_mut(|i| i.consume_shortcut(&self.options.undo_shortcut)) {
+            return;
+        }
+
+        // Bytes which couldn't be read before the write have nothing to be restored to.
+        let writes = self.history.pop_group();
+        self.history.begin_group();
+        for write in writes.into_iter().rev() {
+            if let Some(previous) = write.previous {
+                self.write_value(mem, read_fn, write_fn, write.address, previous);
+            }
+        }
+        self.history.end_group();
+    }
+
+    /// Pass a write to the `write_fn`, unless [`MemoryEditorOptions::writes_per_frame`] were already made this frame.
+    fn write_or_queue<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        write_fn: &mut impl FnMut(&mut T, Address, u8),
+        address: Address,
+        value: u8,
+    ) {
+        let frame_data = &mut self.frame_data;
+        match self.options.writes_per_frame {
+            // Writes behind a queue wait for their turn, to be performed in the order they were made.
+            Some(budget) if frame_data.queued_write_count() > 0 || frame_data.frame_writes >= budget.max(1) => {
+                frame_data.queue_write(address, value);
+            }
+            _ => self.perform_write(mem, write_fn, address, value),
+        }
+    }
+
+    /// Perform the writes queued in earlier frames, as many as [`MemoryEditorOptions::writes_per_frame`] allows.
+    fn flush_queued_writes<T: ?Sized>(
+        &mut self,
+        ctx: &Context,
+        mem: &mut T,
+        write_fn: &mut impl FnMut(&mut T, Address, u8),
+    ) {
+        self.frame_data.frame_writes = 0;
+        // Once the budget is removed the whole queue is flushed at once.
+        let budget = self.options.writes_per_frame.map_or(usize::MAX, |budget| budget.max(1));
+
+        while self.frame_data.frame_writes < budget {
+            let Some((address, value)) = self.frame_data.pop_queued_write() else {
+                return;
+            };
+            self.perform_write(mem, write_fn, address, value);
+        }
+
+        if self.frame_data.queued_write_count() > 0 {
+            ctx.request_repaint();
+        }
+    }
+
+    fn perform_write<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        write_fn: &mut impl FnMut(&mut T, Address, u8),
+        address: Address,
+        value: u8,
+    ) {
+        write_fn(mem, address, value);
+        self.ascii_rows.invalidate(address);
+        self.frame_data.frame_writes += 1;
+        self.metrics.writes += 1;
+    }
+
+    /// Ask the user to confirm the queued writes, see [`MemoryEditorOptions::confirm_writes`].
+    fn draw_write_confirmation<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        write_fn: &mut Option<impl FnMut(&mut T, Address, u8)>,
+    ) {
+        let labels = &self.options.labels;
+        let writes = &self.frame_data.unconfirmed_writes;
+        let question = match writes[..] {
+            [(address, value)] => fill(
+                &labels.confirm_write,
+                &[
+                    ("value", &format!("{:#04X}", value)),
+                    ("address", &format!("{:#X}", address)),
+                ],
+            ),
+            _ => {
+                // A batch isn't necessarily contiguous, such as when typing into a scattered selection.
+                let addresses: BTreeSet<Address> = writes.iter().map(|&(address, _)| address).collect();
+                let ranges = addresses
+                    .iter()
+                    .zip(addresses.iter().skip(1))
+                    .filter(|(a, b)| *a + 1 != **b)
+                    .count()
+                    + 1;
+                let (start, end) = (addresses.first().unwrap_or(&0), addresses.last().unwrap_or(&0));
+
+                if ranges == 1 {
+                    fill(
+                        &labels.confirm_writes,
+                        &[
+                            ("count", &writes.len()),
+                            ("range", &format!("{:#X}..{:#X}", start, end.saturating_add(1))),
+                        ],
+                    )
+                } else {
+                    fill(
+                        &labels.confirm_scattered_writes,
+                        &[
+                            ("count", &writes.len()),
+                            ("ranges", &ranges),
+                            ("start", &format!("{:#X}", start)),
+                            ("end", &format!("{:#X}", end)),
+                        ],
+                    )
+                }
+            }
+        };
+
+        let (confirm, cancel) = ui
+            .horizontal(|ui| {
+                ui.colored_label(ui.visuals().warn_fg_color, question)
+                    .on_hover_ui(|ui| {
+                        for (address, value) in writes.iter().take(16) {
+                            ui.label(format!("{:#X}: {:02X}", address, value));
+                        }
+                        if writes.len() > 16 {
+                            ui.label(fill(&labels.more_writes, &[("count", &(writes.len() - 16))]));
+                        }
+                    });
+                (ui.button(&labels.write).clicked(), ui.button(&labels.cancel).clicked())
+            })
+            .inner;
+
+        if confirm {
+            let writes = std::mem::take(&mut self.frame_data.unconfirmed_writes);
+
+            // The lock could've been enabled in the meantime, in which case the writes are dropped.
+            if let Some(write_fn) = write_fn.as_mut() {
+                self.history.begin_group();
+                for (address, value) in writes {
+                    self.commit_write(mem, read_fn, write_fn, address, value);
+                }
+                self.history.end_group();
+            }
+        } else if cancel {
+            self.frame_data.unconfirmed_writes.clear();
+        }
+    }
+
+    fn draw_ascii_sidebar<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        start_address: Address,
+        address_space: &Range<Address>,
     ) {
         let options = &self.options;
+        let column_count = self.column_count();
+        let ascii_font = options.ascii_font(ui.style());
+        // The separator is always between the values and the sidebar.
+        let is_right_to_left = options.layout_direction == LayoutDirection::RightToLeft;
+        let separator = || egui::Separator::default().vertical().spacing(ASCII_SEPARATOR_SPACING);
 
         ui.horizontal(|ui| {
-            ui.add(egui::Separator::default().vertical().spacing(3.0));
             ui.style_mut().spacing.item_spacing.x = 0.0;
+            if !is_right_to_left {
+                ui.add(separator());
+            }
 
-            ui.horizontal(|ui| {
-                for i in 0..options.column_count {
-                    let memory_address = start_address + i;
+            let row = start_address..(start_address + column_count).min(address_space.end);
+            let search = self.current_search();
+            let is_highlighted = row.clone().any(|address| {
+                Self::host_highlight_colour(&self.highlights, address).is_some()
+                    || self.frame_data.should_highlight(address)
+                    || search.is_some_and(|search| search.match_at(address).is_some())
+            });
 
-                    if !address_space.contains(&memory_address) {
-                        break;
-                    }
+            // Without per-character styling, or sizing for non-monospace fonts, the row can be a single cached label.
+            let extend_to = if !is_highlighted && self.frame_data.view.ascii_cell_size.is_none() {
+                let (text, is_cached) = self.ascii_rows.row(mem, read_fn, row.clone());
+                self.metrics.cached_ascii_rows += usize::from(is_cached);
+                let response = Label::new(RichText::new(text).font(ascii_font))
+                    .sense(Sense::click())
+                    .ui(ui);
+
+                // Every character is equally wide, so the clicked one follows from the position within the row.
+                let pointer = response
+                    .interact_pointer_pos()
+                    .filter(|_| ui.input(|i| i.modifiers.shift));
+                pointer
+                    .filter(|_| response.clicked() && !row.is_empty())
+                    .map(|pointer| {
+                        let fraction = (pointer.x - response.rect.left()) / response.rect.width();
+                        let index = (fraction * row.len() as f32).max(0.0) as usize;
+                        row.start + index.min(row.len() - 1)
+                    })
+            } else {
+                self.draw_ascii_characters(ui, mem, read_fn, row, ascii_font)
+            };
+
+            // Shift+click extends the selection to the clicked character.
+            if let Some(address) = extend_to {
+                self.frame_data.view.selected_edit_address = None;
+                self.frame_data.extend_selection(address);
+            }
+
+            if is_right_to_left {
+                ui.add(separator());
+            }
+        });
+    }
+
+    /// Draw every character of the ASCII sidebar as its own label, such that they can be highlighted individually.
+    ///
+    /// Returns the address of the character the user Shift+clicked to extend the selection to, if any.
+    fn draw_ascii_characters<T: ?Sized>(
+        &self,
+        ui: &mut Ui,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        row: Range<Address>,
+        ascii_font: FontId,
+    ) -> Option<Address> {
+        let search = self.current_search();
+        let mut extend_to = None;
 
-                    let mem_val: u8 = read_fn(mem, memory_address).unwrap_or(0);
-                    // Check if it's a printable ASCII character
-                    let character = if !(32..128).contains(&mem_val) {
-                        '.'
-                    } else {
-                        mem_val as char
-                    };
-                    let mut text = RichText::new(character).text_style(options.memory_editor_ascii_text_style.clone());
-
-                    if self.frame_data.should_highlight(memory_address) {
-                        text = text
-                            .color(self.options.highlight_text_colour)
-                            .background_color(ui.style().visuals.code_bg_color);
+        ui.horizontal(|ui| {
+            for memory_address in row {
+                let mem_val: u8 = read_fn(mem, memory_address).unwrap_or(0);
+                let mut text = RichText::new(ascii_character(mem_val)).font(ascii_font.clone());
+
+                if let Some((colour, background)) =
+                    search.and_then(|search| search.match_colours(memory_address, &self.options))
+                {
+                    text = text.background_color(background);
+                    if let Some(colour) = colour {
+                        text = text.color(colour);
                     }
+                }
 
-                    ui.label(text);
+                if let Some(colour) = Self::host_highlight_colour(&self.highlights, memory_address) {
+                    text = text.background_color(colour);
                 }
-            });
+
+                if self.frame_data.should_highlight(memory_address) {
+                    text = text
+                        .color(self.frame_data.highlight_colour(&self.options))
+                        .background_color(ui.style().visuals.code_bg_color);
+                }
+
+                let label = Label::new(text).sense(Sense::click());
+                let response = match self.frame_data.view.ascii_cell_size {
+                    Some(size) => ui.add_sized(size, label),
+                    None => label.ui(ui),
+                };
+
+                if response.clicked() && ui.input(|i| i.modifiers.shift) {
+                    extend_to = Some(memory_address);
+                }
+            }
         });
+
+        extend_to
     }
 
-    /// Return the line height for the current provided `Ui` and selected `TextStyle`s
+    /// Predict which addresses will be visible shortly based on the scroll velocity, and notify the prefetch callback.
+    fn update_predicted_visible_range(&mut self, time: f64, address_space: &Range<Address>) {
+        let column_count = self.column_count();
+        let frame_data = &mut self.frame_data;
+        let start = self.visible.addresses.start;
+        let elapsed = time - frame_data.view.previous_visible_time;
+
+        // Switching regions shouldn't be seen as a very fast scroll.
+        if !address_space.contains(&frame_data.view.previous_visible_start) {
+            frame_data.view.scroll_velocity = 0.0;
+        } else if elapsed > 0.0 {
+            let velocity = (start as f64 - frame_data.view.previous_visible_start as f64) / elapsed;
+            // Average with the previous frame to smooth out uneven frame times.
+            frame_data.view.scroll_velocity = (frame_data.view.scroll_velocity + velocity) / 2.0;
+        }
+
+        frame_data.view.previous_visible_start = start;
+        frame_data.view.previous_visible_time = time;
+
+        let length = self.visible.addresses.len();
+        let last_start = address_space.end.saturating_sub(length).max(address_space.start);
+        let predicted_start = (start as f64 + frame_data.view.scroll_velocity * PREFETCH_LOOKAHEAD_SECONDS)
+            .clamp(address_space.start as f64, last_start as f64) as Address;
+        // Align to the start of a row
+        let predicted_start =
+            address_space.start + (predicted_start - address_space.start) / column_count * column_count;
+
+        self.predicted_visible_range = predicted_start..predicted_start.saturating_add(length);
+
+        if let Some(callback) = &self.prefetch_callback {
+            callback(self.predicted_visible_range.clone());
+        }
+    }
+
+    /// Return the line height for the current provided `Ui` and selected fonts
     fn get_line_height(&self, ui: &mut Ui) -> f32 {
-        let address_size = ui.text_style_height(&self.options.memory_editor_address_text_style);
-        let body_size = ui.text_style_height(&self.options.memory_editor_text_style);
-        let ascii_size = ui.text_style_height(&self.options.memory_editor_ascii_text_style);
-        address_size.max(body_size).max(ascii_size)
+        let style = ui.style();
+        let fonts = [
+            self.options.address_font(style),
+            self.options.value_font(style),
+            self.options.ascii_font(style),
+        ];
+        ui.fonts(|f| fonts.iter().map(|font| f.row_height(font)).fold(0.0, f32::max))
     }
 
     /// Shrink the window to the previous frame's memory viewer's width.
     /// This essentially allows us to only have height resize, and have width grow/shrink as appropriate.
+    ///
+    /// If the window has been made too narrow for all columns, it snaps to the widest set of whole columns that fits.
     fn shrink_window_ui(&self, ui: &mut Ui) {
         // This should take the `min` of ui.min_rect().width() and the frame data width, but that seems to have issues at the moment.
-        ui.set_max_width(self.frame_data.previous_frame_editor_width);
+        let available_width = ui.available_width();
+        let width = if self.frame_data.view.previous_frame_editor_width > available_width {
+            self.snapped_width(ui.ctx(), available_width)
+        } else {
+            self.frame_data.view.previous_frame_editor_width
+        };
+
+        ui.set_max_width(width);
+    }
+
+    /// Select the entire region on `Ctrl+A`, or only the visible part of it on `Ctrl+Shift+A`.
+    fn handle_select_all_shortcut(&mut self, ctx: &Context, address_space: &Range<Address>) {
+        // Text fields, including the memory cell being edited, have their own select all.
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        let (select_all_pressed, visible_only) =
+            ctx.input(|i| (i.modifiers.command && i.key_pressed(egui::Key::A), i.modifiers.shift));
+
+        if !select_all_pressed {
+            return;
+        }
+
+        if visible_only {
+            let visible = &self.visible.addresses;
+            let start = visible.start.max(address_space.start);
+            self.frame_data.select_range(start..visible.end.min(address_space.end));
+        } else {
+            self.frame_data.select_range(address_space.clone());
+        }
+    }
+
+    /// Copy the selected bytes on `Ctrl+C` as `0xADDRESS: VALUE` lines, or only their values on `Ctrl+Shift+C`.
+    ///
+    /// A scattered selection takes precedence over the highlighted range.
+    fn handle_copy_shortcut<T: ?Sized>(
+        &mut self,
+        ctx: &Context,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+    ) {
+        if self.frame_data.view.selected_highlight_address.is_none()
+            && self.frame_data.view.scattered_selection.is_empty()
+        {
+            return;
+        }
+
+        // Don't steal the copy from other text fields, the memory cell being edited is fine as it only holds digits.
+        if ctx.wants_keyboard_input() && self.frame_data.view.selected_edit_address.is_none() {
+            return;
+        }
+
+        // Most integrations turn `Ctrl+C` into a `Copy` event instead of a key press.
+        let (copy_pressed, value_only) = ctx.input(|i| {
+            let copy_pressed = i.events.iter().any(|e| matches!(e, egui::Event::Copy))
+                || (i.modifiers.command && i.key_pressed(egui::Key::C));
+            (copy_pressed, i.modifiers.shift)
+        });
+
+        if !copy_pressed || !self.is_selection_copyable() {
+            return;
+        }
+
+        // A range of bytes is copied as they're shown, such that it can be pasted into other hex editors.
+        if self.frame_data.view.selection_end.is_some() && self.frame_data.view.scattered_selection.is_empty() {
+            let text = self.selection_hex(mem, read_fn);
+            ctx.copy_text(text);
+            return;
+        }
+
+        let lines: Vec<String> = self
+            .selected_addresses()
+            .map(|address| {
+                let value = read_fn(mem, address).map_or(self.options.none_display_value.clone(), |value| {
+                    format!("{:02X}", value)
+                });
+
+                if value_only {
+                    value
+                } else {
+                    format!("{:#X}: {}", address, value)
+                }
+            })
+            .collect();
+
+        ctx.copy_text(lines.join("\n"));
+    }
+
+    /// The addresses of the scattered selection if there is one, otherwise those of the contiguous selection.
+    fn selected_addresses(&self) -> impl Iterator<Item = Address> + '_ {
+        let scattered = &self.frame_data.view.scattered_selection;
+        let range = self.frame_data.selection().filter(|_| scattered.is_empty());
+
+        range.into_iter().flatten().chain(scattered.iter().copied())
+    }
+
+    /// The amount of [`Self::selected_addresses`].
+    fn selected_count(&self) -> usize {
+        match self.frame_data.view.scattered_selection.len() {
+            0 => self.frame_data.selection().map_or(0, |selection| selection.len()),
+            count => count,
+        }
+    }
+
+    /// Whether the selection is at most [`MemoryEditorOptions::max_copy_bytes`] large, otherwise a warning is shown
+    /// instead of copying it.
+    fn is_selection_copyable(&mut self) -> bool {
+        let count = self.selected_count();
+        let is_copyable = count <= self.options.max_copy_bytes;
+        self.frame_data.view.oversized_copy = (!is_copyable).then(|| (self.frame_data.selection(), count));
+
+        is_copyable
+    }
+
+    /// The selected bytes as space separated hex values, such as `DE AD BE EF`.
+    ///
+    /// A scattered selection, such as a rectangular one, puts the bytes of every row on their own line.
+    fn selection_hex<T: ?Sized>(&self, mem: &mut T, read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>) -> String {
+        let column_count = self.column_count();
+        let region_start = self
+            .address_ranges
+            .get(&self.options.selected_address_range)
+            .map_or(0, |range| range.start);
+        let row = |address: Address| address.saturating_sub(region_start) / column_count;
+        let is_scattered = !self.frame_data.view.scattered_selection.is_empty();
+        let mut text = String::new();
+        let mut previous_row = None;
+
+        for address in self.selected_addresses() {
+            match previous_row {
+                Some(previous) if is_scattered && previous != row(address) => text.push('\n'),
+                Some(_) => text.push(' '),
+                None => {}
+            }
+            previous_row = Some(row(address));
+
+            match read_fn(mem, address) {
+                Some(value) => text.push_str(&format!("{:02X}", value)),
+                None => text.push_str(&self.options.none_display_value),
+            }
+        }
+
+        text
+    }
+
+    /// The selected bytes as the characters of the ASCII sidebar, see [`MemoryEditorOptions::copy_text_placeholder`].
+    fn selection_text<T: ?Sized>(
+        &self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+    ) -> String {
+        self.selected_addresses()
+            .map(|address| {
+                read_fn(mem, address)
+                    .and_then(printable_character)
+                    .unwrap_or(self.options.copy_text_placeholder)
+            })
+            .collect()
     }
 
     /// Check for arrow keys when we're editing a memory value at an address.
@@ -441,23 +2008,17 @@ impl MemoryEditor {
         use egui::Key::*;
         const KEYS: [egui::Key; 4] = [ArrowLeft, ArrowRight, ArrowDown, ArrowUp];
 
-        let Some(current_address) = self.frame_data.selected_edit_address else {
+        let Some(current_address) = self.frame_data.view.selected_edit_address else {
             return;
         };
 
         let key_pressed = KEYS.iter().find(|&&k| ctx.input(|i| i.key_pressed(k)));
         if let Some(key) = key_pressed {
             let next_address = match key {
-                ArrowDown => current_address + self.options.column_count,
+                ArrowDown => current_address + self.column_count(),
                 ArrowLeft => current_address.saturating_sub(1),
                 ArrowRight => current_address.saturating_add(1),
-                ArrowUp => {
-                    if current_address < self.options.column_count {
-                        0
-                    } else {
-                        current_address - self.options.column_count
-                    }
-                }
+                ArrowUp => current_address.saturating_sub(self.column_count()),
                 _ => unreachable!(),
             };
 
@@ -504,6 +2065,78 @@ impl MemoryEditor {
         }
     }
 
+    /// Set a callback which is invoked every frame with the range of addresses predicted to be visible shortly.
+    ///
+    /// The prediction is based on the current scroll velocity, which allows asynchronous backends to fetch memory
+    /// ahead of scrolling instead of showing [`MemoryEditorOptions::none_display_value`] during fast scrolls.
+    #[must_use]
+    pub fn with_prefetch_callback(mut self, callback: impl Fn(Range<Address>) + Send + Sync + 'static) -> Self {
+        self.prefetch_callback = Some(Arc::new(callback));
+        self
+    }
+
+    /// Set a callback which returns the name of the symbol starting at an address, such as a variable from a linker map.
+    ///
+    /// The names are shown next to the grid when [`MemoryEditorOptions::show_label_column`] is enabled.
+    #[must_use]
+    pub fn with_symbol_provider(
+        mut self,
+        provider: impl Fn(Address) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.symbol_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// Add a column after the ASCII sidebar, whose text for every row is returned by `column`, such as a decoded
+    /// sprite attribute. Columns are shown in the order they were added, `name` is shown when hovering them.
+    ///
+    /// The callback receives the first address of the row and a function reading the memory through the `read_fn` of
+    /// the frame, which returns `None` outside of the current region. Other state of the host, such as the selected
+    /// mapper bank, can be captured by the callback.
+    ///
+    /// ```no_run
+    /// # use egui_memory_editor::MemoryEditor;
+    /// let editor = MemoryEditor::new().with_derived_column("Tile", |start, read| {
+    ///     read(start + 1).map_or_else(String::new, |tile| format!("Tile {tile}"))
+    /// });
+    /// ```
+    #[must_use]
+    pub fn with_derived_column(
+        mut self,
+        name: impl Into<String>,
+        column: impl Fn(Address, &mut dyn FnMut(Address) -> Option<u8>) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.derived_columns.push((name.into(), Arc::new(column)));
+        self
+    }
+
+    /// Set a callback which is queried before an address is edited through the UI, returning `false` prevents the
+    /// inline editor from opening and any write to the address.
+    ///
+    /// The callback gets a function reading the memory of the current region, such that edits can depend on the
+    /// memory itself, like a lock flag. Other dynamic conditions, such as only allowing edits while an emulator is
+    /// paused, can be implemented by capturing shared state like an `Arc<AtomicBool>`. Only relevant if a `write_fn`
+    /// is provided.
+    #[must_use]
+    pub fn with_edit_permission(
+        mut self,
+        permission: impl Fn(Address, &mut dyn FnMut(Address) -> Option<u8>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.edit_permission = Some(Arc::new(permission));
+        self
+    }
+
+    /// Set a callback which is notified whenever the editor rejects input, such as a typed character which isn't a
+    /// hex digit or a write to an address which isn't editable.
+    ///
+    /// The rejected value is already outlined in the UI, see [`MemoryEditorOptions::show_rejected_input`], this allows
+    /// the host to give further feedback such as a toast or a sound.
+    #[must_use]
+    pub fn with_rejected_input_callback(mut self, callback: impl Fn(RejectedInput) + Send + Sync + 'static) -> Self {
+        self.rejected_input_callback = Some(Arc::new(callback));
+        self
+    }
+
     /// Set the memory options, useful if you use the `persistence` feature.
     #[inline]
     #[must_use]
@@ -517,6 +2150,87 @@ impl MemoryEditor {
     /// See also [`Self::with_options`]
     pub fn set_options(&mut self, options: MemoryEditorOptions) {
         self.options = options;
+        self.reset_layout();
+    }
+
+    // ** Option setters **
+
+    /// Set the amount of columns of the main UI, clamped to `1..=64`.
+    ///
+    /// See [`MemoryEditorOptions::column_count`].
+    pub fn set_column_count(&mut self, column_count: usize) {
+        self.options.column_count = column_count;
+        self.reset_layout();
+    }
+
+    /// Set whether to show the ASCII sidebar.
+    ///
+    /// See [`MemoryEditorOptions::show_ascii`].
+    pub fn set_show_ascii(&mut self, show_ascii: bool) {
+        self.options.show_ascii = show_ascii;
+        self.reset_layout();
+    }
+
+    /// Set how the value of every byte is displayed, stopping any edit in progress as its cells might no longer
+    /// exist.
+    ///
+    /// See [`MemoryEditorOptions::cell_format`].
+    pub fn set_cell_format(&mut self, cell_format: CellFormat) {
+        self.options.cell_format = cell_format;
+        self.frame_data.view.selected_edit_sub_cell = 0;
+        self.frame_data.view.selected_edit_address_string.clear();
+        self.reset_layout();
+    }
+
+    /// Switch to the layout of `profile`, as if it was selected in the UI.
+    ///
+    /// See [`MemoryEditorOptions::profiles`].
+    pub fn apply_layout_profile(&mut self, profile: &LayoutProfile) {
+        self.set_column_count(profile.column_count);
+        self.set_cell_format(profile.cell_format);
+        self.set_show_ascii(profile.show_ascii);
+        self.options.data_preview = profile.data_preview;
+    }
+
+    /// Set the maximum amount of addresses long running operations read per frame, at least `1`.
+    ///
+    /// See [`MemoryEditorOptions::task_bytes_per_frame`].
+    pub fn set_task_bytes_per_frame(&mut self, bytes: usize) {
+        self.options.task_bytes_per_frame = bytes.max(1);
+    }
+
+    /// Switch to the address range with the given name, as if it was selected in the UI.
+    ///
+    /// Returns `false`, without switching, if no range with that name was added.
+    pub fn set_selected_address_range(&mut self, range_name: &str) -> bool {
+        if !self.address_ranges.contains_key(range_name) {
+            return false;
+        }
+
+        self.options.selected_address_range = range_name.to_string();
+        self.reset_layout();
+        true
+    }
+}
+
+/// Whether `permission` allows editing `address`, every address is editable without one.
+///
+/// Separate from [`MemoryEditor::is_editable`] for the places where parts of the editor are already borrowed.
+fn is_edit_permitted(
+    permission: &Option<EditPermission>,
+    address: Address,
+    read: &mut dyn FnMut(Address) -> Option<u8>,
+) -> bool {
+    permission.as_ref().is_none_or(|permission| permission(address, read))
+}
+
+fn slice_read(memory: &mut [u8], address: Address) -> Option<u8> {
+    memory.get(address).copied()
+}
+
+fn slice_write(memory: &mut [u8], address: Address, value: u8) {
+    if let Some(byte) = memory.get_mut(address) {
+        *byte = value;
     }
 }
 
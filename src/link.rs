@@ -0,0 +1,46 @@
+//! Deep links to a location in the editor, such as `Memory@0xFF05`, to persist where the user was or to navigate to
+//! the editor from other views.
+use crate::{Address, MemoryEditor};
+
+/// Format a deep link to `address` in `region`.
+#[inline]
+fn format_link(region: &str, address: Address) -> String {
+    format!("{}@{:#X}", region, address)
+}
+
+impl MemoryEditor {
+    /// Returns a deep link to the selected address, or the first visible address if nothing is selected.
+    ///
+    /// The link has the format `region@0xADDRESS`, and can be opened again with [`Self::open_link`].
+    pub fn goto_link(&self) -> String {
+        let address = self
+            .frame_data
+            .view
+            .selected_highlight_address
+            .unwrap_or(self.visible.addresses.start);
+
+        format_link(&self.options.selected_address_range, address)
+    }
+
+    /// Jump to the location of a deep link created by [`Self::goto_link`] on the next frame.
+    ///
+    /// Returns `false` if the link is malformed, or refers to a region or address which doesn't exist.
+    pub fn open_link(&mut self, link: &str) -> bool {
+        // Region names can contain `@` as well, the address never does.
+        let Some((region, address)) = link.trim().rsplit_once('@') else {
+            return false;
+        };
+        let Some(address) = crate::utilities::parse_address(address) else {
+            return false;
+        };
+
+        match self.address_ranges.get(region) {
+            Some(range) if range.contains(&address) => {
+                self.options.selected_address_range = region.to_string();
+                self.queue_goto(address);
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! A single list of every navigation target in the current region, such as search matches and markers.
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use egui::{Color32, Rect, RichText, ScrollArea, Stroke, Ui, Visuals};
+
+use crate::labels::{fill, Labels};
+use crate::option_data::MemoryEditorOptions;
+use crate::{Address, MemoryEditor};
+
+/// The most locations of a single kind that are listed, to keep the list cheap to build every frame.
+const LOCATION_LIMIT: usize = 10_000;
+
+/// Where a location in the `Locations` section comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum LocationKind {
+    SearchMatch,
+    Marker,
+    Bookmark,
+    Mismatch,
+    Modified,
+}
+
+impl LocationKind {
+    const ALL: [LocationKind; 5] = [
+        LocationKind::SearchMatch,
+        LocationKind::Marker,
+        LocationKind::Bookmark,
+        LocationKind::Mismatch,
+        LocationKind::Modified,
+    ];
+
+    fn label(self, labels: &Labels) -> &str {
+        match self {
+            LocationKind::SearchMatch => &labels.location_search_match,
+            LocationKind::Marker => &labels.location_marker,
+            LocationKind::Bookmark => &labels.location_bookmark,
+            LocationKind::Mismatch => &labels.location_mismatch,
+            LocationKind::Modified => &labels.location_modified,
+        }
+    }
+
+    /// The colour of the tick marks of this kind, matching how the grid shows its addresses.
+    fn colour(self, options: &MemoryEditorOptions, visuals: &Visuals) -> Color32 {
+        match self {
+            LocationKind::SearchMatch => options.search_match_colour,
+            LocationKind::Bookmark => options.highlight_text_colour,
+            LocationKind::Marker | LocationKind::Mismatch => visuals.error_fg_color,
+            LocationKind::Modified => visuals.warn_fg_color,
+        }
+    }
+}
+
+/// The UI state of the `Locations` section.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct LocationsState {
+    /// The kinds which are filtered out of the list.
+    pub hidden_kinds: BTreeSet<LocationKind>,
+}
+
+impl MemoryEditor {
+    /// Draw the `Locations` section, listing the search matches, markers, comparison mismatches and modified bytes of
+    /// the current region.
+    pub(crate) fn draw_locations(&mut self, ui: &mut Ui, current_address_range: &Range<Address>) {
+        egui::CollapsingHeader::new(&self.options.labels.locations_title)
+            .default_open(false)
+            .show(ui, |ui| {
+                let labels = &self.options.labels;
+                let hidden_kinds = &mut self.locations.hidden_kinds;
+
+                ui.horizontal(|ui| {
+                    for kind in LocationKind::ALL {
+                        let mut is_shown = !hidden_kinds.contains(&kind);
+
+                        if ui.checkbox(&mut is_shown, kind.label(labels)).changed() {
+                            if is_shown {
+                                hidden_kinds.remove(&kind);
+                            } else {
+                                hidden_kinds.insert(kind);
+                            }
+                        }
+                    }
+                });
+
+                let (locations, is_truncated) = self.collect_locations(current_address_range);
+                let labels = &self.options.labels;
+
+                if locations.is_empty() {
+                    ui.label(&labels.no_locations);
+                    return;
+                }
+
+                if is_truncated {
+                    ui.label(fill(&labels.locations_truncated, &[("limit", &LOCATION_LIMIT)]));
+                }
+
+                let mut goto = None;
+                let value_font = self.options.value_font(ui.style());
+                let row_height = ui.fonts(|fonts| fonts.row_height(&value_font));
+
+                ScrollArea::vertical()
+                    .id_salt("locations")
+                    .max_height(row_height * 8.0)
+                    .show_rows(ui, row_height, locations.len(), |ui, rows| {
+                        for &(address, kind) in &locations[rows] {
+                            ui.horizontal(|ui| {
+                                let text = RichText::new(format!("{:#X}", address)).font(value_font.clone());
+
+                                if ui.link(text).clicked() {
+                                    goto = Some(address);
+                                }
+                                ui.weak(kind.label(labels));
+                            });
+                        }
+                    });
+
+                if let Some(address) = goto {
+                    self.frame_data
+                        .set_goto_address(address, current_address_range, self.column_count());
+                }
+            });
+    }
+
+    /// Draw a tick next to the scrollbar for every shown location of the region, at their relative position, see
+    /// [`MemoryEditorOptions::show_scrollbar_ticks`].
+    pub(crate) fn draw_scrollbar_ticks(&self, ui: &mut Ui, viewport: Rect, address_space: &Range<Address>) {
+        let scroll = &ui.spacing().scroll;
+        let right = viewport.right() - scroll.bar_outer_margin;
+        let x_range = right - scroll.bar_width..=right;
+        let painter = ui.painter_at(viewport);
+
+        // Locations closer together than a pixel share one tick, keep the first for every pixel row.
+        // Looking up the first location of every row works on the complete sets, which can be far larger than the list.
+        let pixels = (viewport.height().round() as usize).max(1);
+        let bucket_start = |pixel: usize| {
+            address_space.start + (address_space.len() as u128 * pixel as u128 / pixels as u128) as Address
+        };
+        let mut ticks: Vec<Option<(Address, LocationKind)>> = vec![None; pixels];
+        for (kind, set) in self.location_sets(address_space) {
+            for (pixel, tick) in ticks.iter_mut().enumerate() {
+                let first = set.first_in(bucket_start(pixel)..bucket_start(pixel + 1));
+                if let Some(address) = first.filter(|&address| tick.is_none_or(|(first, _)| address < first)) {
+                    *tick = Some((address, kind));
+                }
+            }
+        }
+
+        for (pixel, tick) in ticks.into_iter().enumerate() {
+            if let Some((_, kind)) = tick {
+                painter.hline(
+                    x_range.clone(),
+                    viewport.top() + pixel as f32,
+                    Stroke::new(2.0, kind.colour(&self.options, ui.visuals())),
+                );
+            }
+        }
+    }
+
+    /// Returns the shown locations within `addresses` ordered by address, and whether any kind had more than
+    /// [`LOCATION_LIMIT`] locations.
+    fn collect_locations(&self, addresses: &Range<Address>) -> (Vec<(Address, LocationKind)>, bool) {
+        let mut locations = Vec::new();
+        let mut is_truncated = false;
+
+        for (kind, set) in self.location_sets(addresses) {
+            let before = locations.len();
+            locations.extend(
+                set.iter_in(addresses.clone())
+                    .take(LOCATION_LIMIT + 1)
+                    .map(|address| (address, kind)),
+            );
+            if locations.len() - before > LOCATION_LIMIT {
+                locations.pop();
+                is_truncated = true;
+            }
+        }
+
+        locations.sort();
+        (locations, is_truncated)
+    }
+
+    /// Returns the locations of every kind which isn't hidden, for the region containing `addresses`.
+    fn location_sets(&self, addresses: &Range<Address>) -> Vec<(LocationKind, LocationSet<'_>)> {
+        let region = &self.options.selected_address_range;
+        let mut sets = Vec::new();
+
+        if let Some(search) = self.current_search() {
+            sets.push((LocationKind::SearchMatch, LocationSet::Matches(&search.matches)));
+        }
+
+        sets.push((LocationKind::Marker, LocationSet::Borrowed(&self.markers)));
+        sets.push((LocationKind::Bookmark, LocationSet::Borrowed(&self.bookmarks)));
+
+        if let Some(comparison) = self
+            .comparison
+            .comparison
+            .as_ref()
+            .filter(|comparison| comparison.task.is_none() && &comparison.region == region)
+        {
+            sets.push((LocationKind::Mismatch, LocationSet::Borrowed(&comparison.mismatches)));
+        }
+
+        if !self.locations.hidden_kinds.contains(&LocationKind::Modified) {
+            let modified = self
+                .history
+                .writes()
+                .iter()
+                .map(|write| write.address)
+                .filter(|address| addresses.contains(address))
+                .collect();
+            sets.push((LocationKind::Modified, LocationSet::Owned(modified)));
+        }
+
+        sets.retain(|(kind, _)| !self.locations.hidden_kinds.contains(kind));
+        sets
+    }
+}
+
+/// The addresses of one kind of location.
+enum LocationSet<'a> {
+    /// Search matches, ordered by their start.
+    Matches(&'a [Range<Address>]),
+    Borrowed(&'a BTreeSet<Address>),
+    Owned(BTreeSet<Address>),
+}
+
+impl LocationSet<'_> {
+    /// Returns the locations within `addresses`, ordered by address.
+    fn iter_in(&self, addresses: Range<Address>) -> Box<dyn Iterator<Item = Address> + '_> {
+        match self {
+            LocationSet::Matches(matches) => {
+                let first = matches.partition_point(|found| found.start < addresses.start);
+                Box::new(
+                    matches[first..]
+                        .iter()
+                        .map(|found| found.start)
+                        .take_while(move |start| *start < addresses.end),
+                )
+            }
+            LocationSet::Borrowed(set) => Box::new(set.range(addresses).copied()),
+            LocationSet::Owned(set) => Box::new(set.range(addresses).copied()),
+        }
+    }
+
+    /// Returns the lowest location within `addresses`.
+    fn first_in(&self, addresses: Range<Address>) -> Option<Address> {
+        if addresses.is_empty() {
+            return None;
+        }
+        self.iter_in(addresses).next()
+    }
+}
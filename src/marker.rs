@@ -0,0 +1,58 @@
+//! The optional marker gutter to the left of the addresses, intended for memory breakpoints or other markers.
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use egui::{Sense, Ui, Vec2};
+
+use crate::{Address, MemoryEditor};
+
+impl MemoryEditor {
+    /// Returns all marked addresses.
+    ///
+    /// Clicking the gutter of an unmarked row marks its start address, clicking a marked row removes all markers in it.
+    pub fn markers(&self) -> &BTreeSet<Address> {
+        &self.markers
+    }
+
+    /// Mark or unmark the provided address.
+    pub fn set_marker(&mut self, address: Address, is_marked: bool) {
+        if is_marked {
+            self.markers.insert(address);
+        } else {
+            self.markers.remove(&address);
+        }
+    }
+
+    /// Remove all markers.
+    pub fn clear_markers(&mut self) {
+        self.markers.clear();
+    }
+
+    /// Draw the marker gutter for a single row, returns `true` if the row's marker was toggled.
+    pub(crate) fn draw_marker_gutter(&mut self, ui: &mut Ui, line_height: f32, row: &Range<Address>) -> bool {
+        let (rect, response) = ui.allocate_exact_size(Vec2::splat(line_height), Sense::click());
+        let is_marked = self.markers.range(row.clone()).next().is_some();
+        let radius = line_height * 0.3;
+
+        if is_marked {
+            ui.painter()
+                .circle_filled(rect.center(), radius, ui.visuals().error_fg_color);
+        } else if response.hovered() {
+            ui.painter()
+                .circle_filled(rect.center(), radius, ui.visuals().error_fg_color.gamma_multiply(0.3));
+        }
+
+        if response.clicked() {
+            if is_marked {
+                let marked: Vec<Address> = self.markers.range(row.clone()).copied().collect();
+                marked.iter().for_each(|address| {
+                    self.markers.remove(address);
+                });
+            } else {
+                self.markers.insert(row.start);
+            }
+        }
+
+        response.clicked()
+    }
+}
@@ -0,0 +1,64 @@
+//! Per-frame counters to help diagnose why the editor is slow for a particular host.
+use std::time::Duration;
+
+use egui::Ui;
+
+use crate::MemoryEditor;
+
+/// Statistics about the work the editor did in the last frame it was drawn.
+///
+/// Every visible byte is read through `read_fn` each frame, unless
+/// [`crate::option_data::MemoryEditorOptions::refresh_interval`] is set. The values are then cached until the next
+/// refresh, or until [`MemoryEditor::invalidate_range`] is called. The ASCII sidebar additionally reuses the text of
+/// rows whose bytes didn't change, see [`MemoryEditorMetrics::cached_ascii_rows`].
+///
+/// A high [`MemoryEditorMetrics::reads`] count relative to [`MemoryEditorMetrics::bytes_drawn`] therefore usually
+/// points to a running task, such as a comparison, see [`crate::option_data::MemoryEditorOptions::task_bytes_per_frame`].
+///
+/// See [`MemoryEditor::metrics`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MemoryEditorMetrics {
+    /// The amount of times `read_fn` was called.
+    pub reads: usize,
+    /// The amount of reads served from the values cached for
+    /// [`crate::option_data::MemoryEditorOptions::refresh_interval`], without calling `read_fn`.
+    pub cached_reads: usize,
+    /// The amount of times `write_fn` was called.
+    pub writes: usize,
+    /// The amount of rows rendered in the main grid.
+    pub rows_drawn: usize,
+    /// The amount of bytes rendered in the main grid, each of which is one or more widgets.
+    pub bytes_drawn: usize,
+    /// The amount of rows of the ASCII sidebar whose text was unchanged since the last frame, and thus reused.
+    pub cached_ascii_rows: usize,
+    /// The amount of widgets the editor added to its `Ui`, excluding popups such as context menus.
+    pub widgets: usize,
+    /// The time it took to draw the editor contents, including the host's `read_fn` and `write_fn` calls.
+    ///
+    /// When the options and grid are drawn separately this is the time of both combined.
+    ///
+    /// Always `None` on `wasm32`, where [`std::time::Instant`] isn't available.
+    pub draw_time: Option<Duration>,
+}
+
+impl MemoryEditorMetrics {
+    /// The fraction of reads which were served from the cache rather than `read_fn`, `None` if nothing was read.
+    pub fn cache_hit_rate(&self) -> Option<f32> {
+        let lookups = self.reads + self.cached_reads;
+        (lookups > 0).then(|| self.cached_reads as f32 / lookups as f32)
+    }
+}
+
+/// The amount of widgets registered in the layer of `ui` so far this frame.
+pub(crate) fn widget_count(ui: &Ui) -> usize {
+    ui.ctx()
+        .viewport(|viewport| viewport.this_pass.widgets.get_layer(ui.layer_id()).count())
+}
+
+impl MemoryEditor {
+    /// Returns the statistics of the last frame the editor was drawn.
+    pub fn metrics(&self) -> &MemoryEditorMetrics {
+        &self.metrics
+    }
+}
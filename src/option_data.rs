@@ -1,17 +1,33 @@
+use crate::jump::InterestingByteJump;
+use crate::patch::PendingPatch;
 use crate::Address;
-use egui::{Color32, TextStyle};
+use egui::{Color32, FontId, Key, KeyboardShortcut, Modifiers, Rect, Style, TextStyle, Vec2};
+use std::collections::{BTreeSet, HashMap, VecDeque};
 use std::ops::Range;
+use std::time::Duration;
+
+pub use crate::labels::Labels;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Endianness {
     Big,
     Little,
+    /// PDP-endian, little-endian 16-bit words stored in big-endian order: `0x0A0B0C0D` is stored as `0B 0A 0D 0C`.
+    Middle,
+    /// Big-endian 16-bit words stored in little-endian order: `0x0A0B0C0D` is stored as `0C 0D 0A 0B`.
+    ByteSwapped,
 }
 
 impl Endianness {
     pub fn iter() -> impl Iterator<Item = Endianness> {
-        vec![Endianness::Big, Endianness::Little].into_iter()
+        vec![
+            Endianness::Big,
+            Endianness::Little,
+            Endianness::Middle,
+            Endianness::ByteSwapped,
+        ]
+        .into_iter()
     }
 }
 
@@ -47,7 +63,96 @@ impl DataFormatType {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+/// How the value of a byte is displayed in the main UI.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CellFormat {
+    /// Two hex digits per byte, `3A`.
+    Byte,
+    /// Every nibble is displayed, and can be edited, as a separate cell, `3 A`.
+    /// Useful for 4-bit packed data such as certain palette or audio formats.
+    Nibble,
+    /// Every bit is displayed as a separate cell, `00111010`, which is toggled by clicking it.
+    /// Useful for flag registers.
+    Binary,
+}
+
+impl CellFormat {
+    pub fn iter() -> impl Iterator<Item = CellFormat> {
+        [CellFormat::Byte, CellFormat::Nibble, CellFormat::Binary].into_iter()
+    }
+
+    /// The amount of cells a single byte is split over.
+    pub const fn sub_cells(&self) -> usize {
+        match self {
+            CellFormat::Byte => 1,
+            CellFormat::Nibble => 2,
+            CellFormat::Binary => 8,
+        }
+    }
+
+    /// The amount of digits a single cell displays.
+    pub const fn digits(&self) -> usize {
+        match self {
+            CellFormat::Byte => 2,
+            CellFormat::Nibble | CellFormat::Binary => 1,
+        }
+    }
+
+    /// The amount of bytes displayed in one group, groups are spaced further apart than the bytes within them.
+    pub const fn bytes_per_group(&self) -> usize {
+        match self {
+            CellFormat::Byte | CellFormat::Nibble => 8,
+            CellFormat::Binary => 4,
+        }
+    }
+
+    /// The horizontal spacing between the cells of a single byte.
+    pub const fn sub_cell_spacing(&self) -> f32 {
+        match self {
+            CellFormat::Byte | CellFormat::Nibble => 3.0,
+            CellFormat::Binary => 0.0,
+        }
+    }
+
+    /// Whether cells are edited by clicking them to toggle their value, rather than by typing.
+    pub const fn is_toggle(&self) -> bool {
+        matches!(self, CellFormat::Binary)
+    }
+
+    /// Format the part of `value` displayed in `sub_cell`.
+    pub fn format(&self, value: u8, sub_cell: usize) -> String {
+        match self {
+            CellFormat::Byte => format!("{:02X}", value),
+            CellFormat::Nibble => format!("{:X}", (value >> self.shift(sub_cell)) & 0xF),
+            CellFormat::Binary => format!("{}", (value >> self.shift(sub_cell)) & 1),
+        }
+    }
+
+    /// Replace the part of `value` displayed in `sub_cell` with `cell_value`.
+    pub fn apply(&self, value: u8, sub_cell: usize, cell_value: u8) -> u8 {
+        let mask: u8 = match self {
+            CellFormat::Byte => 0xFF,
+            CellFormat::Nibble => 0xF,
+            CellFormat::Binary => 1,
+        };
+        let shift = self.shift(sub_cell);
+
+        (value & !(mask << shift)) | ((cell_value & mask) << shift)
+    }
+
+    /// Flip all bits displayed in `sub_cell`.
+    pub fn toggle(&self, value: u8, sub_cell: usize) -> u8 {
+        self.apply(value, sub_cell, !(value >> self.shift(sub_cell)))
+    }
+
+    /// The amount of bits the part displayed in `sub_cell` is shifted by, the most significant part is shown first.
+    const fn shift(&self, sub_cell: usize) -> u32 {
+        ((self.sub_cells() - 1 - sub_cell) * (8 / self.sub_cells())) as u32
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(default))]
 pub struct DataPreviewOptions {
@@ -64,16 +169,122 @@ impl Default for DataPreviewOptions {
     }
 }
 
+/// The horizontal order of the columns of the main UI.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LayoutDirection {
+    /// The addresses are on the left, followed by the values and the ASCII sidebar.
+    LeftToRight,
+    /// The addresses are on the right, preceded by the values and the ASCII sidebar, for right-to-left frontends.
+    ///
+    /// The contents of every column are still written left to right, as is usual for numbers in right-to-left
+    /// scripts.
+    RightToLeft,
+}
+
+/// A named set of layout options, such as `Registers` with 4 columns of binary cells, which can be switched between
+/// in the UI.
+///
+/// Profiles are stored in [`MemoryEditorOptions::profiles`], and are thus saved with the `serde` feature.
+/// Apply one with [`crate::MemoryEditor::apply_layout_profile`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayoutProfile {
+    pub name: String,
+    pub column_count: usize,
+    pub cell_format: CellFormat,
+    pub show_ascii: bool,
+    pub data_preview: DataPreviewOptions,
+}
+
+impl LayoutProfile {
+    /// Create a profile from the current layout of `options`.
+    pub fn from_options(name: impl Into<String>, options: &MemoryEditorOptions) -> Self {
+        LayoutProfile {
+            name: name.into(),
+            column_count: options.column_count,
+            cell_format: options.cell_format,
+            show_ascii: options.show_ascii,
+            data_preview: options.data_preview,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(default))]
 pub struct MemoryEditorOptions {
     /// Whether to show the ASCII representation of all the `u8` values in the main UI.
     pub show_ascii: bool,
+    /// Whether to automatically hide the ASCII sidebar when there isn't enough horizontal space to display it.
+    /// Default is `false`.
+    pub auto_hide_ascii: bool,
+    /// Whether to automatically display fewer columns than [`MemoryEditorOptions::column_count`] when there isn't
+    /// enough horizontal space to display them all.
+    /// Default is `false`.
+    pub auto_reduce_columns: bool,
+    /// Whether to show a gutter to the left of the addresses, where clicking toggles a marker for the row.
+    /// Intended for memory breakpoints, see [`crate::MemoryEditor::markers`] and [`crate::EditorResponse::toggled_markers`].
+    /// Default is `false`.
+    pub show_marker_gutter: bool,
+    /// The address the address column counts from, showing every row as its offset from it such as `+0x0010`.
+    /// Can be any address, even outside of the address ranges, and is set from the options or the context menu of an
+    /// address.
+    /// Default is `None`, showing the addresses themselves.
+    pub display_base: Option<Address>,
+    /// Whether to show a column after the grid with the names of the symbols starting in each row.
+    /// Only has an effect when a symbol provider was set with [`crate::MemoryEditor::with_symbol_provider`].
+    /// Default is `false`.
+    pub show_label_column: bool,
+    /// Whether to show the percentage of `0x00` and `0xFF` bytes and the entropy of the selected region next to the
+    /// region selector. The summary is computed over multiple frames, and reads the entire region once.
+    /// Default is `false`.
+    pub show_region_summary: bool,
+    /// Whether to show the address of the first visible row, and how far it is into the region, in the top right
+    /// corner of the grid next to the scrollbar.
+    /// Default is `false`.
+    pub show_scroll_position: bool,
+    /// Whether to show ticks next to the scrollbar at the relative positions of the search matches, markers,
+    /// comparison mismatches and modified bytes of the region, as filtered in the `Locations` section.
+    /// Default is `false`.
+    pub show_scrollbar_ticks: bool,
+    /// Whether to show a `0`-`F` keypad over the grid while a value is being edited, for touch devices and
+    /// controller-driven frontends without a keyboard.
+    /// Default is `false`.
+    pub show_keypad: bool,
+    /// Highlight every readable byte which differs from this value, such as `0x00` after initializing memory or `0xFF`
+    /// after erasing flash, to show which parts were touched since.
+    /// Default is `None`.
+    pub fill_diff_value: Option<u8>,
+    /// Whether editing is temporarily disabled, even though a `write_fn` was provided.
+    /// Can be toggled in the UI to prevent accidental edits while browsing live memory.
+    /// Default is `false`.
+    pub is_write_locked: bool,
+    /// Whether every write made through the UI has to be confirmed before it's passed to the `write_fn`.
+    /// Useful for fragile targets such as memory mapped IO.
+    /// Default is `false`.
+    pub confirm_writes: bool,
+    /// The most writes passed to the `write_fn` per frame, further writes are queued and performed in the following
+    /// frames, while their bytes are shown in the warning colour. Keeps the UI responsive when every write goes through
+    /// a slow target, such as a debug probe. A budget of `0` is treated as `1`, such that writes are never stuck.
+    /// Default is `None`, performing every write immediately.
+    pub writes_per_frame: Option<usize>,
+    /// Whether to briefly shake an outline around a value whose input was rejected, such as a typed character which
+    /// isn't a hex digit. See also [`crate::MemoryEditor::with_rejected_input_callback`].
+    /// Default is `true`.
+    pub show_rejected_input: bool,
     /// Whether `0x00` values in the main UI should use the [`MemoryEditorOptions::zero_colour`].
     pub show_zero_colour: bool,
     /// The value to display if the `read_fn` ever returns [Option::None].
     pub none_display_value: String,
+    /// The character non-printable bytes, and those the `read_fn` returns `None` for, are replaced with when copying
+    /// the selection as text.
+    /// Default is `.`, matching the ASCII sidebar.
+    pub copy_text_placeholder: char,
+    /// The most bytes copied from the selection at once, larger selections show a warning instead of reading every
+    /// selected byte in a single frame.
+    /// Default is `0x100000`, one MiB.
+    pub max_copy_bytes: usize,
     /// Whether the options header is collapsed by default or not.
     /// Default is `false`.
     pub is_options_collapsed: bool,
@@ -83,6 +294,9 @@ pub struct MemoryEditorOptions {
     ///
     /// At the moment, you'll at most be able to display the range: `0..2^(24 + log_2(column_count))`.
     pub column_count: usize,
+    /// How the value of every byte is displayed in the main UI.
+    /// Default is [`CellFormat::Byte`].
+    pub cell_format: CellFormat,
     /// Whether column size can be modified
     /// Default is `true`.
     pub is_resizable_column: bool,
@@ -91,17 +305,94 @@ pub struct MemoryEditorOptions {
     /// The colour for address indicators on the very left of the UI.
     pub address_text_colour: Color32,
     /// The highlight colour for both the main UI and the ASCII sidebar.
-    /// This will be enabled when you right-click an address, or when using the `goto address` function in the UI.
+    /// This will be enabled when you right-click an address.
     pub highlight_text_colour: Color32,
+    /// The highlight colour of the target of the `goto address` function in the UI.
+    pub goto_highlight_colour: Color32,
+    /// The text colour of the active match of the `Search` section, in both the values and the ASCII sidebar.
+    pub search_match_colour: Color32,
+    /// The background colour of every other match of the `Search` section.
+    pub search_match_background_colour: Color32,
     /// The [`egui::TextStyle`] for the main UI, indicating the values.
+    /// Unused if [`MemoryEditorOptions::value_font_id`] or [`MemoryEditorOptions::font_id`] is set.
     /// Default is [`egui::TextStyle::Monospace`]
     pub memory_editor_text_style: TextStyle,
     /// The [`egui::TextStyle`] for the addresses in the main UI on the left.
+    /// Unused if [`MemoryEditorOptions::address_font_id`] or [`MemoryEditorOptions::font_id`] is set.
     /// Default is [`egui::TextStyle::Monospace`]
     pub memory_editor_address_text_style: TextStyle,
     /// The [`egui::TextStyle`] for the ASCII values in the right side-bar (if they're enabled).
+    /// Unused if [`MemoryEditorOptions::ascii_font_id`] or [`MemoryEditorOptions::font_id`] is set.
     /// Default is [`egui::TextStyle::Monospace`]
     pub memory_editor_ascii_text_style: TextStyle,
+    /// A font which overrides all text styles of the main UI, for when the editor should use a different font than
+    /// the app's global [`egui::TextStyle::Monospace`] style.
+    ///
+    /// Proportional fonts are supported, every value is then given the width of the widest digit to keep the columns
+    /// aligned.
+    /// Default is `None`.
+    pub font_id: Option<FontId>,
+    /// The font for the addresses in the main UI, overriding both [`MemoryEditorOptions::font_id`] and
+    /// [`MemoryEditorOptions::memory_editor_address_text_style`].
+    /// Default is `None`.
+    pub address_font_id: Option<FontId>,
+    /// The font for the values in the main UI, overriding both [`MemoryEditorOptions::font_id`] and
+    /// [`MemoryEditorOptions::memory_editor_text_style`].
+    /// Default is `None`.
+    pub value_font_id: Option<FontId>,
+    /// The font for the ASCII sidebar, overriding both [`MemoryEditorOptions::font_id`] and
+    /// [`MemoryEditorOptions::memory_editor_ascii_text_style`].
+    /// Default is `None`.
+    pub ascii_font_id: Option<FontId>,
+    /// The horizontal order of the columns of the main UI.
+    /// Default is [`LayoutDirection::LeftToRight`].
+    pub layout_direction: LayoutDirection,
+    /// The minimum time between reads of the visible memory, in the meantime the values of the last read are shown.
+    /// Useful to avoid contention with a mutex protected emulator core, rather than reading it every frame.
+    /// Edits made in the UI are always shown immediately, long running operations read the current memory.
+    /// Default is `None`, reading the memory every frame.
+    pub refresh_interval: Option<Duration>,
+    /// Whether files can be dropped onto the editor, which are then reported in
+    /// [`crate::EditorResponse::dropped_files`] for the host to load.
+    /// Default is `false`.
+    pub accept_dropped_files: bool,
+    /// Whether jumping to an address animates the scroll instead of moving there instantly.
+    /// Default is `false`.
+    pub animate_goto: bool,
+    /// Whether a goto to an address outside of the current address range goes to the nearest address within it,
+    /// rather than only showing an error.
+    /// Default is `false`.
+    pub clamp_goto: bool,
+    /// The shortcut which goes to the next match of the search.
+    /// Default is `F3`.
+    pub find_next_shortcut: KeyboardShortcut,
+    /// The shortcut which goes to the previous match of the search.
+    /// Default is `Shift+F3`.
+    pub find_previous_shortcut: KeyboardShortcut,
+    /// The shortcut which reverts the last operation that wrote to memory, see
+    /// [`crate::patch::EditHistory::groups`].
+    ///
+    /// The reverting writes are recorded as an operation of their own, so using the shortcut again redoes the
+    /// reverted operation.
+    /// Default is `Ctrl+Z`.
+    pub undo_shortcut: KeyboardShortcut,
+    /// The maximum amount of addresses long running operations, such as comparisons, read per frame.
+    /// Lower values keep the UI more responsive for slow `read_fn`s, at the cost of taking longer to complete.
+    /// Default is `0x10000`.
+    pub task_bytes_per_frame: usize,
+    /// The position of the window and the size of its contents the last time it was shown by
+    /// [`crate::MemoryEditor::window_ui`], used as the initial geometry of the window when the options are restored.
+    /// Default is `None`, letting `egui` place the window.
+    pub window_rect: Option<Rect>,
+    /// Whether the window was open the last time [`crate::MemoryEditor::window_ui`] was called, hosts can
+    /// initialize their `is_open` flag with it when the options are restored.
+    /// Default is `true`.
+    pub is_window_open: bool,
+    /// The layout profiles which can be selected in the UI.
+    /// Default is empty, profiles can be added in the UI, or with [`LayoutProfile::from_options`].
+    pub profiles: Vec<LayoutProfile>,
+    /// The user-facing strings of the UI, which can be overridden to translate the editor.
+    pub labels: Labels,
     /// The selected address range, always applicable, not really relevant for consumers of the editor.
     pub(crate) selected_address_range: String,
 }
@@ -111,78 +402,443 @@ impl Default for MemoryEditorOptions {
         MemoryEditorOptions {
             data_preview: Default::default(),
             show_ascii: true,
+            auto_hide_ascii: false,
+            auto_reduce_columns: false,
+            show_marker_gutter: false,
+            display_base: None,
+            show_label_column: false,
+            show_region_summary: false,
+            show_scroll_position: false,
+            show_scrollbar_ticks: false,
+            show_keypad: false,
+            fill_diff_value: None,
+            profiles: Vec::new(),
+            labels: Default::default(),
+            is_write_locked: false,
+            confirm_writes: false,
+            writes_per_frame: None,
+            show_rejected_input: true,
             show_zero_colour: true,
             none_display_value: "--".to_string(),
+            copy_text_placeholder: '.',
+            max_copy_bytes: 0x10_0000,
             is_options_collapsed: false,
             zero_colour: Color32::from_gray(80),
             is_resizable_column: true,
             column_count: 16,
+            cell_format: CellFormat::Byte,
             address_text_colour: Color32::from_rgb(125, 0, 125),
             highlight_text_colour: Color32::from_rgb(0, 140, 140),
+            goto_highlight_colour: Color32::from_rgb(210, 120, 0),
+            search_match_colour: Color32::from_rgb(70, 160, 40),
+            search_match_background_colour: Color32::from_rgba_unmultiplied(70, 160, 40, 48),
             memory_editor_text_style: TextStyle::Monospace,
             memory_editor_address_text_style: TextStyle::Monospace,
             memory_editor_ascii_text_style: TextStyle::Monospace,
+            font_id: None,
+            address_font_id: None,
+            value_font_id: None,
+            ascii_font_id: None,
+            layout_direction: LayoutDirection::LeftToRight,
+            refresh_interval: None,
+            accept_dropped_files: false,
+            animate_goto: false,
+            clamp_goto: false,
+            find_next_shortcut: KeyboardShortcut::new(Modifiers::NONE, Key::F3),
+            find_previous_shortcut: KeyboardShortcut::new(Modifiers::SHIFT, Key::F3),
+            undo_shortcut: KeyboardShortcut::new(Modifiers::COMMAND, Key::Z),
+            task_bytes_per_frame: 0x10000,
+            window_rect: None,
+            is_window_open: true,
             selected_address_range: "".to_string(),
         }
     }
 }
 
+impl MemoryEditorOptions {
+    /// The font of the addresses in the main UI.
+    pub(crate) fn address_font(&self, style: &Style) -> FontId {
+        self.resolve_font(&self.address_font_id, &self.memory_editor_address_text_style, style)
+    }
+
+    /// The font of the values in the main UI, also used for other lists of values, such as the edit history.
+    pub(crate) fn value_font(&self, style: &Style) -> FontId {
+        self.resolve_font(&self.value_font_id, &self.memory_editor_text_style, style)
+    }
+
+    /// The font of the ASCII sidebar.
+    pub(crate) fn ascii_font(&self, style: &Style) -> FontId {
+        self.resolve_font(&self.ascii_font_id, &self.memory_editor_ascii_text_style, style)
+    }
+
+    /// The most specific of the element's own font, the editor-wide font, and the element's text style.
+    fn resolve_font(&self, font_id: &Option<FontId>, text_style: &TextStyle, style: &Style) -> FontId {
+        font_id
+            .as_ref()
+            .or(self.font_id.as_ref())
+            .cloned()
+            .unwrap_or_else(|| text_style.resolve(style))
+    }
+}
+
 /// Some extra, non-serializable state for between frames.
 #[derive(Debug, Default, Clone)]
 pub(crate) struct BetweenFrameData {
+    /// The state of the view currently shown, see [`ViewState`].
+    pub view: ViewState,
+
+    pub memory_range_combo_box_enabled: bool,
+    /// Whether the host provided a `write_fn` this frame, regardless of [`MemoryEditorOptions::is_write_locked`].
+    pub has_write_fn: bool,
+    /// Whether the [`crate::SLICE_RANGE_NAME`] range was registered by one of the slice methods.
+    pub is_slice_range_registered: bool,
+
+    /// The `egui` time of the last frame the editor was drawn in, used to detect the first draw of a frame.
+    pub last_frame_time: Option<f64>,
+
+    /// The text of the display base field, see [`MemoryEditorOptions::display_base`].
+    pub display_base_string: String,
+    /// The name to save the current layout under, see [`LayoutProfile`].
+    pub new_profile_name: String,
+
+    /// Writes waiting for the user to confirm them, see [`MemoryEditorOptions::confirm_writes`].
+    pub unconfirmed_writes: Vec<(Address, u8)>,
+    /// Writes waiting for a later frame, see [`MemoryEditorOptions::writes_per_frame`].
+    queued_writes: VecDeque<(Address, u8)>,
+    /// The last queued value of every address in `queued_writes`, and how many writes to it are queued.
+    queued_values: HashMap<Address, (u8, usize)>,
+    /// The amount of writes passed to the `write_fn` this frame.
+    pub frame_writes: usize,
+    /// The address and time of the last rejected input, see [`MemoryEditorOptions::show_rejected_input`].
+    pub rejected_input: Option<(Address, f64)>,
+    /// Writes replayed from a recording, performed as soon as a `write_fn` is available.
+    pub replayed_writes: Vec<(Address, u8)>,
+    /// A patch waiting for the user to confirm it, its addresses are highlighted in the UI.
+    pub pending_patch: Option<PendingPatch>,
+    /// The amount of writes of the last applied patch which were skipped, as their address wasn't editable.
+    pub skipped_patch_writes: usize,
+    /// The running jump to the next interesting byte, started from the goto row of the options.
+    pub interesting_byte_jump: Option<InterestingByteJump>,
+}
+
+/// The state of a single view onto the memory, such as its layout, scroll position, selection and goto.
+///
+/// Every [`crate::MemoryEditorView`] keeps its own, which it exchanges with the one of the editor while drawing.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ViewState {
     /// Used to ensure we can resize the window in height, but not in width.
     pub previous_frame_editor_width: f32,
     pub previous_frame_text_edit_size: f32,
+    /// The amount of columns displayed this frame, `0` if not yet determined.
+    pub effective_column_count: usize,
+    /// The most columns which fit on the screen this frame, `0` if there is no limit.
+    pub screen_column_limit: usize,
+    /// The fixed size of every value and ASCII character this frame, only set for fonts which aren't monospace.
+    pub value_cell_size: Option<Vec2>,
+    pub ascii_cell_size: Option<Vec2>,
+    /// The available width of the window before it was shrunk, see [`crate::MemoryEditor::window_ui`].
+    pub window_available_width: Option<f32>,
+    /// Whether the ASCII sidebar was hidden due to a lack of space this frame.
+    pub is_ascii_auto_hidden: bool,
+    /// The first visible address and the time of the previous frame, used to determine the scroll velocity.
+    pub previous_visible_start: Address,
+    pub previous_visible_time: f64,
+    /// Scroll velocity in addresses per second.
+    pub scroll_velocity: f64,
     /// The address a user clicked on in the UI in the previous frame, used for DataPreview
     pub selected_edit_address: Option<Address>,
     pub selected_edit_address_string: String,
+    /// The cell within the `selected_edit_address` being edited, see [`CellFormat::sub_cells`].
+    pub selected_edit_sub_cell: usize,
     pub selected_edit_address_request_focus: bool,
-
-    pub memory_range_combo_box_enabled: bool,
+    /// The address of the data preview value being edited, and its text.
+    pub data_preview_edit: Option<(Address, String)>,
+    pub data_preview_edit_request_focus: bool,
+    pub data_preview_error: Option<String>,
 
     pub selected_highlight_address: Option<Address>,
+    /// The other end of a multi-byte selection starting at the `selected_highlight_address`, inclusive.
+    pub selection_end: Option<Address>,
+    /// Individually selected addresses, toggled with Ctrl+click. A value typed into one of them is written to all.
+    pub scattered_selection: BTreeSet<Address>,
+    /// Whether the user is dragging across the values, extending the selection to every value the pointer passes.
+    pub is_drag_selecting: bool,
+    /// The address an `Alt`+drag started at, see [`BetweenFrameData::extend_rectangle_selection`].
+    pub rectangle_anchor: Option<Address>,
+    /// Whether the `selected_highlight_address` was set by a goto, rather than the user selecting it.
+    pub is_goto_highlight: bool,
     /// Whether to show additional highlights around items after the current selected item when they'd be part
     /// of the value in the data preview section.
     pub show_additional_highlights: bool,
 
+    /// The region shown in the previous frame, used to detect region switches.
+    pub previous_region: String,
+    /// The region the current selection belongs to, see [`BetweenFrameData::switch_region_selection`].
+    pub selection_region: String,
+    /// The selection of every other region, restored when switching back to it.
+    pub region_selections: HashMap<String, RegionSelection>,
+    /// The last scroll offset of every region, restored when switching back to it.
+    pub region_scroll_offsets: HashMap<String, f32>,
+
     pub goto_address_string: String,
+    /// Why the last submitted goto didn't go to the entered address, shown next to the goto field.
+    pub goto_error: Option<String>,
     pub goto_address_line: Option<usize>,
+    /// The target of the last goto, used to record it, see [`crate::recording`].
+    pub last_goto: Option<Address>,
+    /// A line to scroll to on the next frame, without highlighting anything.
+    pub scroll_line: Option<usize>,
+    /// An address to scroll to at the start of the next frame, see [`crate::MemoryEditor::with_initial_address`].
+    pub initial_address: Option<Address>,
+    /// An address to jump to at the start of the next frame, see [`crate::MemoryEditor::queue_goto`].
+    pub queued_goto: Option<Address>,
+    /// The running goto scroll animation as `(start offset, target offset, start time)`.
+    pub goto_animation: Option<(f32, f32, f64)>,
+
+    /// The selection and its size when copying it was refused, see [`MemoryEditorOptions::max_copy_bytes`].
+    pub oversized_copy: Option<(Option<Range<Address>>, usize)>,
+    /// The area of the keypad in the last frame, if it was shown, see [`MemoryEditorOptions::show_keypad`].
+    pub keypad_rect: Option<Rect>,
+}
+
+/// The selection state of a region which isn't currently shown.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct RegionSelection {
+    pub highlight_address: Option<Address>,
+    pub selection_end: Option<Address>,
+    pub scattered_selection: BTreeSet<Address>,
 }
 
 impl BetweenFrameData {
+    /// Queue a write for a later frame, see [`MemoryEditorOptions::writes_per_frame`].
+    pub fn queue_write(&mut self, address: Address, value: u8) {
+        self.queued_writes.push_back((address, value));
+        let (queued_value, count) = self.queued_values.entry(address).or_insert((value, 0));
+        *queued_value = value;
+        *count += 1;
+    }
+
+    /// Take the oldest queued write out of the queue.
+    pub fn pop_queued_write(&mut self) -> Option<(Address, u8)> {
+        let (address, value) = self.queued_writes.pop_front()?;
+        if let Some((_, count)) = self.queued_values.get_mut(&address) {
+            *count -= 1;
+            if *count == 0 {
+                self.queued_values.remove(&address);
+            }
+        }
+
+        Some((address, value))
+    }
+
+    /// The amount of writes waiting for a later frame.
+    #[inline]
+    pub fn queued_write_count(&self) -> usize {
+        self.queued_writes.len()
+    }
+
+    /// The value the last queued write to `address` will write, if any.
+    #[inline]
+    pub fn queued_value(&self, address: Address) -> Option<u8> {
+        self.queued_values.get(&address).map(|&(value, _)| value)
+    }
+
+    /// Store the selection of the previous region and restore the one of `region`, if `region` changed.
+    ///
+    /// Overlapping regions thus keep separate selections, rather than sharing the addresses they have in common.
+    pub fn switch_region_selection(&mut self, region: &str) {
+        if self.view.selection_region == region {
+            return;
+        }
+
+        let previous_region = std::mem::replace(&mut self.view.selection_region, region.to_string());
+        // Before the first frame there is no previous region, keep whatever was selected up front.
+        if previous_region.is_empty() {
+            return;
+        }
+
+        let previous = RegionSelection {
+            highlight_address: self.view.selected_highlight_address.take(),
+            selection_end: self.view.selection_end.take(),
+            scattered_selection: std::mem::take(&mut self.view.scattered_selection),
+        };
+        self.view.region_selections.insert(previous_region, previous);
+
+        let restored = self.view.region_selections.remove(region).unwrap_or_default();
+        self.view.selected_highlight_address = restored.highlight_address;
+        self.view.selection_end = restored.selection_end;
+        self.view.scattered_selection = restored.scattered_selection;
+        self.view.goto_address_string = restored
+            .highlight_address
+            .map_or_else(String::new, |address| format!("{:X}", address));
+        self.view.is_goto_highlight = false;
+        self.view.selected_edit_address = None;
+    }
+
     pub fn set_highlight_address(&mut self, new_address: Address) {
+        self.view.is_goto_highlight = false;
+        self.view.selection_end = None;
+        // Editing one of the scattered addresses keeps the selection, such that the value is applied to all of them.
+        if !self.view.scattered_selection.contains(&new_address) {
+            self.view.scattered_selection.clear();
+        }
         // We want to be able to unselect it.
-        self.selected_highlight_address = if matches!(self.selected_highlight_address, Some(current) if current == new_address)
+        self.view.selected_highlight_address = if matches!(self.view.selected_highlight_address, Some(current) if current == new_address)
         {
-            self.goto_address_string.clear();
+            self.view.goto_address_string.clear();
             None
         } else {
-            self.goto_address_string = format!("{:X}", new_address);
+            self.view.goto_address_string = format!("{:X}", new_address);
             Some(new_address)
         };
     }
 
     pub fn set_selected_edit_address(&mut self, new_address: Option<Address>, address_space: &Range<Address>) {
-        self.selected_edit_address_string.clear();
+        self.view.selected_edit_address_string.clear();
+        self.view.selected_edit_sub_cell = 0;
         if matches!(new_address, Some(address) if address_space.contains(&address)) {
             self.set_highlight_address(new_address.unwrap());
-            self.selected_edit_address_request_focus = true;
-            self.selected_edit_address = new_address;
+            self.view.selected_edit_address_request_focus = true;
+            self.view.selected_edit_address = new_address;
         } else {
-            self.selected_edit_address = None;
+            self.view.selected_edit_address = None;
+        }
+    }
+
+    /// Start editing a specific cell of the provided address, see [`CellFormat::sub_cells`].
+    pub fn set_selected_edit_cell(&mut self, address: Address, sub_cell: usize, address_space: &Range<Address>) {
+        if self.view.selected_edit_address == Some(address) {
+            // Don't unselect the highlight when moving within the same byte
+            self.view.selected_edit_address_string.clear();
+            self.view.selected_edit_address_request_focus = true;
+        } else {
+            self.set_selected_edit_address(Some(address), address_space);
+        }
+        self.view.selected_edit_sub_cell = sub_cell;
+    }
+
+    /// Scroll to the provided address on the next frame and highlight it.
+    pub fn set_goto_address(&mut self, address: Address, address_space: &Range<Address>, column_count: usize) {
+        if let Some(offset) = address.checked_sub(address_space.start) {
+            self.view.goto_address_line = Some(offset / column_count);
+            self.view.goto_address_string = format!("{:X}", address);
+            self.view.selected_highlight_address = Some(address);
+            self.view.selection_end = None;
+            self.view.is_goto_highlight = true;
+            self.view.last_goto = Some(address);
+        }
+    }
+
+    /// Select all of `addresses`, replacing the current selection.
+    pub fn select_range(&mut self, addresses: Range<Address>) {
+        if addresses.is_empty() {
+            return;
+        }
+
+        self.view.is_goto_highlight = false;
+        self.view.scattered_selection.clear();
+        self.view.goto_address_string = format!("{:X}", addresses.start);
+        self.view.selected_highlight_address = Some(addresses.start);
+        self.view.selection_end = Some(addresses.end - 1);
+    }
+
+    /// Start a drag selection at `address`, replacing the current selection.
+    pub fn start_drag_selection(&mut self, address: Address) {
+        self.view.is_goto_highlight = false;
+        self.view.is_drag_selecting = true;
+        self.view.scattered_selection.clear();
+        self.view.selected_edit_address = None;
+        self.view.goto_address_string = format!("{:X}", address);
+        self.view.selected_highlight_address = Some(address);
+        self.view.selection_end = None;
+        self.view.rectangle_anchor = None;
+    }
+
+    /// Start a rectangular drag selection at `address`, replacing the current selection.
+    pub fn start_rectangle_selection(&mut self, address: Address) {
+        self.start_drag_selection(address);
+        self.view.rectangle_anchor = Some(address);
+        self.view.scattered_selection.insert(address);
+    }
+
+    /// Select the same columns in every row from the `rectangle_anchor` up to and including `address`, as a
+    /// scattered selection.
+    pub fn extend_rectangle_selection(
+        &mut self,
+        address: Address,
+        address_space: &Range<Address>,
+        column_count: usize,
+    ) {
+        let Some(anchor) = self.view.rectangle_anchor else {
+            return;
+        };
+        let position = |address: Address| {
+            let offset = address - address_space.start;
+            (offset / column_count, offset % column_count)
+        };
+        let (anchor_row, anchor_column) = position(anchor);
+        let (row, column) = position(address);
+        let columns = anchor_column.min(column)..=anchor_column.max(column);
+
+        self.view.is_goto_highlight = false;
+        self.view.scattered_selection = (anchor_row.min(row)..=anchor_row.max(row))
+            .flat_map(|row| {
+                columns
+                    .clone()
+                    .map(move |column| address_space.start + row * column_count + column)
+            })
+            .filter(|address| address_space.contains(address))
+            .collect();
+    }
+
+    /// Extend the selection from the `selected_highlight_address` up to and including `address`.
+    ///
+    /// Selects just `address` if nothing was selected yet.
+    pub fn extend_selection(&mut self, address: Address) {
+        if self.view.selected_highlight_address.is_some() {
+            self.view.is_goto_highlight = false;
+            self.view.selection_end = Some(address);
+        } else {
+            self.set_highlight_address(address);
+        }
+    }
+
+    /// The selected addresses, ordered from lowest to highest regardless of the direction the selection was made in.
+    pub fn selection(&self) -> Option<Range<Address>> {
+        let anchor = self.view.selected_highlight_address?;
+        let end = self.view.selection_end.unwrap_or(anchor);
+
+        Some(anchor.min(end)..anchor.max(end) + 1)
+    }
+
+    /// The colour to use for the `selected_highlight_address`.
+    #[inline]
+    pub fn highlight_colour(&self, options: &MemoryEditorOptions) -> Color32 {
+        if self.view.is_goto_highlight {
+            options.goto_highlight_colour
+        } else {
+            options.highlight_text_colour
         }
     }
 
     #[inline]
     pub fn should_highlight(&self, address: Address) -> bool {
-        self.selected_highlight_address.map_or(false, |addr| addr == address)
-            || self.selected_edit_address.map_or(false, |addr| addr == address)
+        self.view.selected_highlight_address == Some(address)
+            || self.view.selection_end.is_some()
+                && self.selection().is_some_and(|selection| selection.contains(&address))
+            || self.view.selected_edit_address == Some(address)
+            || self.view.scattered_selection.contains(&address)
     }
 
     pub fn should_subtle_highlight(&self, address: Address, data_format: DataFormatType) -> bool {
-        self.show_additional_highlights
-            && self.selected_highlight_address.map_or(false, |addr| {
-                (addr..addr + data_format.bytes_to_read()).contains(&address)
-            })
+        if self.view.selection_end.is_some() {
+            return self.selection().is_some_and(|selection| selection.contains(&address));
+        }
+
+        self.view.show_additional_highlights
+            && self
+                .view
+                .selected_highlight_address
+                .is_some_and(|addr| (addr..addr + data_format.bytes_to_read()).contains(&address))
     }
 }
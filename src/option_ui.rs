@@ -1,43 +1,82 @@
 use std::ops::Range;
 
-use egui::Ui;
+use egui::{RichText, ScrollArea, Ui};
 
-use crate::option_data::{DataFormatType, DataPreviewOptions, Endianness};
-use crate::{Address, MemoryEditor};
+use crate::address_input::AddressInput;
+use crate::labels::fill;
+use crate::layout::MAX_COLUMN_COUNT;
+use crate::option_data::{CellFormat, DataFormatType, DataPreviewOptions, Endianness, LayoutProfile};
+use crate::{Address, MemoryEditor, RejectedInput};
 
 impl MemoryEditor {
     /// Draw the `Options` collapsing header with the main options and data preview hidden underneath.
     pub(crate) fn draw_options_area<T: ?Sized>(
         &mut self,
         ui: &mut Ui,
+        current_address_range: &Range<Address>,
         mem: &mut T,
         read: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        write: &mut Option<impl FnMut(&mut T, Address, u8)>,
     ) {
-        let current_address_range = self
-            .address_ranges
-            .get(&self.options.selected_address_range)
-            .unwrap()
-            .clone();
-
-        egui::CollapsingHeader::new("🛠 Options")
+        egui::CollapsingHeader::new(&self.options.labels.options_title)
             .default_open(!self.options.is_options_collapsed)
             .show(ui, |ui| {
-                self.draw_main_options(ui, &current_address_range);
+                if self.frame_data.pending_patch.is_some() {
+                    self.draw_pending_patch(ui, mem, read, write);
+                } else if self.frame_data.skipped_patch_writes > 0 {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        fill(
+                            &self.options.labels.skipped_patch_writes,
+                            &[("count", &self.frame_data.skipped_patch_writes)],
+                        ),
+                    );
+                }
+
+                self.draw_main_options(ui, current_address_range);
+
+                self.draw_data_preview(ui, current_address_range, mem, read, write);
+
+                self.draw_template(ui, current_address_range, mem, read, write);
+
+                self.draw_comparison(ui, current_address_range);
+
+                self.draw_checksum(ui, current_address_range, mem, read, write);
+
+                self.draw_hashes(ui, current_address_range);
 
-                self.draw_data_preview(ui, &current_address_range, mem, read);
+                self.draw_analysis(ui, current_address_range);
+
+                self.draw_search(ui, current_address_range, mem, read, write);
+
+                self.draw_locations(ui, current_address_range);
+
+                #[cfg(feature = "histogram")]
+                self.draw_histogram(ui, current_address_range);
+
+                if write.is_some() {
+                    self.draw_edit_history(ui);
+                }
             });
+
+        // Both the search and the value search can go to the matches of another region.
+        self.process_search_region_goto();
     }
 
     /// Draw the main options, including the column selection and goto address.
     fn draw_main_options(&mut self, ui: &mut Ui, current_address_range: &Range<Address>) {
         egui::Grid::new("options_grid").show(ui, |ui| {
+            let labels = &self.options.labels;
+
             // Memory region selection
             if self.frame_data.memory_range_combo_box_enabled {
                 let selected_address_range = &mut self.options.selected_address_range;
                 let address_ranges = &self.address_ranges;
+                let show_region_summary = self.options.show_region_summary;
+                let region_summaries = &mut self.region_summaries;
 
                 ui.horizontal(|ui| {
-                    ui.label("Region:");
+                    ui.label(&labels.region);
 
                     egui::ComboBox::from_id_salt("RegionCombo")
                         .selected_text(selected_address_range.clone())
@@ -46,6 +85,20 @@ impl MemoryEditor {
                                 ui.selectable_value(selected_address_range, range_name.clone(), range_name);
                             });
                         });
+
+                    if show_region_summary {
+                        if let Some(summary) = region_summaries.get(selected_address_range.as_str()) {
+                            ui.weak(summary.description(labels));
+                        }
+
+                        if ui
+                            .small_button("⟳")
+                            .on_hover_text(&labels.recompute_summary_hover)
+                            .clicked()
+                        {
+                            region_summaries.remove(selected_address_range.as_str());
+                        }
+                    }
                 });
             };
 
@@ -55,71 +108,139 @@ impl MemoryEditor {
             if self.options.is_resizable_column {
                 ui.add(
                     egui::DragValue::new(&mut columns_u8)
-                        .range(1.0..=64.0)
-                        .prefix("Columns: ")
+                        .range(1..=MAX_COLUMN_COUNT as u8)
+                        .prefix(&labels.columns)
                         .speed(0.5),
                 );
             } else {
-                ui.add(egui::Label::new(format!("Columns: {}", columns_u8)));
+                ui.add(egui::Label::new(format!("{}{}", labels.columns, columns_u8)));
             }
 
             self.options.column_count = columns_u8 as usize;
 
             // Goto address
+            let goto = AddressInput::new(
+                &mut self.frame_data.view.goto_address_string,
+                current_address_range.clone(),
+            )
+            .desired_width(ui.available_width())
+            .clamp(self.options.clamp_goto)
+            .show(ui);
+            goto.response.on_hover_text(&labels.goto_hover);
+            let is_jump_running = self.frame_data.interesting_byte_jump.is_some();
+            let mut is_jump_toggled = false;
+            ui.horizontal(|ui| {
+                ui.label(fill(
+                    &labels.goto,
+                    &[("range", &format!("{:#X?}", current_address_range))],
+                ));
+
+                if ui.small_button("🔗").on_hover_text(&labels.copy_link_hover).clicked() {
+                    ui.ctx().copy_text(self.goto_link());
+                }
+
+                if is_jump_running {
+                    ui.spinner();
+                    is_jump_toggled = ui.small_button("✖").on_hover_text(&labels.cancel).clicked();
+                } else {
+                    is_jump_toggled = ui
+                        .small_button("⏭")
+                        .on_hover_text(&labels.interesting_byte_hover)
+                        .clicked();
+                }
+
+                if let Some(error) = &self.frame_data.view.goto_error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+            });
+
+            if goto.is_submitted {
+                self.frame_data.view.goto_error = match (goto.address, goto.out_of_range) {
+                    (_, Some(address)) => Some(fill(
+                        &labels.goto_out_of_range,
+                        &[
+                            ("address", &format!("{:#X}", address)),
+                            ("range", &format!("{:#X?}", current_address_range)),
+                        ],
+                    )),
+                    // Submitting an empty field only clears the selection.
+                    (None, None) if !self.frame_data.view.goto_address_string.trim().is_empty() => {
+                        Some(labels.goto_invalid.clone())
+                    }
+                    _ => None,
+                };
+
+                match goto.address {
+                    Some(address) => {
+                        self.frame_data
+                            .set_goto_address(address, current_address_range, self.column_count())
+                    }
+                    None => self.frame_data.view.selected_highlight_address = None,
+                }
+            }
+
+            ui.end_row();
+
+            // Find, shares its query and results with the `Search` section.
             let response = ui
                 .add_sized(
                     ui.available_size(),
-                    egui::TextEdit::singleline(&mut self.frame_data.goto_address_string).hint_text("0000"),
+                    egui::TextEdit::singleline(&mut self.search.query).hint_text(&labels.find_hint),
                 )
-                .on_hover_text(
-                    "Goto an address, format: \n\
-                    * An address like `0xAA` can be written as `AA`\n\
-                    * Offset from the base address, if the base is `0xFF00` then one can enter `5` to go to `0xFF05`\n\
-                    Press enter to move to the address",
-                );
-            ui.label(format!("Goto: {:#X?}", current_address_range));
-
-            self.frame_data.goto_address_string.retain(|c| c.is_ascii_hexdigit());
-
-            // For some reason egui is triggering response.clicked() when we press enter at the moment
-            // (didn't used to do this). The additional check for not having enter pressed will need to stay until that is fixed.
-            if response.clicked() && !ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                self.frame_data.goto_address_string.clear();
-            }
+                .on_hover_text(&labels.find_hover);
+            ui.horizontal(|ui| {
+                ui.label(&labels.find);
+
+                if let Some(search) = self.search.search.as_ref().filter(|search| search.task.is_none()) {
+                    let position = match search.active {
+                        Some(active) => fill(
+                            &labels.search_position,
+                            &[("index", &(active + 1)), ("count", &search.matches.len())],
+                        ),
+                        None => fill(&labels.no_matches, &[("query", &search.query)]),
+                    };
+                    ui.weak(position);
+                }
+            });
 
-            // If we pressed enter, move to the address
             if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                let goto_address_string = &mut self.frame_data.goto_address_string;
-
-                if goto_address_string.starts_with("0x") || goto_address_string.starts_with("0X") {
-                    *goto_address_string = goto_address_string[2..].to_string();
+                let region = &self.options.selected_address_range;
+                let other_regions = self.other_regions();
+                if let Some(address) = self
+                    .search
+                    .submit(labels, region, current_address_range.clone(), &other_regions)
+                {
+                    self.frame_data
+                        .set_goto_address(address, current_address_range, self.column_count());
                 }
 
-                let address = Address::from_str_radix(goto_address_string, 16).ok().and_then(|addr| {
-                    if current_address_range.contains(&addr) {
-                        Some(addr)
-                    } else {
-                        // For brevity the user should be able to elide the base address, e.g when using the range
-                        // 0xFF00..0xFFFF the user can write 0x5 to go to 0xFF05
-                        let offset_addr = addr.saturating_add(current_address_range.start);
-
-                        if current_address_range.contains(&offset_addr) {
-                            // We're doing an offset jump, we should update the string to reflect the absolute address
-                            *goto_address_string = format!("{:X}", offset_addr);
-                            Some(offset_addr)
-                        } else {
-                            None
-                        }
-                    }
-                });
+                // Keep the focus, such that pressing enter again goes to the next match.
+                response.request_focus();
+            }
 
-                self.frame_data.goto_address_line = address
-                    .and_then(|addr| addr.checked_sub(current_address_range.start))
-                    .map(|addr| addr / self.options.column_count);
-                self.frame_data.selected_highlight_address = address;
+            ui.end_row();
 
-                response.surrender_focus();
+            // Display base, any address such that it also works for the load address of a binary. The address space
+            // is exclusive, so `Address::MAX` is reported as out of range, but is just as valid.
+            let display_base = AddressInput::new(&mut self.frame_data.display_base_string, 0..Address::MAX)
+                .hint_text(&labels.display_base_hint)
+                .desired_width(ui.available_width())
+                .show(ui);
+            if display_base.is_submitted {
+                if self.frame_data.display_base_string.trim().is_empty() {
+                    self.options.display_base = None;
+                } else if let Some(address) = display_base.address.or(display_base.out_of_range) {
+                    self.options.display_base = Some(address);
+                }
+            }
+            if !display_base.response.has_focus() {
+                self.frame_data.display_base_string = self
+                    .options
+                    .display_base
+                    .map_or_else(String::new, |base| format!("{:X}", base));
             }
+            display_base.response.on_hover_text(&labels.display_base_hover);
+            ui.label(&labels.display_base);
 
             ui.end_row();
 
@@ -127,16 +248,115 @@ impl MemoryEditor {
             let show_ascii_sidebar = &mut self.options.show_ascii;
             let show_zero_colour = &mut self.options.show_zero_colour;
 
-            ui.checkbox(show_ascii_sidebar, "Show ASCII").on_hover_text(format!(
-                "{} the ASCII representation view",
-                if *show_ascii_sidebar { "Disable" } else { "Enable" }
-            ));
+            let ascii_hover = if *show_ascii_sidebar {
+                &labels.disable_ascii_hover
+            } else {
+                &labels.enable_ascii_hover
+            };
+            ui.checkbox(show_ascii_sidebar, &labels.show_ascii)
+                .on_hover_text(ascii_hover);
+
+            ui.checkbox(show_zero_colour, &labels.zero_colour)
+                .on_hover_text(&labels.zero_colour_hover);
+
+            if self.frame_data.has_write_fn {
+                ui.checkbox(&mut self.options.show_keypad, &labels.keypad)
+                    .on_hover_text(&labels.keypad_hover);
+                ui.toggle_value(&mut self.options.is_write_locked, &labels.read_only)
+                    .on_hover_text(&labels.read_only_hover);
+            }
+
+            let mut cell_format = self.options.cell_format;
+            egui::ComboBox::from_label(&labels.cells)
+                .selected_text(format!("{:?}", cell_format))
+                .show_ui(ui, |ui| {
+                    for format in CellFormat::iter() {
+                        ui.selectable_value(&mut cell_format, format, format!("{:?}", format));
+                    }
+                })
+                .response
+                .on_hover_text(&labels.cells_hover);
+            if cell_format != self.options.cell_format {
+                self.set_cell_format(cell_format);
+            }
 
-            ui.checkbox(show_zero_colour, "Custom zero colour")
-                .on_hover_text("If enabled memory values of '0x00' will be coloured differently");
+            if is_jump_toggled && is_jump_running {
+                self.frame_data.interesting_byte_jump = None;
+            } else if is_jump_toggled {
+                self.start_interesting_byte_jump(current_address_range);
+            }
+
+            self.draw_profile_selection(ui);
         });
     }
 
+    /// Draw the dropdown to switch between, and save, [`LayoutProfile`]s.
+    fn draw_profile_selection(&mut self, ui: &mut Ui) {
+        let options = &self.options;
+        let labels = &options.labels;
+        let new_profile_name = &mut self.frame_data.new_profile_name;
+        let current = options
+            .profiles
+            .iter()
+            .find(|profile| **profile == LayoutProfile::from_options(profile.name.clone(), options))
+            .map_or_else(|| labels.custom_profile.clone(), |profile| profile.name.clone());
+        let mut selected = None;
+        let mut removed = None;
+        let mut is_saved = false;
+
+        egui::ComboBox::from_label(&labels.profile)
+            .selected_text(current)
+            .show_ui(ui, |ui| {
+                for (i, profile) in options.profiles.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if ui.selectable_label(false, &profile.name).clicked() {
+                            selected = Some(i);
+                        }
+                        if ui
+                            .small_button("🗑")
+                            .on_hover_text(&labels.remove_profile_hover)
+                            .clicked()
+                        {
+                            removed = Some(i);
+                        }
+                    });
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(new_profile_name)
+                            .hint_text(&labels.profile_name_hint)
+                            .desired_width(100.0),
+                    );
+
+                    is_saved = ui
+                        .add_enabled(
+                            !new_profile_name.trim().is_empty(),
+                            egui::Button::new(&labels.save_profile),
+                        )
+                        .clicked();
+                });
+            })
+            .response
+            .on_hover_text(&labels.profile_hover);
+
+        let options = &mut self.options;
+        if is_saved {
+            let profile = LayoutProfile::from_options(new_profile_name.trim(), options);
+            // Saving under an existing name overwrites that profile.
+            options.profiles.retain(|existing| existing.name != profile.name);
+            options.profiles.push(profile);
+            new_profile_name.clear();
+        }
+        if let Some(profile) = selected.and_then(|i| options.profiles.get(i).cloned()) {
+            self.apply_layout_profile(&profile);
+        }
+        if let Some(i) = removed {
+            self.options.profiles.remove(i);
+        }
+    }
+
     /// Draws the data preview underneath a collapsing header.
     fn draw_data_preview<T: ?Sized>(
         &mut self,
@@ -144,14 +364,20 @@ impl MemoryEditor {
         current_address_range: &Range<Address>,
         mem: &mut T,
         read: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        write: &mut Option<impl FnMut(&mut T, Address, u8)>,
     ) {
-        let response = egui::CollapsingHeader::new("⛃ Data Preview")
+        let labels = &self.options.labels;
+        let mut write_request = None;
+        let other_regions = self.other_regions();
+        // Edited through a copy, such that the value search can borrow the rest of the options.
+        let mut data_preview = self.options.data_preview;
+        let response = egui::CollapsingHeader::new(&labels.data_preview_title)
             .default_open(false)
             .show(ui, |ui| {
                 egui::Grid::new("data_preview_grid").show(ui, |ui| {
-                    let data_preview_options = &mut self.options.data_preview;
+                    let data_preview_options = &mut data_preview;
                     // Format selection
-                    egui::ComboBox::from_label("Endianness")
+                    egui::ComboBox::from_label(&labels.endianness)
                         .selected_text(format!("{:?}", data_preview_options.selected_endianness))
                         .show_ui(ui, |ui| {
                             for endian in Endianness::iter() {
@@ -163,9 +389,9 @@ impl MemoryEditor {
                             }
                         })
                         .response
-                        .on_hover_text("Select the endianness of the data");
+                        .on_hover_text(&labels.endianness_hover);
 
-                    egui::ComboBox::from_label("Format")
+                    egui::ComboBox::from_label(&labels.data_format)
                         .selected_text(format!("{:?}", data_preview_options.selected_data_format))
                         .show_ui(ui, |ui| {
                             for format in DataFormatType::iter() {
@@ -177,43 +403,278 @@ impl MemoryEditor {
                             }
                         })
                         .response
-                        .on_hover_text("Select the number type for data interpretation");
+                        .on_hover_text(&labels.data_format_hover);
 
                     ui.end_row();
 
                     // Read and display the value
-                    let hover_text = "Right click a value in the UI to select it, right click again to unselect";
-
-                    if let Some(address) = self.frame_data.selected_highlight_address {
-                        let value =
-                            Self::read_mem_value(mem, read, address, *data_preview_options, current_address_range);
-                        ui.label(format!("Value at {:#X} (decimal): ", address))
-                            .on_hover_text(hover_text);
-                        ui.label(value);
+                    let hover_text = &labels.selection_hover;
+
+                    if let Some(selection) = self.frame_data.selection() {
+                        let format = data_preview_options.selected_data_format;
+                        // A single selected byte reads forward, a multi-byte selection limits the value to its length.
+                        let length = if self.frame_data.view.selection_end.is_some() {
+                            selection.len().min(format.bytes_to_read())
+                        } else {
+                            format.bytes_to_read()
+                        };
+                        let value = Self::read_mem_value(
+                            mem,
+                            read,
+                            selection.start..selection.start + length,
+                            *data_preview_options,
+                            current_address_range,
+                        );
+                        ui.label(fill(
+                            &labels.value_at,
+                            &[("address", &format!("{:#X}", selection.start))],
+                        ))
+                        .on_hover_text(hover_text);
+
+                        let frame_data = &mut self.frame_data;
+                        match &mut frame_data.view.data_preview_edit {
+                            Some((address, text)) if *address == selection.start => {
+                                let response = ui.add(egui::TextEdit::singleline(text).desired_width(120.0));
+                                if std::mem::take(&mut frame_data.view.data_preview_edit_request_focus) {
+                                    response.request_focus();
+                                }
+
+                                if response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                                    match crate::utilities::value_string_to_bytes(*data_preview_options, text) {
+                                        Some(bytes) => {
+                                            write_request = Some((selection.start, bytes));
+                                            frame_data.view.data_preview_error = None;
+                                        }
+                                        None => {
+                                            frame_data.view.data_preview_error = Some(fill(
+                                                &labels.invalid_value,
+                                                &[("value", &text.trim()), ("format", &format!("{:?}", format))],
+                                            ))
+                                        }
+                                    }
+                                    frame_data.view.data_preview_edit = None;
+                                } else if response.lost_focus() {
+                                    frame_data.view.data_preview_edit = None;
+                                }
+                            }
+                            // Only whole values which fit in the address space can be written.
+                            _ if write.is_some()
+                                && length == format.bytes_to_read()
+                                && selection.start + length <= current_address_range.end =>
+                            {
+                                if ui.link(&value).on_hover_text(&labels.edit_field_hover).clicked() {
+                                    frame_data.view.data_preview_edit = Some((selection.start, value));
+                                    frame_data.view.data_preview_edit_request_focus = true;
+                                }
+                            }
+                            _ => {
+                                ui.label(value);
+                            }
+                        }
+
+                        if length < format.bytes_to_read() {
+                            ui.end_row();
+                            ui.colored_label(
+                                ui.visuals().warn_fg_color,
+                                fill(
+                                    &labels.selection_too_short,
+                                    &[
+                                        ("length", &length),
+                                        ("format", &format!("{:?}", format)),
+                                        ("needed", &format.bytes_to_read()),
+                                    ],
+                                ),
+                            );
+                        }
+
+                        // Peek at the neighbouring values of the same type, useful for walking arrays.
+                        let size = format.bytes_to_read();
+                        let previous = selection.start.checked_sub(size);
+                        let next = selection.start.checked_add(size);
+
+                        for (label, address) in [(&labels.previous_value_at, previous), (&labels.next_value_at, next)] {
+                            let Some(address) = address.filter(|address| current_address_range.contains(address))
+                            else {
+                                continue;
+                            };
+
+                            ui.end_row();
+                            let value = Self::read_mem_value(
+                                mem,
+                                read,
+                                address..address + size,
+                                *data_preview_options,
+                                current_address_range,
+                            );
+                            ui.label(fill(label, &[("address", &format!("{:#X}", address))]));
+                            ui.label(value);
+                        }
                     } else {
-                        ui.label("Value (decimal): ").on_hover_text(hover_text);
-                        ui.label("None");
+                        ui.label(&labels.value).on_hover_text(hover_text);
+                        ui.label(&labels.no_value);
                     }
                 });
+
+                if let Some(error) = &self.frame_data.view.data_preview_error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                let read = &mut |address| read(mem, address);
+                Self::draw_value_search(
+                    ui,
+                    &mut self.search,
+                    &self.options,
+                    data_preview,
+                    current_address_range,
+                    &other_regions,
+                    read,
+                )
             });
+        self.options.data_preview = data_preview;
+
+        if let (Some((start, bytes)), Some(write)) = (write_request, write.as_mut()) {
+            self.apply_bytes(mem, read, write, start, &bytes);
+        }
+
+        if let Some(address) = response.body_returned.flatten() {
+            self.frame_data
+                .set_goto_address(address, current_address_range, self.column_count());
+        }
 
         // Currently relies on the header being open_default(false), otherwise we'd enable the highlight when closing the preview!
         if response.header_response.clicked() {
-            self.frame_data.show_additional_highlights = !self.frame_data.show_additional_highlights;
+            self.frame_data.view.show_additional_highlights = !self.frame_data.view.show_additional_highlights;
         }
     }
 
+    /// Draws a dry-run preview of the pending patch, with the option to apply or discard it.
+    fn draw_pending_patch<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        mem: &mut T,
+        read: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        write: &mut Option<impl FnMut(&mut T, Address, u8)>,
+    ) {
+        let Some(patch) = self.frame_data.pending_patch.as_mut() else {
+            return;
+        };
+
+        let labels = &self.options.labels;
+        let differences = &patch.differences;
+        let mut cancel = false;
+        let mut apply = false;
+        let mut discard = false;
+
+        egui::CollapsingHeader::new(&labels.pending_patch_title)
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.label(fill(
+                    &labels.pending_patch_summary,
+                    &[("count", &patch.changes.len()), ("differences", &differences.len())],
+                ));
+
+                if let Some(task) = &patch.task {
+                    cancel = task.draw_progress(ui, labels);
+                }
+
+                let value_font = self.options.value_font(ui.style());
+                let row_height = ui.fonts(|fonts| fonts.row_height(&value_font));
+                ScrollArea::vertical().max_height(row_height * 8.0).show_rows(
+                    ui,
+                    row_height,
+                    differences.len(),
+                    |ui, rows| {
+                        for &(address, current, value) in &differences[rows] {
+                            let current = current.map_or(self.options.none_display_value.clone(), |current| {
+                                format!("{:02X}", current)
+                            });
+                            ui.label(
+                                RichText::new(format!("{:#X}: {} → {:02X}", address, current, value))
+                                    .font(value_font.clone()),
+                            );
+                        }
+                    },
+                );
+
+                ui.horizontal(|ui| {
+                    apply = ui
+                        .add_enabled(write.is_some(), egui::Button::new(&labels.apply))
+                        .on_hover_text(&labels.apply_hover)
+                        .clicked();
+                    discard = ui.button(&labels.discard).clicked();
+                });
+            });
+
+        if cancel {
+            patch.task = None;
+        }
+
+        if apply {
+            if let (Some(patch), Some(write)) = (self.frame_data.pending_patch.take(), write.as_mut()) {
+                // Applying the previewed patch already is a confirmation, but not a permission.
+                self.history.begin_group();
+                for (address, value) in patch.changes {
+                    if self.is_editable(mem, read, address) {
+                        self.commit_write(mem, read, write, address, value);
+                    } else {
+                        self.frame_data.skipped_patch_writes += 1;
+                        self.reject_input(RejectedInput::NotEditable { address });
+                    }
+                }
+                self.history.end_group();
+            }
+        } else if discard {
+            self.frame_data.pending_patch = None;
+        }
+    }
+
+    /// Draws the summary of the edits made this session, with the option to export them.
+    fn draw_edit_history(&mut self, ui: &mut Ui) {
+        let labels = &self.options.labels;
+
+        egui::CollapsingHeader::new(&labels.edit_history_title)
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let changed_addresses = self.history.changes().len();
+                    ui.label(fill(
+                        &labels.edit_history_summary,
+                        &[("writes", &self.history.len()), ("addresses", &changed_addresses)],
+                    ));
+
+                    ui.add_enabled_ui(!self.history.is_empty(), |ui| {
+                        if ui
+                            .button(&labels.copy_as_list)
+                            .on_hover_text(&labels.copy_as_list_hover)
+                            .clicked()
+                        {
+                            ui.ctx().copy_text(self.history.to_address_value_list());
+                        }
+
+                        if ui
+                            .button(&labels.clear)
+                            .on_hover_text(&labels.clear_history_hover)
+                            .clicked()
+                        {
+                            self.history.clear();
+                        }
+                    });
+                });
+            });
+    }
+
     fn read_mem_value<T: ?Sized>(
         mem: &mut T,
         read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
-        address: Address,
+        addresses: Range<Address>,
         data_preview: DataPreviewOptions,
         address_space: &Range<Address>,
     ) -> String {
+        // Addresses outside of the selection or address space read as zero.
         let bytes = (0..data_preview.selected_data_format.bytes_to_read())
             .map(|i| {
-                let read_address = address + i;
-                if address_space.contains(&read_address) {
+                let read_address = addresses.start + i;
+                if addresses.contains(&read_address) && address_space.contains(&read_address) {
                     read_fn(mem, read_address).unwrap_or(0)
                 } else {
                     0
@@ -221,6 +682,6 @@ impl MemoryEditor {
             })
             .collect::<Vec<u8>>();
 
-        crate::utilities::slice_to_decimal_string(data_preview, &bytes)
+        crate::utilities::bytes_to_value_string(data_preview, &bytes).unwrap_or_default()
     }
 }
@@ -0,0 +1,564 @@
+//! Tracking of the writes made through the editor, which can be exported as a patch.
+//!
+//! Every write performed by the UI is recorded in an [`EditHistory`], which can be retrieved with
+//! [`crate::MemoryEditor::edit_history`].
+//! The history can be exported as a plain `address: value` list, or, with the `patches` feature enabled, as an
+//! `IPS`/`BPS` patch for ROM-hacking workflows.
+//!
+//! Conversely, a [`Patch`] can be handed to [`crate::MemoryEditor::set_pending_patch`], after which the UI will show
+//! a preview of all affected addresses before applying it through the `write_fn`.
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::task::ChunkedTask;
+use crate::Address;
+
+/// A single write made through the editor.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct WriteRecord {
+    /// The address that was written to.
+    pub address: Address,
+    /// The value at `address` before the write, if the `read_fn` could provide it.
+    pub previous: Option<u8>,
+    /// The value that was written.
+    pub value: u8,
+}
+
+/// The collection of all writes made through the editor during this session, in the order they were made.
+///
+/// Writes made by a single operation, such as applying a patch, form one group which is undone as a whole.
+#[derive(Clone, Debug, Default)]
+pub struct EditHistory {
+    writes: Vec<WriteRecord>,
+    /// The index in `writes` at which every group starts.
+    group_starts: Vec<usize>,
+    /// How many operations are grouping their writes, new writes are added to the last group while above zero.
+    group_depth: usize,
+}
+
+impl EditHistory {
+    /// All writes in the order they were made.
+    pub fn writes(&self) -> &[WriteRecord] {
+        &self.writes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    /// The amount of writes made, writing the same address twice counts twice.
+    pub fn len(&self) -> usize {
+        self.writes.len()
+    }
+
+    /// The writes grouped by the operation which made them, a typed value is one group while a paste is another.
+    pub fn groups(&self) -> impl Iterator<Item = &[WriteRecord]> {
+        let ends = self.group_starts.iter().skip(1).copied().chain([self.writes.len()]);
+        self.group_starts
+            .iter()
+            .zip(ends)
+            .map(|(&start, end)| &self.writes[start..end])
+    }
+
+    /// Forget all recorded writes.
+    pub fn clear(&mut self) {
+        self.writes.clear();
+        self.group_starts.clear();
+    }
+
+    /// Add all writes recorded until [`Self::end_group`] to a single group.
+    ///
+    /// Groups can be nested, in which case the outermost group contains all writes.
+    pub(crate) fn begin_group(&mut self) {
+        if self.group_depth == 0 {
+            self.group_starts.push(self.writes.len());
+        }
+        self.group_depth += 1;
+    }
+
+    pub(crate) fn end_group(&mut self) {
+        self.group_depth = self.group_depth.saturating_sub(1);
+        // Operations which didn't write anything shouldn't leave an empty group to undo.
+        if self.group_depth == 0 && self.group_starts.last() == Some(&self.writes.len()) {
+            self.group_starts.pop();
+        }
+    }
+
+    /// Remove the last group of writes, returning them in the order they were made.
+    pub(crate) fn pop_group(&mut self) -> Vec<WriteRecord> {
+        let start = self.group_starts.pop().unwrap_or_default();
+        self.writes.split_off(start)
+    }
+
+    pub(crate) fn record(&mut self, address: Address, previous: Option<u8>, value: u8) {
+        if self.group_depth == 0 {
+            self.group_starts.push(self.writes.len());
+        }
+        self.writes.push(WriteRecord {
+            address,
+            previous,
+            value,
+        });
+    }
+
+    /// The net result of all writes, mapping every written address to the last value written to it.
+    pub fn changes(&self) -> BTreeMap<Address, u8> {
+        self.writes.iter().map(|write| (write.address, write.value)).collect()
+    }
+
+    /// Export the net changes as a newline separated `0xADDRESS: VALUE` list, e.g. `0xFF05: 3A`.
+    pub fn to_address_value_list(&self) -> String {
+        self.changes()
+            .into_iter()
+            .map(|(address, value)| format!("{:#X}: {:02X}\n", address, value))
+            .collect()
+    }
+
+    /// Group the net changes into runs of consecutive addresses, returned as `(start_address, bytes)`.
+    #[cfg(feature = "patches")]
+    fn changed_runs(&self) -> Vec<(Address, Vec<u8>)> {
+        let mut runs: Vec<(Address, Vec<u8>)> = Vec::new();
+
+        for (address, value) in self.changes() {
+            match runs.last_mut() {
+                Some((start, bytes)) if *start + bytes.len() == address => bytes.push(value),
+                _ => runs.push((address, vec![value])),
+            }
+        }
+
+        runs
+    }
+}
+
+/// A set of bytes to write, keyed by their offset relative to some base address.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Patch {
+    changes: BTreeMap<usize, u8>,
+}
+
+impl Patch {
+    /// Create a patch from a map of offsets to the values to write.
+    pub fn from_changes(changes: BTreeMap<usize, u8>) -> Self {
+        Patch { changes }
+    }
+
+    /// All offsets the patch writes to, with the values to write.
+    pub fn changes(&self) -> &BTreeMap<usize, u8> {
+        &self.changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The amount of bytes the patch writes.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+}
+
+/// A patch which has been mapped onto the address space, waiting for the user to apply or discard it.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PendingPatch {
+    /// The changes ordered by address.
+    pub changes: Vec<(Address, u8)>,
+    /// The addresses which would change, as `(address, current value, new value)`.
+    /// Computed over multiple frames by `task` with the `read_fn`.
+    pub differences: Vec<(Address, Option<u8>, u8)>,
+    /// Goes through the indices of `changes`, as a sparse patch can span far more addresses than it changes.
+    pub task: Option<ChunkedTask>,
+}
+
+impl PendingPatch {
+    pub fn new(patch: Patch, base: Address) -> Self {
+        let changes: Vec<(Address, u8)> = patch
+            .changes
+            .into_iter()
+            .filter_map(|(offset, value)| Some((base.checked_add(offset)?, value)))
+            .collect();
+
+        PendingPatch {
+            task: Some(ChunkedTask::new(0..changes.len())),
+            changes,
+            differences: Vec::new(),
+        }
+    }
+
+    /// Compute the differences with the current memory for the next chunk of the patch.
+    pub fn step<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        budget: usize,
+    ) {
+        let Some(task) = self.task.as_mut() else {
+            return;
+        };
+        let chunk = task.next_chunk(budget);
+        let is_finished = task.is_finished();
+
+        if let Some(chunk) = chunk {
+            self.collect_differences(chunk, |address| read_fn(mem, address));
+        }
+
+        if is_finished {
+            self.task = None;
+        }
+    }
+
+    /// Compute all remaining differences at once, see [`crate::MemoryEditor::complete_tasks_with_slice`].
+    pub fn complete_with_slice(&mut self, memory: &[u8], base: Address) {
+        if let Some(mut task) = self.task.take() {
+            self.collect_differences(task.take_remaining(), |address| {
+                crate::scan::read_slice(memory, base, address)
+            });
+        }
+    }
+
+    /// Compare the changes at the indices in `chunk` with the current memory.
+    fn collect_differences(&mut self, chunk: Range<usize>, mut read: impl FnMut(Address) -> Option<u8>) {
+        self.differences.extend(
+            self.changes[chunk]
+                .iter()
+                .map(|&(address, value)| (address, read(address), value))
+                .filter(|&(_, current, value)| current != Some(value)),
+        );
+    }
+
+    #[inline]
+    pub fn contains(&self, address: Address) -> bool {
+        self.changes
+            .binary_search_by_key(&address, |&(address, _)| address)
+            .is_ok()
+    }
+}
+
+#[cfg(feature = "patches")]
+pub use formats::*;
+
+#[cfg(feature = "patches")]
+mod formats {
+    use std::collections::BTreeMap;
+
+    use super::{EditHistory, Patch};
+    use crate::Address;
+
+    const IPS_HEADER: &[u8] = b"PATCH";
+    const IPS_FOOTER: &[u8] = b"EOF";
+    /// An `IPS` record starting at this offset would be read as the footer.
+    const IPS_EOF_OFFSET: usize = 0x454F46;
+    const IPS_MAX_OFFSET: usize = 0xFF_FFFF;
+    const IPS_MAX_RECORD_SIZE: usize = 0xFFFF;
+
+    const BPS_HEADER: &[u8] = b"BPS1";
+    const BPS_SOURCE_READ: usize = 0;
+    const BPS_TARGET_READ: usize = 1;
+    const BPS_SOURCE_COPY: usize = 2;
+    /// Three `CRC-32`s
+    const BPS_FOOTER_SIZE: usize = 12;
+
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    pub enum PatchError {
+        /// A change lies below the provided base address.
+        BeforeBase(Address),
+        /// A change lies beyond the maximum offset the format can express.
+        OffsetTooLarge(Address),
+        /// An `IPS` record would have to start at offset `0x454F46`, which every reader interprets as the footer.
+        IpsEofOffset,
+        /// The provided data doesn't start with the expected magic bytes.
+        InvalidHeader,
+        /// The patch ended before all its records could be read.
+        UnexpectedEnd,
+        /// A `BPS` patch read outside the bounds of the source or target.
+        OutOfBounds,
+        /// A number in the `BPS` patch doesn't fit in a `usize`.
+        NumberTooLarge,
+        /// The `BPS` patch itself is corrupt.
+        PatchChecksumMismatch,
+        /// The `BPS` patch was made for a different source file.
+        SourceChecksumMismatch,
+        /// Applying the `BPS` patch didn't result in the expected target file.
+        TargetChecksumMismatch,
+    }
+
+    impl std::fmt::Display for PatchError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                PatchError::BeforeBase(address) => write!(f, "address {:#X} lies before the patch base", address),
+                PatchError::OffsetTooLarge(address) => write!(f, "address {:#X} is too large for the format", address),
+                PatchError::IpsEofOffset => write!(f, "an IPS record can't start at offset 0x454F46"),
+                PatchError::InvalidHeader => write!(f, "not a valid patch file"),
+                PatchError::UnexpectedEnd => write!(f, "the patch ended unexpectedly"),
+                PatchError::OutOfBounds => write!(f, "the patch read outside the source or target"),
+                PatchError::NumberTooLarge => write!(f, "the patch contains a number which is too large"),
+                PatchError::PatchChecksumMismatch => write!(f, "the patch is corrupt"),
+                PatchError::SourceChecksumMismatch => write!(f, "the patch was made for a different source"),
+                PatchError::TargetChecksumMismatch => write!(f, "the patched result doesn't match the expected target"),
+            }
+        }
+    }
+
+    impl std::error::Error for PatchError {}
+
+    impl EditHistory {
+        /// Export the net changes as an `IPS` patch.
+        ///
+        /// File offsets in the patch are relative to `base`, so if your file is mapped at `0x8000` you'd pass `0x8000`.
+        pub fn to_ips(&self, base: Address) -> Result<Vec<u8>, PatchError> {
+            let mut patch = IPS_HEADER.to_vec();
+
+            for (address, bytes) in self.changed_runs() {
+                let start = address.checked_sub(base).ok_or(PatchError::BeforeBase(address))?;
+
+                for (i, chunk) in bytes.chunks(IPS_MAX_RECORD_SIZE).enumerate() {
+                    let offset = start + i * IPS_MAX_RECORD_SIZE;
+
+                    if offset > IPS_MAX_OFFSET {
+                        return Err(PatchError::OffsetTooLarge(base + offset));
+                    }
+                    if offset == IPS_EOF_OFFSET {
+                        return Err(PatchError::IpsEofOffset);
+                    }
+
+                    patch.extend_from_slice(&(offset as u32).to_be_bytes()[1..]);
+                    patch.extend_from_slice(&(chunk.len() as u16).to_be_bytes());
+                    patch.extend_from_slice(chunk);
+                }
+            }
+
+            patch.extend_from_slice(IPS_FOOTER);
+            Ok(patch)
+        }
+
+        /// Export the net changes as a `BPS` patch against the provided `source` file.
+        ///
+        /// File offsets are relative to `base`, the target file is `source` with all changes applied. Changes beyond
+        /// the end of `source` will grow the target, with any gap filled by `0x00`.
+        pub fn to_bps(&self, source: &[u8], base: Address) -> Result<Vec<u8>, PatchError> {
+            let mut target = source.to_vec();
+
+            for (address, value) in self.changes() {
+                let offset = address.checked_sub(base).ok_or(PatchError::BeforeBase(address))?;
+
+                if offset >= target.len() {
+                    target.resize(offset + 1, 0);
+                }
+                target[offset] = value;
+            }
+
+            let mut patch = BPS_HEADER.to_vec();
+            encode_bps_number(&mut patch, source.len());
+            encode_bps_number(&mut patch, target.len());
+            // No metadata
+            encode_bps_number(&mut patch, 0);
+
+            let mut offset = 0;
+            while offset < target.len() {
+                let is_unchanged = |i: usize| source.get(i) == Some(&target[i]);
+                let unchanged = is_unchanged(offset);
+                let length = (offset..target.len())
+                    .take_while(|&i| is_unchanged(i) == unchanged)
+                    .count();
+
+                if unchanged {
+                    encode_bps_number(&mut patch, ((length - 1) << 2) | BPS_SOURCE_READ);
+                } else {
+                    encode_bps_number(&mut patch, ((length - 1) << 2) | BPS_TARGET_READ);
+                    patch.extend_from_slice(&target[offset..offset + length]);
+                }
+
+                offset += length;
+            }
+
+            patch.extend_from_slice(&crate::utilities::crc32(source).to_le_bytes());
+            patch.extend_from_slice(&crate::utilities::crc32(&target).to_le_bytes());
+            let patch_crc = crate::utilities::crc32(&patch);
+            patch.extend_from_slice(&patch_crc.to_le_bytes());
+
+            Ok(patch)
+        }
+    }
+
+    impl Patch {
+        /// Parse an `IPS` patch, including run-length encoded records.
+        pub fn from_ips(data: &[u8]) -> Result<Self, PatchError> {
+            let mut reader = data.strip_prefix(IPS_HEADER).ok_or(PatchError::InvalidHeader)?;
+            let mut changes = BTreeMap::new();
+
+            loop {
+                if reader.starts_with(IPS_FOOTER) {
+                    break;
+                }
+
+                let offset = usize::from_be_bytes(pad_be(take(&mut reader, 3)?));
+                let size = usize::from_be_bytes(pad_be(take(&mut reader, 2)?));
+
+                if size == 0 {
+                    let run_length = usize::from_be_bytes(pad_be(take(&mut reader, 2)?));
+                    let value = take(&mut reader, 1)?[0];
+                    changes.extend((offset..offset + run_length).map(|offset| (offset, value)));
+                } else {
+                    let bytes = take(&mut reader, size)?;
+                    changes.extend(bytes.iter().enumerate().map(|(i, &value)| (offset + i, value)));
+                }
+            }
+
+            Ok(Patch { changes })
+        }
+
+        /// Parse a `BPS` patch made for `source`, the patch checksums are verified.
+        ///
+        /// The resulting [`Patch`] only contains the bytes which differ from `source`.
+        pub fn from_bps(data: &[u8], source: &[u8]) -> Result<Self, PatchError> {
+            use crate::utilities::crc32;
+
+            if !data.starts_with(BPS_HEADER) {
+                return Err(PatchError::InvalidHeader);
+            }
+            if data.len() < BPS_HEADER.len() + BPS_FOOTER_SIZE {
+                return Err(PatchError::UnexpectedEnd);
+            }
+
+            let (body, footer) = data.split_at(data.len() - BPS_FOOTER_SIZE);
+            let read_crc = |i: usize| u32::from_le_bytes(footer[i * 4..i * 4 + 4].try_into().unwrap());
+
+            if crc32(&data[..data.len() - 4]) != read_crc(2) {
+                return Err(PatchError::PatchChecksumMismatch);
+            }
+            if crc32(source) != read_crc(0) {
+                return Err(PatchError::SourceChecksumMismatch);
+            }
+
+            let mut reader = &body[BPS_HEADER.len()..];
+            let source_size = decode_bps_number(&mut reader)?;
+            let target_size = decode_bps_number(&mut reader)?;
+            let metadata_size = decode_bps_number(&mut reader)?;
+            take(&mut reader, metadata_size)?;
+
+            if source_size != source.len() {
+                return Err(PatchError::SourceChecksumMismatch);
+            }
+
+            // The declared size can't be trusted before the checksum of the target is verified, while every target
+            // byte is either read from the source or the patch, or copied from earlier bytes.
+            let mut target = Vec::with_capacity(target_size.min(source.len().saturating_add(data.len())));
+            let mut source_offset: isize = 0;
+            let mut target_offset: isize = 0;
+
+            while !reader.is_empty() {
+                let action = decode_bps_number(&mut reader)?;
+                let length = (action >> 2) + 1;
+
+                // Actions writing past the declared size would never result in the expected target.
+                if target.len().checked_add(length).is_none_or(|end| end > target_size) {
+                    return Err(PatchError::OutOfBounds);
+                }
+
+                match action & 3 {
+                    BPS_SOURCE_READ => {
+                        let start = target.len();
+                        let bytes = source.get(start..start + length).ok_or(PatchError::OutOfBounds)?;
+                        target.extend_from_slice(bytes);
+                    }
+                    BPS_TARGET_READ => target.extend_from_slice(take(&mut reader, length)?),
+                    BPS_SOURCE_COPY => {
+                        source_offset = source_offset
+                            .checked_add(decode_bps_offset(&mut reader)?)
+                            .ok_or(PatchError::OutOfBounds)?;
+                        let start = usize::try_from(source_offset).map_err(|_| PatchError::OutOfBounds)?;
+                        let bytes = start
+                            .checked_add(length)
+                            .and_then(|end| source.get(start..end))
+                            .ok_or(PatchError::OutOfBounds)?;
+                        target.extend_from_slice(bytes);
+                        // Fits, as `start + length` lies within the source.
+                        source_offset += length as isize;
+                    }
+                    // Target copy
+                    _ => {
+                        target_offset = target_offset
+                            .checked_add(decode_bps_offset(&mut reader)?)
+                            .ok_or(PatchError::OutOfBounds)?;
+                        // The copied range may overlap with the bytes being written, so copy byte-by-byte.
+                        for _ in 0..length {
+                            let index = usize::try_from(target_offset).map_err(|_| PatchError::OutOfBounds)?;
+                            let byte = *target.get(index).ok_or(PatchError::OutOfBounds)?;
+                            target.push(byte);
+                            target_offset += 1;
+                        }
+                    }
+                }
+            }
+
+            if target.len() != target_size || crc32(&target) != read_crc(1) {
+                return Err(PatchError::TargetChecksumMismatch);
+            }
+
+            let changes = target
+                .into_iter()
+                .enumerate()
+                .filter(|&(i, value)| source.get(i) != Some(&value))
+                .collect();
+
+            Ok(Patch { changes })
+        }
+    }
+
+    /// Take `count` bytes from the front of `reader`.
+    fn take<'a>(reader: &mut &'a [u8], count: usize) -> Result<&'a [u8], PatchError> {
+        if reader.len() < count {
+            return Err(PatchError::UnexpectedEnd);
+        }
+        let (taken, rest) = reader.split_at(count);
+        *reader = rest;
+        Ok(taken)
+    }
+
+    /// Left-pad a big-endian number to the size of a `usize`.
+    fn pad_be(bytes: &[u8]) -> [u8; std::mem::size_of::<usize>()] {
+        let mut result = [0; std::mem::size_of::<usize>()];
+        result[std::mem::size_of::<usize>() - bytes.len()..].copy_from_slice(bytes);
+        result
+    }
+
+    fn decode_bps_number(reader: &mut &[u8]) -> Result<usize, PatchError> {
+        let mut number: usize = 0;
+        let mut shift: usize = 1;
+
+        loop {
+            let byte = take(reader, 1)?[0] as usize;
+            number = (byte & 0x7F)
+                .checked_mul(shift)
+                .and_then(|value| number.checked_add(value))
+                .ok_or(PatchError::NumberTooLarge)?;
+
+            if byte & 0x80 != 0 {
+                return Ok(number);
+            }
+
+            // `checked_shl` only fails for shifts beyond the bit width, not for bits shifted out.
+            shift = shift.checked_mul(0x80).ok_or(PatchError::NumberTooLarge)?;
+            number = number.checked_add(shift).ok_or(PatchError::NumberTooLarge)?;
+        }
+    }
+
+    /// Relative offsets are stored with their sign in the lowest bit.
+    fn decode_bps_offset(reader: &mut &[u8]) -> Result<isize, PatchError> {
+        let number = decode_bps_number(reader)?;
+        let magnitude = (number >> 1) as isize;
+        Ok(if number & 1 == 1 { -magnitude } else { magnitude })
+    }
+
+    fn encode_bps_number(output: &mut Vec<u8>, mut number: usize) {
+        loop {
+            let low = (number & 0x7F) as u8;
+            number >>= 7;
+            if number == 0 {
+                output.push(0x80 | low);
+                break;
+            }
+            output.push(low);
+            number -= 1;
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Numbered marks for quickly jumping between a few addresses, independent of the bookmarks in the `Locations`.
+use std::ops::Range;
+
+use egui::{Context, Event, Key};
+
+use crate::{Address, MemoryEditor};
+
+/// The amount of quick marks, numbered `1` up to and including this.
+pub(crate) const QUICK_MARK_COUNT: usize = 9;
+
+/// The number of the quick mark for a digit key, `None` for other keys and `0`.
+fn quick_mark_number(key: Key) -> Option<usize> {
+    let number = match key {
+        Key::Num1 => 1,
+        Key::Num2 => 2,
+        Key::Num3 => 3,
+        Key::Num4 => 4,
+        Key::Num5 => 5,
+        Key::Num6 => 6,
+        Key::Num7 => 7,
+        Key::Num8 => 8,
+        Key::Num9 => 9,
+        _ => return None,
+    };
+
+    Some(number)
+}
+
+impl MemoryEditor {
+    /// Returns the region and address of quick mark `number`, from `1` to `9`, if it's set.
+    ///
+    /// Quick marks are set on the selected address with `Ctrl+Shift+1..9`, and jumped back to with `Ctrl+1..9`.
+    pub fn quick_mark(&self, number: usize) -> Option<(&str, Address)> {
+        let (region, address) = self.quick_marks.get(number.checked_sub(1)?)?.as_ref()?;
+        Some((region, *address))
+    }
+
+    /// Set quick mark `number`, from `1` to `9`, to `address` in the current region, or clear it with `None`.
+    pub fn set_quick_mark(&mut self, number: usize, address: Option<Address>) {
+        let Some(mark) = number.checked_sub(1).and_then(|index| self.quick_marks.get_mut(index)) else {
+            return;
+        };
+
+        *mark = address.map(|address| (self.options.selected_address_range.clone(), address));
+    }
+
+    /// Set a quick mark on `Ctrl+Shift+1..9`, or go to it on `Ctrl+1..9`.
+    ///
+    /// The digits are matched on their physical key as well, as `Shift` changes them into symbols on most layouts.
+    pub(crate) fn handle_quick_mark_shortcuts(&mut self, ctx: &Context, address_space: &Range<Address>) {
+        // Other text fields keep their keys, the value being edited doesn't take digits with `Ctrl` though.
+        if ctx.wants_keyboard_input() && self.frame_data.view.selected_edit_address.is_none() {
+            return;
+        }
+
+        let shortcut = ctx.input_mut(|i| {
+            let mut shortcut = None;
+            i.events.retain(|event| {
+                let Event::Key {
+                    key,
+                    physical_key,
+                    pressed: true,
+                    modifiers,
+                    ..
+                } = event
+                else {
+                    return true;
+                };
+                let number = quick_mark_number(*key).or_else(|| physical_key.and_then(quick_mark_number));
+
+                match number {
+                    Some(number) if modifiers.command && shortcut.is_none() => {
+                        shortcut = Some((number, modifiers.shift));
+                        false
+                    }
+                    _ => true,
+                }
+            });
+            shortcut
+        });
+
+        match shortcut {
+            Some((number, true)) => {
+                let cursor = self
+                    .frame_data
+                    .view
+                    .selected_edit_address
+                    .or(self.frame_data.view.selected_highlight_address)
+                    .unwrap_or(self.visible.addresses.start.max(address_space.start));
+                self.set_quick_mark(number, Some(cursor));
+            }
+            Some((number, false)) => {
+                let Some((region, address)) = self
+                    .quick_mark(number)
+                    .map(|(region, address)| (region.to_string(), address))
+                else {
+                    return;
+                };
+
+                if region == self.options.selected_address_range {
+                    self.frame_data
+                        .set_goto_address(address, address_space, self.column_count());
+                } else if self.set_selected_address_range(&region) {
+                    self.queue_goto(address);
+                }
+            }
+            None => {}
+        }
+    }
+}
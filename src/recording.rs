@@ -0,0 +1,150 @@
+//! Recording of the user's interactions with the editor, which can be exported and replayed.
+//!
+//! Start a recording with [`crate::MemoryEditor::start_recording`], after which every goto, selection, and write made
+//! through the UI is stored with a timestamp. The resulting [`Recording`] can be replayed action by action with
+//! [`crate::MemoryEditor::replay_action`], which is useful for regression tests of host integrations or reproducing
+//! bugs.
+use std::ops::Range;
+
+use crate::{Address, MemoryEditor};
+
+/// A single interaction of the user with the editor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UserAction {
+    /// Jumped to `address` in `region`.
+    Goto { region: String, address: Address },
+    /// Changed the selection, `None` when the selection was cleared.
+    Select { addresses: Option<Range<Address>> },
+    /// Wrote `value` to `address`.
+    Write { address: Address, value: u8 },
+}
+
+/// A [`UserAction`] with the moment it happened.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedAction {
+    /// The amount of seconds since the start of the recording.
+    pub time: f64,
+    pub action: UserAction,
+}
+
+/// All actions recorded in a session, in the order they happened.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Recording {
+    pub actions: Vec<RecordedAction>,
+}
+
+/// The state of a running recording.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Recorder {
+    /// The time of the first frame of the recording, as given by egui, `None` until the first frame is drawn.
+    start_time: Option<f64>,
+    /// The time of the current frame.
+    frame_time: f64,
+    previous_selection: Option<Range<Address>>,
+    recording: Recording,
+}
+
+impl Recorder {
+    fn push(&mut self, action: UserAction) {
+        let time = self.frame_time - self.start_time.unwrap_or(self.frame_time);
+        self.recording.actions.push(RecordedAction { time, action });
+    }
+}
+
+impl MemoryEditor {
+    /// Start recording the user's interactions, discarding any previous unfinished recording.
+    pub fn start_recording(&mut self) {
+        self.recorder = Some(Recorder {
+            previous_selection: self.frame_data.selection(),
+            ..Default::default()
+        });
+    }
+
+    /// Stop the running recording and return it, if there was one.
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        self.recorder.take().map(|recorder| recorder.recording)
+    }
+
+    /// Returns the running recording, if there is one.
+    pub fn recording(&self) -> Option<&Recording> {
+        self.recorder.as_ref().map(|recorder| &recorder.recording)
+    }
+
+    /// Perform a recorded action as if the user did it.
+    ///
+    /// Gotos and selections take effect on the next frame, writes are performed during the next frame which has a
+    /// `write_fn`, and go through the same checks as writes made in the UI.
+    pub fn replay_action(&mut self, action: &UserAction) {
+        match action {
+            UserAction::Goto { region, address } => {
+                if self.address_ranges.contains_key(region) {
+                    self.options.selected_address_range = region.clone();
+                }
+                self.queue_goto(*address);
+            }
+            UserAction::Select { addresses } => {
+                let frame_data = &mut self.frame_data;
+                frame_data.view.is_goto_highlight = false;
+                frame_data.view.selected_highlight_address = addresses.as_ref().map(|addresses| addresses.start);
+                frame_data.view.selection_end = addresses
+                    .as_ref()
+                    .filter(|addresses| addresses.len() > 1)
+                    .map(|addresses| addresses.end - 1);
+            }
+            UserAction::Write { address, value } => self.frame_data.replayed_writes.push((*address, *value)),
+        }
+    }
+
+    /// Perform all writes queued by [`Self::replay_action`].
+    pub(crate) fn perform_replayed_writes<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        write_fn: &mut impl FnMut(&mut T, Address, u8),
+    ) {
+        for (address, value) in std::mem::take(&mut self.frame_data.replayed_writes) {
+            self.write_value(mem, read_fn, write_fn, address, value);
+        }
+    }
+
+    /// Set the time of the current frame, which is used for the timestamps of the recorded actions.
+    pub(crate) fn start_recorder_frame(&mut self, time: f64) {
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.frame_time = time;
+            recorder.start_time.get_or_insert(time);
+        }
+    }
+
+    /// Record the gotos and selection changes made during this frame.
+    pub(crate) fn finish_recorder_frame(&mut self) {
+        let goto = self.frame_data.view.last_goto.take();
+        let selection = self.frame_data.selection();
+        let Some(recorder) = self.recorder.as_mut() else {
+            return;
+        };
+
+        if let Some(address) = goto {
+            recorder.push(UserAction::Goto {
+                region: self.options.selected_address_range.clone(),
+                address,
+            });
+        } else if recorder.previous_selection != selection {
+            recorder.push(UserAction::Select {
+                addresses: selection.clone(),
+            });
+        }
+
+        recorder.previous_selection = selection;
+    }
+
+    /// Record a write made through the UI.
+    #[inline]
+    pub(crate) fn record_write(&mut self, address: Address, value: u8) {
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.push(UserAction::Write { address, value });
+        }
+    }
+}
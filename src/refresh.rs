@@ -0,0 +1,99 @@
+//! Throttling of the reads of the visible memory, see [`crate::option_data::MemoryEditorOptions::refresh_interval`].
+use std::collections::HashMap;
+use std::ops::Range;
+use std::time::Duration;
+
+use crate::{Address, MemoryEditor};
+
+/// The values of the visible addresses as of the last refresh, which are shown until the next one.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RefreshCache {
+    interval: Option<Duration>,
+    /// The `egui` time of the last refresh.
+    last_refresh: Option<f64>,
+    /// Only the addresses in this range are cached, such that long running tasks always read the current memory.
+    addresses: Range<Address>,
+    values: HashMap<Address, Option<u8>>,
+    /// Incremented whenever cached values are forgotten, such that derived caches know when to read again.
+    generation: u64,
+}
+
+impl RefreshCache {
+    /// Prepare the cache for a new frame at `time`, forgetting all values if the next refresh is due.
+    ///
+    /// Returns the time until the next refresh, if refreshes are throttled.
+    pub fn start_frame(&mut self, time: f64, interval: Option<Duration>, visible: &Range<Address>) -> Option<Duration> {
+        self.interval = interval;
+        let Some(interval) = interval else {
+            self.values.clear();
+            self.last_refresh = None;
+            return None;
+        };
+
+        // Newly visible addresses are read once and then cached as well, so scrolling doesn't read every frame.
+        if self.addresses != *visible {
+            self.addresses = visible.clone();
+            self.values.retain(|address, _| visible.contains(address));
+            self.generation += 1;
+        }
+
+        let elapsed = self
+            .last_refresh
+            .map_or(f64::INFINITY, |last_refresh| time - last_refresh);
+        if elapsed >= interval.as_secs_f64() {
+            self.values.clear();
+            self.generation += 1;
+            self.last_refresh = Some(time);
+            Some(interval)
+        } else {
+            Some(interval.saturating_sub(Duration::from_secs_f64(elapsed)))
+        }
+    }
+
+    /// Identifies the currently cached values, `None` if refreshes aren't throttled and every read is live.
+    ///
+    /// As long as this stays the same, reading the cached addresses again returns the same values.
+    pub fn generation(&self) -> Option<u64> {
+        self.interval.map(|_| self.generation)
+    }
+
+    pub fn read<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        address: Address,
+    ) -> Option<u8> {
+        if self.interval.is_none() || !self.addresses.contains(&address) {
+            return read_fn(mem, address);
+        }
+
+        *self.values.entry(address).or_insert_with(|| read_fn(mem, address))
+    }
+
+    /// Forget the value of `address`, such that a write to it is visible immediately.
+    pub fn invalidate(&mut self, address: Address) {
+        self.values.remove(&address);
+        self.generation += 1;
+    }
+
+    pub fn invalidate_range(&mut self, addresses: Range<Address>) {
+        self.generation += 1;
+        if addresses.len() < self.values.len() {
+            addresses.for_each(|address| self.invalidate(address));
+        } else {
+            self.values.retain(|address, _| !addresses.contains(address));
+        }
+    }
+}
+
+impl MemoryEditor {
+    /// Re-read `addresses` in the next frame, even if the next refresh isn't due yet.
+    ///
+    /// Hosts which know exactly which memory changed, such as an emulator with write hooks, can use this to keep the
+    /// editor up to date without frequent refreshes. Combine with a long
+    /// [`crate::option_data::MemoryEditorOptions::refresh_interval`], such as [`Duration::MAX`], to only read the
+    /// memory when it changes.
+    pub fn invalidate_range(&mut self, addresses: Range<Address>) {
+        self.refresh.invalidate_range(addresses);
+    }
+}
@@ -0,0 +1,26 @@
+//! The per-frame response of the editor, reporting interactions the host might want to act on.
+use std::ops::Range;
+
+use egui::DroppedFile;
+
+use crate::Address;
+
+/// Information about the interactions with the editor in a single frame.
+///
+/// Returned by all methods which draw the editor, such as [`crate::MemoryEditor::window_ui`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EditorResponse {
+    /// The start addresses of the rows whose marker was toggled in the marker gutter this frame.
+    ///
+    /// See [`crate::option_data::MemoryEditorOptions::show_marker_gutter`].
+    pub toggled_markers: Vec<Address>,
+    /// The new visible range if it changed this frame, due to scrolling, resizing, or switching regions.
+    ///
+    /// Useful to synchronise other views with the editor, see also [`crate::MemoryEditor::visible_range_details`].
+    pub visible_range_changed: Option<Range<Address>>,
+    /// The files dropped onto the editor this frame, which can be read with [`crate::read_dropped_file`].
+    ///
+    /// Only filled if [`crate::option_data::MemoryEditorOptions::accept_dropped_files`] is enabled.
+    pub dropped_files: Vec<DroppedFile>,
+}
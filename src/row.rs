@@ -0,0 +1,221 @@
+//! A single row of memory outside of the editor, with the editor's formatting.
+use std::ops::Range;
+
+use egui::{Color32, FontId, Response, RichText, Style, TextWrapMode, Ui, Widget, WidgetText};
+
+use crate::ascii::ascii_character;
+use crate::layout::{ASCII_SEPARATOR_SPACING, CELL_SPACING, GRID_COLUMN_SPACING};
+use crate::option_data::{CellFormat, LayoutDirection, MemoryEditorOptions};
+use crate::Address;
+
+/// The text of the address column for the row starting at `address`, padded to `characters` hex digits.
+///
+/// With a `base` the address is shown as its signed offset from it, see [`MemoryEditorOptions::display_base`].
+pub(crate) fn address_text(address: Address, characters: usize, base: Option<Address>) -> String {
+    format!("{}:", copied_address_text(address, characters, base))
+}
+
+/// The text copied for `address`, formatted like [`address_text`] without the trailing colon.
+pub(crate) fn copied_address_text(address: Address, characters: usize, base: Option<Address>) -> String {
+    let format = |address| crate::utilities::format_address(address, characters);
+
+    match base {
+        Some(base) if address >= base => format!("+{}", format(address - base)),
+        Some(base) => format!("-{}", format(base - address)),
+        None => format(address),
+    }
+}
+
+/// The text of one cell of a value, unreadable values are shown as [`MemoryEditorOptions::none_display_value`].
+pub(crate) fn value_text(options: &MemoryEditorOptions, value: Option<u8>, sub_cell: usize) -> String {
+    match value {
+        Some(value) => options.cell_format.format(value, sub_cell),
+        None if options.cell_format.sub_cells() > 1 => options.none_display_value.chars().take(1).collect(),
+        None => options.none_display_value.clone(),
+    }
+}
+
+/// The colour of a value which isn't highlighted, see [`MemoryEditorOptions::show_zero_colour`].
+pub(crate) fn value_colour(options: &MemoryEditorOptions, value: Option<u8>, style: &Style) -> Color32 {
+    if options.show_zero_colour && value.is_none_or(|value| value == 0) {
+        options.zero_colour
+    } else {
+        style.visuals.text_color()
+    }
+}
+
+/// The text of one cell of a value before any highlighting, in `font` and the colour of [`value_colour`].
+pub(crate) fn value_cell_text(
+    options: &MemoryEditorOptions,
+    value: Option<u8>,
+    sub_cell: usize,
+    font: &FontId,
+    style: &Style,
+) -> RichText {
+    RichText::new(value_text(options, value, sub_cell))
+        .font(font.clone())
+        .color(value_colour(options, value, style))
+}
+
+/// Lay out the cells of a single value next to each other, calling `draw_cell` for every one of them.
+///
+/// Depending on the format a byte can be split over multiple cells, such as one per nibble.
+pub(crate) fn value_cells(ui: &mut Ui, cell_format: CellFormat, mut draw_cell: impl FnMut(&mut Ui, usize)) {
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = cell_format.sub_cell_spacing();
+
+        for sub_cell in 0..cell_format.sub_cells() {
+            draw_cell(ui, sub_cell);
+        }
+    });
+}
+
+/// The text of a row as it's rendered, such as `0xFF00: 12 34 | .4`, for copying it to the clipboard.
+///
+/// Values are separated by a space and groups by two, the ASCII representation is only included if `show_ascii` is set.
+/// With [`LayoutDirection::RightToLeft`] the columns are in the mirrored order, such as `.4 | 12 34 0xFF00:`.
+pub(crate) fn row_text(
+    options: &MemoryEditorOptions,
+    start_address: Address,
+    address_characters: usize,
+    values: &[Option<u8>],
+    show_ascii: bool,
+) -> String {
+    let cell_format = options.cell_format;
+    let groups: Vec<String> = values
+        .chunks(cell_format.bytes_per_group())
+        .map(|group| {
+            let cells: Vec<String> = group
+                .iter()
+                .map(|&value| {
+                    (0..cell_format.sub_cells())
+                        .map(|sub_cell| value_text(options, value, sub_cell))
+                        .collect()
+                })
+                .collect();
+            cells.join(" ")
+        })
+        .collect();
+
+    let address = address_text(start_address, address_characters, options.display_base);
+    let values_text = groups.join("  ");
+    let ascii: Option<String> =
+        show_ascii.then(|| values.iter().map(|value| ascii_character(value.unwrap_or(0))).collect());
+
+    match (options.layout_direction, ascii) {
+        (LayoutDirection::LeftToRight, None) => format!("{} {}", address, values_text),
+        (LayoutDirection::LeftToRight, Some(ascii)) => format!("{} {} | {}", address, values_text, ascii),
+        (LayoutDirection::RightToLeft, None) => format!("{} {}", values_text, address),
+        (LayoutDirection::RightToLeft, Some(ascii)) => format!("{} | {} {}", ascii, values_text, address),
+    }
+}
+
+/// A read-only row of memory formatted like the rows of a [`crate::MemoryEditor`], for showing a few bytes elsewhere,
+/// such as the memory at a crash site inside a log.
+///
+/// The address, the values and the ASCII sidebar follow the cell format, fonts, colours and
+/// [`MemoryEditorOptions::show_ascii`] of the provided options.
+///
+/// ```no_run
+/// # use egui_memory_editor::{MemoryEditor, MemoryRow};
+/// # let editor = MemoryEditor::new();
+/// # let ui: &mut egui::Ui = todo!();
+/// let bytes = [0xDE, 0xAD, 0xBE, 0xEF];
+/// ui.add(MemoryRow::from_slice(&editor.options, 0x8000, &bytes).highlight(0x8001..0x8002).annotation("Crash"));
+/// ```
+pub struct MemoryRow<'a> {
+    options: &'a MemoryEditorOptions,
+    start_address: Address,
+    values: Vec<Option<u8>>,
+    highlight: Range<Address>,
+    annotation: Option<WidgetText>,
+}
+
+impl<'a> MemoryRow<'a> {
+    /// A row of `values` starting at `start_address`, where `None` is an unreadable byte.
+    pub fn new(
+        options: &'a MemoryEditorOptions,
+        start_address: Address,
+        values: impl IntoIterator<Item = Option<u8>>,
+    ) -> Self {
+        MemoryRow {
+            options,
+            start_address,
+            values: values.into_iter().collect(),
+            highlight: 0..0,
+            annotation: None,
+        }
+    }
+
+    /// A row of readable `bytes` starting at `start_address`.
+    pub fn from_slice(options: &'a MemoryEditorOptions, start_address: Address, bytes: &[u8]) -> Self {
+        Self::new(options, start_address, bytes.iter().copied().map(Some))
+    }
+
+    /// Highlight the values in `addresses`, like the selection of the editor.
+    pub fn highlight(mut self, addresses: Range<Address>) -> Self {
+        self.highlight = addresses;
+        self
+    }
+
+    /// Show `text` after the row, such as a description of what the row contains.
+    pub fn annotation(mut self, text: impl Into<WidgetText>) -> Self {
+        self.annotation = Some(text.into());
+        self
+    }
+}
+
+impl Widget for MemoryRow<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let options = self.options;
+        // Values beyond the end of the address space have no address to show them at.
+        let length = (Address::MAX - self.start_address)
+            .checked_add(1)
+            .map_or(self.values.len(), |length| length.min(self.values.len()));
+        let values = &self.values[..length];
+        let last_address = self.start_address + length.saturating_sub(1);
+        let address_characters = format!("{:X}", last_address).chars().count();
+        let cell_format = options.cell_format;
+        let value_font = options.value_font(ui.style());
+
+        ui.horizontal(|ui| {
+            ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
+            ui.spacing_mut().item_spacing.x = GRID_COLUMN_SPACING;
+
+            ui.label(
+                RichText::new(address_text(self.start_address, address_characters, None))
+                    .color(options.address_text_colour)
+                    .font(options.address_font(ui.style())),
+            );
+
+            for (group, values) in values.chunks(cell_format.bytes_per_group()).enumerate() {
+                let group_start = self.start_address + group * cell_format.bytes_per_group();
+
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = CELL_SPACING;
+
+                    for (address, &value) in (group_start..=Address::MAX).zip(values) {
+                        value_cells(ui, cell_format, |ui, sub_cell| {
+                            let mut text = value_cell_text(options, value, sub_cell, &value_font, ui.style());
+                            if self.highlight.contains(&address) {
+                                text = text.color(options.highlight_text_colour);
+                            }
+                            ui.label(text);
+                        });
+                    }
+                });
+            }
+
+            if options.show_ascii {
+                ui.add(egui::Separator::default().vertical().spacing(ASCII_SEPARATOR_SPACING));
+                let text: String = values.iter().map(|value| ascii_character(value.unwrap_or(0))).collect();
+                ui.label(RichText::new(text).font(options.ascii_font(ui.style())));
+            }
+
+            if let Some(annotation) = self.annotation {
+                ui.label(annotation);
+            }
+        })
+        .response
+    }
+}
@@ -0,0 +1,83 @@
+//! Scans over plain byte slices, used as a fast-path when the host's memory is available as a slice.
+//!
+//! With the `rayon` feature enabled the scans are run in parallel.
+use crate::Address;
+
+/// The amount of bytes each parallel job processes.
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_SIZE: usize = 0x10000;
+
+/// Read `address` from `memory`, where `memory[0]` lives at `base`.
+#[inline]
+pub(crate) fn read_slice(memory: &[u8], base: Address, address: Address) -> Option<u8> {
+    memory.get(address.checked_sub(base)?).copied()
+}
+
+/// Returns the offsets at which `memory` and `reference` differ, only the overlapping part is compared.
+pub(crate) fn find_mismatches(memory: &[u8], reference: &[u8]) -> Vec<usize> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        memory
+            .par_chunks(PARALLEL_CHUNK_SIZE)
+            .zip(reference.par_chunks(PARALLEL_CHUNK_SIZE))
+            .enumerate()
+            .flat_map_iter(|(chunk_index, (memory, reference))| {
+                mismatches_sequential(memory, reference).map(move |offset| chunk_index * PARALLEL_CHUNK_SIZE + offset)
+            })
+            .collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        mismatches_sequential(memory, reference).collect()
+    }
+}
+
+/// Returns the offsets at which `pattern` occurs in `memory`, where a `None` in the pattern matches any byte.
+pub(crate) fn find_pattern(memory: &[u8], pattern: &[Option<u8>]) -> Vec<usize> {
+    if pattern.is_empty() || memory.len() < pattern.len() {
+        return Vec::new();
+    }
+    let starts = memory.len() - pattern.len() + 1;
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        // Chunk the start offsets rather than the memory, such that matches crossing a chunk boundary are found.
+        (0..starts.div_ceil(PARALLEL_CHUNK_SIZE))
+            .into_par_iter()
+            .flat_map_iter(|chunk_index| {
+                let chunk_start = chunk_index * PARALLEL_CHUNK_SIZE;
+                let chunk_end = (chunk_start + PARALLEL_CHUNK_SIZE).min(starts);
+                (chunk_start..chunk_end).filter(|&offset| is_pattern_at(memory, pattern, offset))
+            })
+            .collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        (0..starts)
+            .filter(|&offset| is_pattern_at(memory, pattern, offset))
+            .collect()
+    }
+}
+
+#[inline]
+fn is_pattern_at(memory: &[u8], pattern: &[Option<u8>], offset: usize) -> bool {
+    memory[offset..offset + pattern.len()]
+        .iter()
+        .zip(pattern)
+        .all(|(value, byte)| byte.is_none_or(|byte| *value == byte))
+}
+
+fn mismatches_sequential<'a>(memory: &'a [u8], reference: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+    memory
+        .iter()
+        .zip(reference)
+        .enumerate()
+        .filter(|(_, (a, b))| a != b)
+        .map(|(offset, _)| offset)
+}
@@ -0,0 +1,1166 @@
+//! Searching a range for text, as decoded by the ASCII sidebar, or for a pattern of hex bytes.
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use egui::{Color32, RichText, ScrollArea, Ui};
+
+use crate::ascii::ascii_character;
+use crate::labels::{fill, Labels};
+use crate::layout::{ASCII_SEPARATOR_SPACING, CELL_SPACING};
+use crate::option_data::{DataPreviewOptions, MemoryEditorOptions};
+use crate::task::ChunkedTask;
+use crate::{Address, MemoryEditor};
+
+/// The amount of bytes shown on either side of a match in the results.
+const MATCH_CONTEXT: usize = 4;
+
+/// How the query of the `Search` section is interpreted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum SearchMode {
+    #[default]
+    Text,
+    Hex,
+    #[cfg(feature = "regex")]
+    Regex,
+    /// A typed value of the `Data Preview`, which isn't selectable in the `Search` section.
+    Value,
+}
+
+/// How the value of a match has to have changed since the last scan for a rescan to keep it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ValueChange {
+    Increased,
+    Decreased,
+    Unchanged,
+    Changed,
+}
+
+impl ValueChange {
+    const ALL: [ValueChange; 4] = [
+        ValueChange::Increased,
+        ValueChange::Decreased,
+        ValueChange::Unchanged,
+        ValueChange::Changed,
+    ];
+
+    fn label(self, labels: &Labels) -> &str {
+        match self {
+            ValueChange::Increased => &labels.increased,
+            ValueChange::Decreased => &labels.decreased,
+            ValueChange::Unchanged => &labels.unchanged,
+            ValueChange::Changed => &labels.changed,
+        }
+    }
+
+    fn matches(self, ordering: Ordering) -> bool {
+        match self {
+            ValueChange::Increased => ordering == Ordering::Greater,
+            ValueChange::Decreased => ordering == Ordering::Less,
+            ValueChange::Unchanged => ordering == Ordering::Equal,
+            ValueChange::Changed => ordering != Ordering::Equal,
+        }
+    }
+}
+
+/// The amount of decoded characters a regex is matched against at once, such that a region is never decoded as a
+/// whole.
+#[cfg(feature = "regex")]
+const REGEX_WINDOW: usize = 0x10000;
+/// How much consecutive windows of a regex search overlap, longer matches can be cut short at the end of a window.
+#[cfg(feature = "regex")]
+const REGEX_MAX_MATCH: usize = 0x1000;
+
+/// How the memory is matched against the query.
+#[derive(Clone, Debug)]
+enum Matcher {
+    /// The characters of the query, matched against the decoded character of every address.
+    Text(Vec<char>),
+    /// The bytes of a hex pattern, matched against the values themselves. `None` is a wildcard, matching any readable
+    /// byte.
+    Bytes(Vec<Option<u8>>),
+    /// Regexes can match text of any length, so the decoded text is collected into overlapping windows of
+    /// [`REGEX_WINDOW`] characters which are matched one at a time.
+    #[cfg(feature = "regex")]
+    Regex { regex: regex::Regex, text: String },
+}
+
+impl Default for Matcher {
+    fn default() -> Self {
+        Matcher::Text(Vec::new())
+    }
+}
+
+/// A running rescan of the matches of a value search, see [`TextSearch::rescan`].
+#[derive(Clone, Debug)]
+struct Rescan {
+    change: ValueChange,
+    /// The matches before the rescan, their indices are what the task of the search goes through.
+    matches: Vec<Range<Address>>,
+    values: Vec<Vec<u8>>,
+}
+
+/// A running or finished search of a range.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TextSearch {
+    pub query: String,
+    pub mode: SearchMode,
+    pub is_case_insensitive: bool,
+    /// The format of the value of a [`SearchMode::Value`] search.
+    pub data_preview: DataPreviewOptions,
+    /// The region the search was run against.
+    pub region: String,
+    pub addresses: Range<Address>,
+    matcher: Matcher,
+    /// The last values, as many as the query is long, used to match the query across chunk boundaries.
+    window: VecDeque<Option<u8>>,
+    /// The addresses of all matches, ordered by their start.
+    pub matches: Vec<Range<Address>>,
+    /// The values of the matches at the last rescan of a [`SearchMode::Value`] search, in the same order. Empty until
+    /// the first rescan, as every match then still holds the encoded query.
+    values: Vec<Vec<u8>>,
+    /// The index of the match last navigated to, which stands out from the other matches.
+    pub active: Option<usize>,
+    /// The amount of matches of the last replace whose replacement didn't fit before the end of the searched range.
+    pub truncated_replacements: usize,
+    /// Whether the editor went to the first match after the search finished.
+    has_jumped: bool,
+    /// The running rescan, during which the task goes through the indices of its matches rather than addresses.
+    rescan: Option<Rescan>,
+    pub task: Option<ChunkedTask>,
+}
+
+impl TextSearch {
+    fn new(
+        query: String,
+        mode: SearchMode,
+        matcher: Matcher,
+        is_case_insensitive: bool,
+        region: String,
+        addresses: Range<Address>,
+    ) -> Self {
+        TextSearch {
+            query,
+            mode,
+            matcher,
+            is_case_insensitive,
+            region,
+            task: Some(ChunkedTask::new(addresses.clone())),
+            addresses,
+            ..Default::default()
+        }
+    }
+
+    /// Search for the literal `query`, whose matches can overlap.
+    pub fn text(query: String, is_case_insensitive: bool, region: String, addresses: Range<Address>) -> Self {
+        let matcher = Matcher::Text(query.chars().collect());
+
+        Self::new(query, SearchMode::Text, matcher, is_case_insensitive, region, addresses)
+    }
+
+    /// Search for the bytes of `pattern`, where `None` matches any byte, `query` is only used for display.
+    pub fn bytes(query: String, pattern: Vec<Option<u8>>, region: String, addresses: Range<Address>) -> Self {
+        Self::new(
+            query,
+            SearchMode::Hex,
+            Matcher::Bytes(pattern),
+            false,
+            region,
+            addresses,
+        )
+    }
+
+    /// Search for the encoded bytes of the typed value `query`, formatted as `data_preview`.
+    pub fn value(
+        query: String,
+        data_preview: DataPreviewOptions,
+        region: String,
+        addresses: Range<Address>,
+    ) -> Option<Self> {
+        let pattern = crate::utilities::value_string_to_bytes(data_preview, &query)?;
+        let mut search = Self::bytes(query, pattern.into_iter().map(Some).collect(), region, addresses);
+        search.mode = SearchMode::Value;
+        search.data_preview = data_preview;
+
+        Some(search)
+    }
+
+    /// The same search, of `addresses` of another region.
+    fn for_region(&self, region: String, addresses: Range<Address>) -> Self {
+        TextSearch {
+            query: self.query.clone(),
+            mode: self.mode,
+            is_case_insensitive: self.is_case_insensitive,
+            data_preview: self.data_preview,
+            matcher: self.matcher.clone(),
+            region,
+            task: Some(ChunkedTask::new(addresses.clone())),
+            addresses,
+            ..Default::default()
+        }
+    }
+
+    /// Search for the non-overlapping matches of the regex `query`.
+    #[cfg(feature = "regex")]
+    pub fn regex(
+        query: String,
+        is_case_insensitive: bool,
+        region: String,
+        addresses: Range<Address>,
+    ) -> Result<Self, regex::Error> {
+        let regex = regex::RegexBuilder::new(&query)
+            .case_insensitive(is_case_insensitive)
+            .build()?;
+
+        let matcher = Matcher::Regex {
+            regex,
+            text: String::new(),
+        };
+
+        Ok(Self::new(
+            query,
+            SearchMode::Regex,
+            matcher,
+            is_case_insensitive,
+            region,
+            addresses,
+        ))
+    }
+
+    /// Search the next chunk of the range, or rescan the next matches, if there are any.
+    pub fn step<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        budget: usize,
+    ) {
+        let query_length = self.query_length();
+        let Some(task) = self.task.as_mut() else {
+            return;
+        };
+
+        if self.rescan.is_some() {
+            // Every match reads as many bytes as the query is long.
+            let chunk = task.next_chunk((budget / query_length.max(1)).max(1));
+            let is_finished = task.is_finished();
+            for index in chunk.into_iter().flatten() {
+                self.rescan_match(index, &mut |address| read_fn(mem, address));
+            }
+            if is_finished {
+                self.finish_rescan();
+            }
+            return;
+        }
+
+        let chunk = task.next_chunk(budget);
+        let is_finished = task.is_finished();
+
+        for address in chunk.into_iter().flatten() {
+            let value = read_fn(mem, address);
+            self.feed(address, value);
+        }
+
+        if is_finished {
+            self.finish();
+        }
+    }
+
+    /// Finish the running search or rescan at once, see [`MemoryEditor::complete_tasks_with_slice`].
+    pub fn complete_with_slice(&mut self, memory: &[u8], base: Address) {
+        let Some(mut task) = self.task.take() else {
+            return;
+        };
+        let remaining = task.take_remaining();
+
+        if self.rescan.is_some() {
+            for index in remaining {
+                self.rescan_match(index, &mut |address| crate::scan::read_slice(memory, base, address));
+            }
+            self.finish_rescan();
+            return;
+        }
+
+        if let Matcher::Bytes(pattern) = &self.matcher {
+            // Matches can start in the values which were already fed, but not end there.
+            let overlap = self.window.len().min(pattern.len().saturating_sub(1));
+            let start = (remaining.start - overlap).max(base);
+            let end = remaining.end.min(base.saturating_add(memory.len()));
+
+            // Addresses outside the slice are unreadable, and thus never part of a match.
+            if start < end {
+                let found = crate::scan::find_pattern(&memory[start - base..end - base], pattern);
+                let length = pattern.len();
+                self.matches
+                    .extend(found.into_iter().map(|offset| start + offset..start + offset + length));
+            }
+        } else {
+            for address in remaining {
+                self.feed(address, crate::scan::read_slice(memory, base, address));
+            }
+        }
+
+        self.finish();
+    }
+
+    /// Returns the index of the match `address` is part of, if any.
+    pub fn match_at(&self, address: Address) -> Option<usize> {
+        // Matches of the same query don't end before the ones starting earlier, so only the last one can contain it.
+        let index = self
+            .matches
+            .partition_point(|range| range.start <= address)
+            .checked_sub(1)?;
+
+        self.matches[index].contains(&address).then_some(index)
+    }
+
+    /// Returns the text and background colour of `address` if it's part of a match, only the active match has a text
+    /// colour.
+    pub fn match_colours(&self, address: Address, options: &MemoryEditorOptions) -> Option<(Option<Color32>, Color32)> {
+        let index = self.match_at(address)?;
+        let text_colour = (self.active == Some(index)).then_some(options.search_match_colour);
+
+        Some((text_colour, options.search_match_background_colour))
+    }
+
+    /// Make the match `offset` matches after the active one active, wrapping around, and return its address.
+    fn step_active(&mut self, offset: isize) -> Option<Address> {
+        let count = self.matches.len();
+        if count == 0 {
+            return None;
+        }
+
+        let index = match self.active {
+            Some(active) => (active as isize + offset).rem_euclid(count as isize) as usize,
+            None if offset < 0 => count - 1,
+            None => 0,
+        };
+        self.active = Some(index);
+
+        Some(self.matches[index].start)
+    }
+
+    /// Start reading the matches again, keeping those whose value changed as `change` since the last scan, and
+    /// remembering their current values for the next rescan. Matches which became unreadable are dropped.
+    pub fn rescan(&mut self, change: ValueChange) {
+        if !matches!(self.matcher, Matcher::Bytes(_)) || self.task.is_some() {
+            return;
+        }
+
+        let matches = std::mem::take(&mut self.matches);
+        self.task = Some(ChunkedTask::new(0..matches.len()));
+        self.rescan = Some(Rescan {
+            change,
+            matches,
+            values: std::mem::take(&mut self.values),
+        });
+        self.active = None;
+    }
+
+    /// Stop the running rescan, keeping the matches from before it. Returns whether a rescan was running.
+    pub fn cancel_rescan(&mut self) -> bool {
+        let Some(rescan) = self.rescan.take() else {
+            return false;
+        };
+        self.matches = rescan.matches;
+        self.values = rescan.values;
+        self.task = None;
+
+        true
+    }
+
+    /// The amount of bytes every match of the query covers.
+    fn query_length(&self) -> usize {
+        match &self.matcher {
+            Matcher::Text(query) => query.len(),
+            Matcher::Bytes(pattern) => pattern.len(),
+            #[cfg(feature = "regex")]
+            Matcher::Regex { .. } => 1,
+        }
+    }
+
+    /// Read the match at `index` of the running rescan again, and keep it if its value changed as requested.
+    fn rescan_match(&mut self, index: usize, read: &mut dyn FnMut(Address) -> Option<u8>) {
+        let (Some(rescan), Matcher::Bytes(pattern)) = (&self.rescan, &self.matcher) else {
+            return;
+        };
+        let found = rescan.matches[index].clone();
+        let Some(current) = found.clone().map(read).collect::<Option<Vec<u8>>>() else {
+            return;
+        };
+        // Before the first rescan every match still holds the encoded query.
+        let previous = match rescan.values.get(index) {
+            Some(previous) => previous.clone(),
+            None => pattern.iter().flatten().copied().collect(),
+        };
+
+        if crate::utilities::compare_values(self.data_preview, &current, &previous)
+            .is_some_and(|ordering| rescan.change.matches(ordering))
+        {
+            self.matches.push(found);
+            self.values.push(current);
+        }
+    }
+
+    fn finish_rescan(&mut self) {
+        self.rescan = None;
+        self.task = None;
+    }
+
+    /// The bytes `text` replaces a match with, interpreted like the query, where `None` keeps the byte of the match.
+    pub fn replacement(&self, text: &str) -> Option<Vec<Option<u8>>> {
+        let bytes = match self.mode {
+            SearchMode::Text => text.is_ascii().then(|| text.bytes().map(Some).collect()),
+            #[cfg(feature = "regex")]
+            SearchMode::Regex => text.is_ascii().then(|| text.bytes().map(Some).collect()),
+            SearchMode::Hex => crate::utilities::parse_hex_pattern(text),
+            SearchMode::Value => crate::utilities::value_string_to_bytes(self.data_preview, text)
+                .map(|bytes| bytes.into_iter().map(Some).collect()),
+        };
+
+        bytes.filter(|bytes: &Vec<_>| !bytes.is_empty())
+    }
+
+    /// Take the active match, or every match, out of the results, and return the writes replacing them with
+    /// `replacement`. Overlapping matches are only replaced once, and replacements are cut off at the end of the
+    /// searched range.
+    pub fn replace(&mut self, replacement: &[Option<u8>], is_all: bool) -> Vec<(Address, u8)> {
+        let replaced = if is_all {
+            self.active = None;
+            self.values.clear();
+            std::mem::take(&mut self.matches)
+        } else {
+            let Some(index) = self.active.or((!self.matches.is_empty()).then_some(0)) else {
+                return Vec::new();
+            };
+            let replaced = self.matches.remove(index);
+            if index < self.values.len() {
+                self.values.remove(index);
+            }
+            // The match after the replaced one becomes active, as though pressing next.
+            self.active = (!self.matches.is_empty()).then(|| index % self.matches.len());
+            vec![replaced]
+        };
+
+        let mut writes = Vec::new();
+        let mut replaced_end = 0;
+        self.truncated_replacements = 0;
+        for found in replaced {
+            if found.start < replaced_end {
+                continue;
+            }
+
+            let end = found.start.checked_add(replacement.len());
+            replaced_end = end.map_or(self.addresses.end, |end| end.min(self.addresses.end));
+            if Some(replaced_end) != end {
+                self.truncated_replacements += 1;
+            }
+
+            let bytes = (found.start..replaced_end).zip(replacement);
+            writes.extend(bytes.filter_map(|(address, byte)| Some((address, (*byte)?))));
+        }
+
+        writes
+    }
+
+    fn feed(&mut self, address: Address, value: Option<u8>) {
+        let length = match &mut self.matcher {
+            Matcher::Text(query) => query.len(),
+            Matcher::Bytes(pattern) => pattern.len(),
+            #[cfg(feature = "regex")]
+            Matcher::Regex { text, .. } => {
+                text.push(ascii_character(value.unwrap_or(0)));
+                if text.len() >= REGEX_WINDOW + REGEX_MAX_MATCH {
+                    let text_start = address + 1 - text.len();
+                    self.match_regex_window(text_start, false);
+                }
+                return;
+            }
+        };
+        if length == 0 {
+            return;
+        }
+
+        self.window.push_back(value);
+        if self.window.len() > length {
+            self.window.pop_front();
+        }
+        if self.window.len() < length {
+            return;
+        }
+
+        let is_match = match &self.matcher {
+            // Unreadable bytes are shown as `.` in the sidebar, and thus matched as such.
+            Matcher::Text(query) => self.window.iter().zip(query).all(|(value, query)| {
+                let character = ascii_character(value.unwrap_or(0));
+                if self.is_case_insensitive {
+                    character.eq_ignore_ascii_case(query)
+                } else {
+                    character == *query
+                }
+            }),
+            Matcher::Bytes(pattern) => self.window.iter().zip(pattern).all(|(&value, &byte)| match byte {
+                Some(byte) => value == Some(byte),
+                None => value.is_some(),
+            }),
+            #[cfg(feature = "regex")]
+            Matcher::Regex { .. } => false,
+        };
+
+        if is_match {
+            self.matches.push(address + 1 - length..address + 1);
+        }
+    }
+
+    /// Match the regex against the collected text, which starts at `text_start`, and drop the text before the
+    /// overlap with the next window. The last window is matched in full.
+    #[cfg(feature = "regex")]
+    fn match_regex_window(&mut self, text_start: Address, is_last: bool) {
+        let Matcher::Regex { regex, text } = &mut self.matcher else {
+            return;
+        };
+        // Matches starting in the overlap are found in the next window, which also holds the text after them.
+        let searched = if is_last {
+            text.len()
+        } else {
+            text.len().saturating_sub(REGEX_MAX_MATCH)
+        };
+        // The first match can overlap the last match of the previous window.
+        let matched_end = self.matches.last().map_or(0, |found| found.end);
+
+        // Every decoded character is a single byte, so offsets in the text map directly onto addresses.
+        self.matches.extend(
+            regex
+                .find_iter(text)
+                .filter(|found| !found.is_empty() && found.start() < searched)
+                .map(|found| text_start + found.start()..text_start + found.end())
+                .filter(|found| found.start >= matched_end),
+        );
+        text.drain(..searched);
+    }
+
+    fn finish(&mut self) {
+        #[cfg(feature = "regex")]
+        if let Matcher::Regex { text, .. } = &self.matcher {
+            let text_start = self.addresses.end - text.len();
+            self.match_regex_window(text_start, true);
+        }
+
+        self.task = None;
+    }
+}
+
+/// The UI state of the `Search` section.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SearchState {
+    pub query: String,
+    pub is_case_insensitive: bool,
+    pub mode: SearchMode,
+    pub is_selection_only: bool,
+    pub error: Option<String>,
+    /// The query of the value search in the `Data Preview`.
+    pub value_query: String,
+    pub value_error: Option<String>,
+    /// The text the matches are replaced with.
+    pub replacement: String,
+    /// Whether new searches also search every other region, see [`SearchState::other_regions`].
+    pub is_all_regions: bool,
+    /// The search of the current region, whose matches can be cycled through and replaced.
+    pub search: Option<TextSearch>,
+    /// The searches of the other regions for the same query, when searching all regions.
+    pub other_regions: Vec<TextSearch>,
+    /// A match of another region which was clicked, the editor switches to its region and goes to it.
+    pub region_goto: Option<(String, Address)>,
+}
+
+impl SearchState {
+    /// Start searching `addresses` of `region` for the query, or go to the next match if the finished search was for
+    /// the same query, such that submitting the query repeatedly cycles through the matches.
+    ///
+    /// With [`SearchState::is_all_regions`] enabled `other_regions` are searched as well.
+    ///
+    /// Returns the address of the next match when cycling.
+    pub fn submit(
+        &mut self,
+        labels: &Labels,
+        region: &str,
+        addresses: Range<Address>,
+        other_regions: &[(String, Range<Address>)],
+    ) -> Option<Address> {
+        if self.query.is_empty() {
+            return None;
+        }
+
+        if let Some(search) = self.search.as_mut().filter(|search| {
+            search.task.is_none()
+                && search.query == self.query
+                && search.mode == self.mode
+                && search.is_case_insensitive == self.is_case_insensitive
+                && search.region == region
+                && search.addresses == addresses
+        }) {
+            return search.step_active(1);
+        }
+
+        let query = self.query.clone();
+        let region = region.to_string();
+        self.error = None;
+
+        let search = match self.mode {
+            SearchMode::Text => Some(TextSearch::text(query, self.is_case_insensitive, region, addresses)),
+            SearchMode::Hex => match crate::utilities::parse_hex_pattern(&query) {
+                // A pattern of only wildcards would match every address.
+                Some(pattern) if pattern.iter().any(Option::is_some) => {
+                    Some(TextSearch::bytes(query, pattern, region, addresses))
+                }
+                _ => {
+                    self.error = Some(labels.invalid_search_pattern.clone());
+                    None
+                }
+            },
+            #[cfg(feature = "regex")]
+            SearchMode::Regex => match TextSearch::regex(query, self.is_case_insensitive, region, addresses) {
+                Ok(search) => Some(search),
+                Err(error) => {
+                    self.error = Some(fill(&labels.invalid_regex, &[("error", &error)]));
+                    None
+                }
+            },
+            // Value searches are started through `submit_value`.
+            SearchMode::Value => None,
+        };
+
+        if let Some(search) = search {
+            self.start(search, other_regions);
+        }
+
+        None
+    }
+
+    /// Replace the current searches by `search`, and the same search of `other_regions` when searching all regions.
+    fn start(&mut self, search: TextSearch, other_regions: &[(String, Range<Address>)]) {
+        self.other_regions = if self.is_all_regions {
+            other_regions
+                .iter()
+                .map(|(region, addresses)| search.for_region(region.clone(), addresses.clone()))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        self.search = Some(search);
+    }
+
+    /// Like [`SearchState::submit`], but for the typed value query of the `Data Preview`, as `data_preview`.
+    pub fn submit_value(
+        &mut self,
+        labels: &Labels,
+        data_preview: DataPreviewOptions,
+        region: &str,
+        addresses: Range<Address>,
+        other_regions: &[(String, Range<Address>)],
+    ) -> Option<Address> {
+        let query = self.value_query.trim();
+        if query.is_empty() {
+            return None;
+        }
+
+        if let Some(search) = self.search.as_mut().filter(|search| {
+            search.task.is_none()
+                && search.mode == SearchMode::Value
+                && search.query == query
+                && search.data_preview == data_preview
+                && search.region == region
+                && search.addresses == addresses
+        }) {
+            return search.step_active(1);
+        }
+
+        self.value_error = None;
+        match TextSearch::value(query.to_string(), data_preview, region.to_string(), addresses) {
+            Some(search) => self.start(search, other_regions),
+            None => {
+                self.value_error = Some(fill(
+                    &labels.invalid_value,
+                    &[
+                        ("value", &query),
+                        ("format", &format!("{:?}", data_preview.selected_data_format)),
+                    ],
+                ))
+            }
+        }
+
+        None
+    }
+}
+
+impl MemoryEditor {
+    /// Draw the `Search` section, for finding text as it's shown in the ASCII sidebar or a pattern of hex bytes, and
+    /// replacing the matches if a `write_fn` was provided.
+    pub(crate) fn draw_search<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        current_address_range: &Range<Address>,
+        mem: &mut T,
+        read: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        write: &mut Option<impl FnMut(&mut T, Address, u8)>,
+    ) {
+        let mut writes = Vec::new();
+        let other_regions = self.other_regions();
+        egui::CollapsingHeader::new(&self.options.labels.search_title)
+            .default_open(false)
+            .show(ui, |ui| {
+                let selection = self.frame_data.selection().filter(|selection| selection.len() > 1);
+                let state = &mut self.search;
+                let labels = &self.options.labels;
+                let mut goto = None;
+
+                ui.horizontal(|ui| {
+                    let response = ui
+                        .add(egui::TextEdit::singleline(&mut state.query).hint_text(&labels.search_hint))
+                        .on_hover_text(&labels.search_hover);
+                    let is_submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+                    ui.selectable_value(&mut state.mode, SearchMode::Text, &labels.search_text);
+                    ui.selectable_value(&mut state.mode, SearchMode::Hex, &labels.search_hex)
+                        .on_hover_text(&labels.search_hex_hover);
+                    #[cfg(feature = "regex")]
+                    ui.selectable_value(&mut state.mode, SearchMode::Regex, &labels.regex)
+                        .on_hover_text(&labels.regex_hover);
+
+                    ui.add_enabled(
+                        state.mode != SearchMode::Hex,
+                        egui::Checkbox::new(&mut state.is_case_insensitive, &labels.case_insensitive),
+                    );
+
+                    ui.add_enabled(
+                        selection.is_some(),
+                        egui::Checkbox::new(&mut state.is_selection_only, &labels.selection_only),
+                    );
+
+                    ui.add_enabled(
+                        !other_regions.is_empty(),
+                        egui::Checkbox::new(&mut state.is_all_regions, &labels.all_regions),
+                    )
+                    .on_hover_text(&labels.all_regions_hover);
+
+                    if ui.button(&labels.search).clicked() || is_submitted {
+                        // A selection only exists in the current region.
+                        let (addresses, other_regions) = match &selection {
+                            Some(selection) if state.is_selection_only => (selection.clone(), &[][..]),
+                            _ => (current_address_range.clone(), &other_regions[..]),
+                        };
+                        goto = state.submit(labels, &self.options.selected_address_range, addresses, other_regions);
+                    }
+                });
+
+                if let Some(error) = &state.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                if let Some(search) = state.search.as_mut() {
+                    let is_cancelled = search.task.as_ref().map(|task| task.draw_progress(ui, labels));
+                    match is_cancelled {
+                        Some(true) if !search.cancel_rescan() => state.search = None,
+                        Some(_) => {}
+                        // Only show results for the region they were computed for.
+                        None if search.region == self.options.selected_address_range => {
+                            if write.is_some() && !search.matches.is_empty() {
+                                writes = Self::draw_replace(ui, &mut state.replacement, search, labels);
+                                // Show the next match after replacing one, such that it can be replaced in turn.
+                                if !writes.is_empty() {
+                                    goto = goto.or(search.active.map(|active| search.matches[active].start));
+                                }
+                            }
+                            if search.truncated_replacements > 0 {
+                                ui.colored_label(
+                                    ui.visuals().warn_fg_color,
+                                    fill(
+                                        &labels.truncated_replacements,
+                                        &[("count", &search.truncated_replacements)],
+                                    ),
+                                );
+                            }
+                            let read = &mut |address| read(mem, address);
+                            goto = goto.or(Self::draw_search_results(
+                                ui,
+                                search,
+                                &self.options,
+                                current_address_range,
+                                read,
+                            ));
+                        }
+                        None => {}
+                    }
+                }
+
+                let read = &mut |address| read(mem, address);
+                Self::draw_other_region_results(ui, state, &self.options, |_| true, read);
+
+                if let Some(address) = goto {
+                    self.frame_data
+                        .set_goto_address(address, current_address_range, self.column_count());
+                }
+            });
+
+        if let Some(write) = write.as_mut() {
+            self.history.begin_group();
+            for (address, value) in writes {
+                self.write_value(mem, read, write, address, value);
+            }
+            self.history.end_group();
+        }
+    }
+
+    /// Draw the replacement of the matches, returns the writes to replace them with if a replace button was clicked.
+    fn draw_replace(
+        ui: &mut Ui,
+        replacement: &mut String,
+        search: &mut TextSearch,
+        labels: &Labels,
+    ) -> Vec<(Address, u8)> {
+        let mut writes = Vec::new();
+
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(replacement).hint_text(&labels.replacement_hint))
+                .on_hover_text(&labels.replacement_hover);
+
+            let bytes = search.replacement(replacement);
+            let is_valid = bytes.is_some() || replacement.is_empty();
+            let is_replace = ui
+                .add_enabled(bytes.is_some(), egui::Button::new(&labels.replace))
+                .clicked();
+            let is_replace_all = ui
+                .add_enabled(bytes.is_some(), egui::Button::new(&labels.replace_all))
+                .clicked();
+
+            if let Some(bytes) = bytes.filter(|_| is_replace || is_replace_all) {
+                writes = search.replace(&bytes, is_replace_all);
+            }
+            if !is_valid {
+                ui.colored_label(ui.visuals().error_fg_color, &labels.invalid_replacement);
+            }
+        });
+
+        writes
+    }
+
+    /// Draw the value search of the `Data Preview`, for finding the addresses of a known value, such as the health of
+    /// the player. Returns the address of the match to go to, if any.
+    pub(crate) fn draw_value_search(
+        ui: &mut Ui,
+        state: &mut SearchState,
+        options: &MemoryEditorOptions,
+        data_preview: DataPreviewOptions,
+        current_address_range: &Range<Address>,
+        other_regions: &[(String, Range<Address>)],
+        read: &mut dyn FnMut(Address) -> Option<u8>,
+    ) -> Option<Address> {
+        let labels = &options.labels;
+        let mut goto = None;
+
+        ui.horizontal(|ui| {
+            let response = ui
+                .add(egui::TextEdit::singleline(&mut state.value_query).hint_text(&labels.find_value_hint))
+                .on_hover_text(&labels.find_value_hover);
+            let is_submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+            if ui.button(&labels.find_value).clicked() || is_submitted {
+                goto = state.submit_value(
+                    labels,
+                    data_preview,
+                    &options.selected_address_range,
+                    current_address_range.clone(),
+                    other_regions,
+                );
+            }
+        });
+
+        if let Some(error) = &state.value_error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+
+        if let Some(search) = state.search.as_mut().filter(|search| search.mode == SearchMode::Value) {
+            let is_cancelled = search.task.as_ref().map(|task| task.draw_progress(ui, labels));
+            match is_cancelled {
+                Some(true) if !search.cancel_rescan() => state.search = None,
+                Some(_) => {}
+                None if search.region == options.selected_address_range => {
+                    if let Some(change) = Self::draw_rescan(ui, labels) {
+                        search.rescan(change);
+                        // The other regions hold the earlier results of the same search, so they're narrowed down too.
+                        for search in state.other_regions.iter_mut().filter(|search| search.task.is_none()) {
+                            search.rescan(change);
+                        }
+                    }
+
+                    goto = goto.or(Self::draw_search_results(
+                        ui,
+                        search,
+                        options,
+                        current_address_range,
+                        read,
+                    ));
+                }
+                None => {}
+            }
+        }
+
+        let is_value_search = |search: &TextSearch| search.mode == SearchMode::Value;
+        Self::draw_other_region_results(ui, state, options, is_value_search, read);
+
+        goto
+    }
+
+    /// Draw the buttons which narrow down the matches of a value search by how their value changed, returns the change
+    /// of the button that was clicked, if any.
+    fn draw_rescan(ui: &mut Ui, labels: &Labels) -> Option<ValueChange> {
+        let mut rescan = None;
+
+        ui.horizontal(|ui| {
+            ui.label(&labels.rescan).on_hover_text(&labels.rescan_hover);
+
+            for change in ValueChange::ALL {
+                if ui.button(change.label(labels)).clicked() {
+                    rescan = Some(change);
+                }
+            }
+        });
+
+        rescan
+    }
+
+    /// Draw the results of the searches of the other regions which pass `filter`, each under the name of its region.
+    fn draw_other_region_results(
+        ui: &mut Ui,
+        state: &mut SearchState,
+        options: &MemoryEditorOptions,
+        filter: impl Fn(&TextSearch) -> bool,
+        read: &mut dyn FnMut(Address) -> Option<u8>,
+    ) {
+        let labels = &options.labels;
+        let mut is_cancelled = false;
+
+        for search in state.other_regions.iter_mut().filter(|search| filter(search)) {
+            // The current region can be shown down here after the user switched to it.
+            let header = fill(
+                &labels.region_matches,
+                &[("region", &search.region), ("count", &search.matches.len())],
+            );
+
+            match &search.task {
+                Some(task) => {
+                    ui.label(&search.region);
+                    is_cancelled |= task.draw_progress(ui, labels);
+                }
+                None => {
+                    egui::CollapsingHeader::new(header)
+                        .id_salt(("search_region", &search.region))
+                        .show(ui, |ui| {
+                            let address_space = search.addresses.clone();
+                            if let Some(address) = Self::draw_search_results(ui, search, options, &address_space, read)
+                            {
+                                state.region_goto = Some((search.region.clone(), address));
+                            }
+                        });
+                }
+            }
+        }
+
+        // Cancelling a rescan keeps the matches from before it, while cancelling a search drops all other regions.
+        if is_cancelled {
+            state.other_regions.retain_mut(|search| search.cancel_rescan());
+        }
+    }
+
+    /// Draw the matches of a finished search, each with the bytes around it, returns the address of the match to go
+    /// to, if one was clicked.
+    fn draw_search_results(
+        ui: &mut Ui,
+        search: &mut TextSearch,
+        options: &MemoryEditorOptions,
+        address_space: &Range<Address>,
+        read: &mut dyn FnMut(Address) -> Option<u8>,
+    ) -> Option<Address> {
+        let labels = &options.labels;
+        let mut goto = None;
+
+        ui.label(fill(
+            &labels.search_matches,
+            &[
+                ("count", &search.matches.len()),
+                ("query", &search.query),
+                ("range", &format!("{:#X?}", search.addresses)),
+            ],
+        ));
+
+        if !search.matches.is_empty() {
+            ui.horizontal(|ui| {
+                if ui.button("⏶").on_hover_text(&labels.previous_match).clicked() {
+                    goto = search.step_active(-1);
+                }
+                if ui.button("⏷").on_hover_text(&labels.next_match).clicked() {
+                    goto = search.step_active(1);
+                }
+
+                if let Some(active) = search.active {
+                    ui.label(fill(
+                        &labels.search_position,
+                        &[("index", &(active + 1)), ("count", &search.matches.len())],
+                    ));
+                }
+            });
+        }
+
+        let value_font = options.value_font(ui.style());
+        let row_height = ui.fonts(|fonts| fonts.row_height(&value_font));
+
+        egui::CollapsingHeader::new(&labels.search_results)
+            .default_open(true)
+            .show(ui, |ui| {
+                ScrollArea::vertical()
+                    .id_salt("search_matches")
+                    .max_height(row_height * 8.0)
+                    .show_rows(ui, row_height, search.matches.len(), |ui, rows| {
+                        for index in rows {
+                            let found = search.matches[index].clone();
+
+                            ui.horizontal(|ui| {
+                                let text = RichText::new(format!("{:#X}", found.start)).font(value_font.clone());
+                                if ui.link(text).clicked() {
+                                    search.active = Some(index);
+                                    goto = Some(found.start);
+                                }
+
+                                Self::draw_match_context(ui, options, found, address_space, read);
+                            });
+                        }
+                    });
+            });
+
+        goto
+    }
+
+    /// Draw the bytes of a match with [`MATCH_CONTEXT`] bytes on either side, in hex and as ASCII.
+    fn draw_match_context(
+        ui: &mut Ui,
+        options: &MemoryEditorOptions,
+        found: Range<Address>,
+        address_space: &Range<Address>,
+        read: &mut dyn FnMut(Address) -> Option<u8>,
+    ) {
+        let value_font = options.value_font(ui.style());
+        // Long matches, such as those of a regex, are cut short to keep the rows narrow.
+        let start = found.start.clamp(address_space.start, address_space.end);
+        let end = found
+            .end
+            .min(start.saturating_add(MATCH_CONTEXT * 2))
+            .clamp(start, address_space.end);
+        let found = start..end;
+        let context = found.start.saturating_sub(MATCH_CONTEXT).max(address_space.start)
+            ..found.end.saturating_add(MATCH_CONTEXT).min(address_space.end);
+
+        let mut ascii = String::new();
+        let mut hex = |addresses: Range<Address>| {
+            let values: Vec<String> = addresses
+                .map(|address| {
+                    let value = read(address);
+                    ascii.push(ascii_character(value.unwrap_or(0)));
+                    value.map_or_else(|| options.none_display_value.clone(), |value| format!("{:02X}", value))
+                })
+                .collect();
+            RichText::new(values.join(" ")).font(value_font.clone())
+        };
+        let before = hex(context.start..found.start);
+        let matched = hex(found.clone()).color(options.search_match_colour);
+        let after = hex(found.end..context.end);
+
+        ui.spacing_mut().item_spacing.x = CELL_SPACING;
+        ui.weak(before);
+        ui.label(matched);
+        ui.weak(after);
+        ui.add(egui::Separator::default().vertical().spacing(ASCII_SEPARATOR_SPACING));
+        ui.weak(RichText::new(ascii).font(options.ascii_font(ui.style())));
+    }
+
+    /// Go to the first match of the search once it finished.
+    pub(crate) fn jump_to_first_search_match(&mut self) {
+        let column_count = self.column_count();
+        let Some(search) = self
+            .search
+            .search
+            .as_mut()
+            .filter(|search| search.task.is_none() && !search.has_jumped)
+        else {
+            return;
+        };
+        search.has_jumped = true;
+
+        // The results are only shown for their own region.
+        if search.region != self.options.selected_address_range {
+            return;
+        }
+
+        if let (Some(address), Some(address_space)) = (search.step_active(1), self.address_ranges.get(&search.region)) {
+            self.frame_data.set_goto_address(address, address_space, column_count);
+        }
+    }
+
+    /// Returns the finished search of the current region, whose matches are highlighted.
+    pub(crate) fn current_search(&self) -> Option<&TextSearch> {
+        let region = &self.options.selected_address_range;
+
+        self.search
+            .search
+            .iter()
+            .chain(&self.search.other_regions)
+            .find(|search| search.task.is_none() && &search.region == region)
+    }
+
+    /// Returns the name and address range of every region but the current one, to search when searching all regions.
+    pub(crate) fn other_regions(&self) -> Vec<(String, Range<Address>)> {
+        self.address_ranges
+            .iter()
+            .filter(|(name, _)| **name != self.options.selected_address_range)
+            .map(|(name, range)| (name.clone(), range.clone()))
+            .collect()
+    }
+
+    /// Go to a match of another region which was clicked in the results, switching to its region.
+    pub(crate) fn process_search_region_goto(&mut self) {
+        if let Some((region, address)) = self.search.region_goto.take() {
+            if self.set_selected_address_range(&region) {
+                self.queue_goto(address);
+            }
+        }
+    }
+
+    /// Go to the next or previous match of the current search on [`MemoryEditorOptions::find_next_shortcut`] and
+    /// [`MemoryEditorOptions::find_previous_shortcut`].
+    pub(crate) fn handle_find_shortcuts(&mut self, ctx: &egui::Context, address_space: &Range<Address>) {
+        let column_count = self.column_count();
+        let region = &self.options.selected_address_range;
+        let Some(search) = self
+            .search
+            .search
+            .iter_mut()
+            .chain(&mut self.search.other_regions)
+            .find(|search| search.task.is_none() && &search.region == region)
+        else {
+            return;
+        };
+
+        // The previous shortcut goes first, as the default `Shift+F3` would otherwise also match `F3`.
+        let offset = ctx.input_mut(|i| {
+            if i.consume_shortcut(&self.options.find_previous_shortcut) {
+                Some(-1)
+            } else if i.consume_shortcut(&self.options.find_next_shortcut) {
+                Some(1)
+            } else {
+                None
+            }
+        });
+
+        if let Some(address) = offset.and_then(|offset| search.step_active(offset)) {
+            self.frame_data.set_goto_address(address, address_space, column_count);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! A summary of the contents of every region, shown next to the region selector to help pick which one to inspect.
+use std::ops::Range;
+
+use crate::labels::fill;
+use crate::option_data::Labels;
+use crate::task::ChunkedTask;
+use crate::{Address, MemoryEditor};
+
+/// The byte value statistics of a single region, computed over multiple frames.
+#[derive(Clone, Debug)]
+pub(crate) struct RegionSummary {
+    /// How often every byte value occurs in the readable part of the region.
+    counts: [usize; 256],
+    unreadable: usize,
+    pub task: Option<ChunkedTask>,
+}
+
+impl RegionSummary {
+    pub fn new(address_space: &Range<Address>) -> Self {
+        RegionSummary {
+            counts: [0; 256],
+            unreadable: 0,
+            task: Some(ChunkedTask::new(address_space.clone())),
+        }
+    }
+
+    /// Summarise the next chunk of the region, if there is one.
+    pub fn step<T: ?Sized>(
+        &mut self,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        budget: usize,
+    ) {
+        let Some(task) = self.task.as_mut() else {
+            return;
+        };
+        let chunk = task.next_chunk(budget);
+        let is_finished = task.is_finished();
+
+        for address in chunk.into_iter().flatten() {
+            self.add(read_fn(mem, address));
+        }
+
+        if is_finished {
+            self.task = None;
+        }
+    }
+
+    /// Finish the running summary at once, see [`MemoryEditor::complete_tasks_with_slice`].
+    pub fn complete_with_slice(&mut self, memory: &[u8], base: Address) {
+        let Some(mut task) = self.task.take() else {
+            return;
+        };
+
+        for address in task.take_remaining() {
+            self.add(crate::scan::read_slice(memory, base, address));
+        }
+    }
+
+    #[inline]
+    fn add(&mut self, value: Option<u8>) {
+        match value {
+            Some(value) => self.counts[value as usize] += 1,
+            None => self.unreadable += 1,
+        }
+    }
+
+    /// Returns the most common readable byte of the region, once the summary is complete.
+    pub fn dominant_value(&self) -> Option<u8> {
+        if self.task.is_some() {
+            return None;
+        }
+
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .max_by_key(|(_, &count)| count)
+            .map(|(value, _)| value as u8)
+    }
+
+    /// A short description of the statistics, such as `12% 00, 3% FF, entropy 5.21`.
+    pub fn description(&self, labels: &Labels) -> String {
+        let total: usize = self.counts.iter().sum();
+        let percentage = |value: u8| {
+            if total == 0 {
+                0.0
+            } else {
+                self.counts[value as usize] as f64 * 100.0 / total as f64
+            }
+        };
+        let mut description = fill(
+            &labels.region_summary,
+            &[
+                ("zero", &format!("{:.0}", percentage(0x00))),
+                ("ff", &format!("{:.0}", percentage(0xFF))),
+                ("entropy", &format!("{:.2}", crate::utilities::entropy(&self.counts))),
+            ],
+        );
+
+        if self.unreadable > 0 {
+            description.push_str(&fill(
+                &labels.region_summary_unreadable,
+                &[("count", &format!("{:#X}", self.unreadable))],
+            ));
+        }
+        if let Some(task) = &self.task {
+            description.push_str(&format!(" ({:.0}%)", task.progress() * 100.0));
+        }
+
+        description
+    }
+}
+
+impl MemoryEditor {
+    /// Returns the summary of the selected region, starting it if it wasn't computed before.
+    pub(crate) fn selected_region_summary(&mut self) -> Option<&mut RegionSummary> {
+        let name = &self.options.selected_address_range;
+        let address_space = self.address_ranges.get(name)?;
+
+        Some(
+            self.region_summaries
+                .entry(name.clone())
+                .or_insert_with(|| RegionSummary::new(address_space)),
+        )
+    }
+}
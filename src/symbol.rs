@@ -0,0 +1,32 @@
+//! Symbol names provided by the host, shown in an optional labels column next to the grid.
+use std::ops::Range;
+
+use egui::{Label, RichText, Ui};
+
+use crate::{Address, MemoryEditor};
+
+impl MemoryEditor {
+    /// Returns the symbol name of `address`, if a symbol provider was set and knows the address.
+    pub fn symbol_at(&self, address: Address) -> Option<String> {
+        self.symbol_provider.as_ref().and_then(|provider| provider(address))
+    }
+
+    /// Draw the names of all symbols starting in `row`, returns early if there is no symbol provider.
+    pub(crate) fn draw_label_column(&self, ui: &mut Ui, row: Range<Address>, address_space: &Range<Address>) {
+        let labels: Vec<String> = row
+            .filter(|address| address_space.contains(address))
+            .filter_map(|address| self.symbol_at(address))
+            .collect();
+
+        if labels.is_empty() {
+            ui.label("");
+            return;
+        }
+
+        let text = RichText::new(labels.join(", "))
+            .font(self.options.address_font(ui.style()))
+            .color(ui.visuals().weak_text_color());
+
+        ui.add(Label::new(text).truncate()).on_hover_text(labels.join("\n"));
+    }
+}
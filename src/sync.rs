@@ -0,0 +1,140 @@
+//! Linking two editors, such that scrolling and selecting in one is mirrored in the other at a fixed offset.
+use std::ops::Range;
+
+use crate::{Address, MemoryEditor};
+
+/// The state of an editor which is mirrored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct SyncSnapshot {
+    region: String,
+    first_visible: Address,
+    selection: Option<Range<Address>>,
+}
+
+impl SyncSnapshot {
+    fn of(editor: &MemoryEditor) -> Self {
+        SyncSnapshot {
+            region: editor.options.selected_address_range.clone(),
+            first_visible: editor.visible.addresses.start,
+            selection: editor.frame_data.selection(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Side {
+    First,
+    Second,
+}
+
+/// Keeps the scroll position and selection of two editors in sync, where `second` shows `offset` bytes further than
+/// `first`. Useful for comparing the same structure in two save states.
+///
+/// Call [`EditorSync::sync`] every frame after both editors were drawn.
+///
+/// ```
+/// # use egui_memory_editor::{EditorSync, MemoryEditor};
+/// let mut first = MemoryEditor::new().with_address_range("Save A", 0..0x1000);
+/// let mut second = MemoryEditor::new().with_address_range("Save B", 0x2000..0x3000);
+/// let mut sync = EditorSync::new(0x2000);
+///
+/// // ... draw both editors ...
+/// sync.sync(&mut first, &mut second);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct EditorSync {
+    /// The amount of bytes the addresses of the second editor are offset from the first.
+    pub offset: isize,
+    previous: Option<(SyncSnapshot, SyncSnapshot)>,
+    /// The editor which was last scrolled by the sync, whose change in the next frame shouldn't be mirrored back.
+    settling: Option<Side>,
+}
+
+impl EditorSync {
+    pub fn new(offset: isize) -> Self {
+        EditorSync {
+            offset,
+            ..Default::default()
+        }
+    }
+
+    /// Mirror whichever editor changed since the last call to the other one.
+    ///
+    /// If both changed the first editor takes precedence, the very first call aligns `second` with `first`.
+    pub fn sync(&mut self, first: &mut MemoryEditor, second: &mut MemoryEditor) {
+        let current = (SyncSnapshot::of(first), SyncSnapshot::of(second));
+        let settling = self.settling.take();
+
+        let leader = match &self.previous {
+            None => Some(Side::First),
+            Some((previous_first, previous_second)) => {
+                let first_changed = *previous_first != current.0 && settling != Some(Side::First);
+                let second_changed = *previous_second != current.1 && settling != Some(Side::Second);
+
+                if first_changed {
+                    Some(Side::First)
+                } else if second_changed {
+                    Some(Side::Second)
+                } else {
+                    None
+                }
+            }
+        };
+
+        match leader {
+            Some(Side::First) => {
+                Self::mirror(&current.0, second, self.offset);
+                self.settling = Some(Side::Second);
+            }
+            Some(Side::Second) => {
+                Self::mirror(&current.1, first, self.offset.wrapping_neg());
+                self.settling = Some(Side::First);
+            }
+            None => {}
+        }
+
+        self.previous = Some((SyncSnapshot::of(first), SyncSnapshot::of(second)));
+    }
+
+    /// Apply the `leader`'s state to the `follower`, shifted by `offset`.
+    fn mirror(leader: &SyncSnapshot, follower: &mut MemoryEditor, offset: isize) {
+        let shift = |address: Address| address.checked_add_signed(offset);
+
+        if let Some(address) = shift(leader.first_visible) {
+            follower.scroll_to_address(address);
+        }
+
+        let frame_data = &mut follower.frame_data;
+        match leader
+            .selection
+            .as_ref()
+            .and_then(|s| Some(shift(s.start)?..shift(s.end)?))
+        {
+            Some(selection) if !selection.is_empty() => {
+                frame_data.view.is_goto_highlight = false;
+                frame_data.view.selected_highlight_address = Some(selection.start);
+                frame_data.view.selection_end = (selection.len() > 1).then(|| selection.end - 1);
+            }
+            _ => {
+                frame_data.view.selected_highlight_address = None;
+                frame_data.view.selection_end = None;
+            }
+        }
+    }
+}
+
+impl MemoryEditor {
+    /// Scroll to `address` on the next frame without highlighting it, switching to a region containing it if needed.
+    pub(crate) fn scroll_to_address(&mut self, address: Address) {
+        let containing_region = self
+            .address_ranges
+            .get_key_value(&self.options.selected_address_range)
+            .filter(|(_, range)| range.contains(&address))
+            .or_else(|| self.address_ranges.iter().find(|(_, range)| range.contains(&address)));
+
+        if let Some((name, range)) = containing_region {
+            self.options.selected_address_range = name.clone();
+            self.frame_data.view.scroll_line = Some((address - range.start) / self.column_count());
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Long running operations over a range of addresses, such as comparisons, which are spread over multiple frames
+//! to avoid blocking the UI thread on big ranges.
+use std::ops::Range;
+
+use egui::{Context, Ui};
+
+use crate::option_data::Labels;
+use crate::{Address, MemoryEditor};
+
+/// Tracks the progress of an operation which processes a bounded amount of addresses per frame.
+#[derive(Clone, Debug)]
+pub(crate) struct ChunkedTask {
+    range: Range<Address>,
+    next: Address,
+}
+
+impl ChunkedTask {
+    pub fn new(range: Range<Address>) -> Self {
+        ChunkedTask {
+            next: range.start,
+            range,
+        }
+    }
+
+    /// Returns the next chunk of at most `budget` addresses to process, or `None` if the task is done.
+    pub fn next_chunk(&mut self, budget: usize) -> Option<Range<Address>> {
+        if self.is_finished() {
+            return None;
+        }
+
+        let start = self.next;
+        self.next = start.saturating_add(budget.max(1)).min(self.range.end);
+        Some(start..self.next)
+    }
+
+    /// Returns all remaining addresses at once, finishing the task.
+    pub fn take_remaining(&mut self) -> Range<Address> {
+        let start = self.next;
+        self.next = self.range.end;
+        start..self.range.end
+    }
+
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.range.end
+    }
+
+    /// The fraction of the range which has been processed, between `0.0` and `1.0`.
+    pub fn progress(&self) -> f32 {
+        if self.range.is_empty() {
+            1.0
+        } else {
+            (self.next - self.range.start) as f32 / self.range.len() as f32
+        }
+    }
+
+    /// Draw a progress bar with a cancel button, returns `true` if the task should be cancelled.
+    pub fn draw_progress(&self, ui: &mut Ui, labels: &Labels) -> bool {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::ProgressBar::new(self.progress())
+                    .desired_width(ui.available_width() / 2.0)
+                    .show_percentage(),
+            );
+            ui.button(&labels.cancel).clicked()
+        })
+        .inner
+    }
+}
+
+impl MemoryEditor {
+    /// Advance all running tasks by one frame's worth of work.
+    ///
+    /// This has to happen outside the options area, as the tasks need to progress even when their section is collapsed.
+    pub(crate) fn step_tasks<T: ?Sized>(
+        &mut self,
+        ctx: &Context,
+        mem: &mut T,
+        read_fn: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+    ) {
+        let budget = self.options.task_bytes_per_frame;
+        let mut is_running = false;
+
+        if let Some(comparison) = self.comparison.comparison.as_mut() {
+            comparison.step(mem, read_fn, budget);
+            is_running |= comparison.task.is_some();
+        }
+
+        if let Some(patch) = self.frame_data.pending_patch.as_mut() {
+            patch.step(mem, read_fn, budget);
+            is_running |= patch.task.is_some();
+        }
+
+        if let Some(analysis) = self.analysis.analysis.as_mut() {
+            analysis.step(mem, read_fn, budget);
+            is_running |= analysis.task.is_some();
+        }
+
+        if let Some(search) = self.search.search.as_mut() {
+            search.step(mem, read_fn, budget);
+            is_running |= search.task.is_some();
+        }
+        // The other regions are searched one after the other.
+        if let Some(search) = self
+            .search
+            .other_regions
+            .iter_mut()
+            .find(|search| search.task.is_some())
+        {
+            search.step(mem, read_fn, budget);
+            is_running = true;
+        }
+        self.jump_to_first_search_match();
+
+        is_running |= self.checksum.step(mem, read_fn, budget);
+
+        // Only the first pass is worth repainting for, later ones follow the memory as frames are drawn anyway.
+        #[cfg(feature = "histogram")]
+        if let Some(histogram) = self.histogram.as_mut() {
+            histogram.step(mem, read_fn, budget);
+            is_running |= histogram.counts.is_none();
+        }
+
+        if let Some(hashes) = self.hashes.as_mut() {
+            hashes.step(mem, read_fn, budget);
+            is_running |= hashes.task.is_some();
+        }
+
+        is_running |= self.step_interesting_byte_jump(mem, read_fn, budget);
+
+        if self.options.show_region_summary {
+            if let Some(summary) = self.selected_region_summary() {
+                summary.step(mem, read_fn, budget);
+                is_running |= summary.task.is_some();
+            }
+        }
+
+        // Keep the UI going until the work is done, even if the host only repaints on input.
+        if is_running {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Complete all running tasks, such as comparisons, at once by operating directly on `memory`.
+    ///
+    /// This is a fast-path for hosts whose memory is a plain slice, where `memory[0]` lives at address `base`.
+    /// Addresses outside the slice are treated as unreadable.
+    /// With the `rayon` feature enabled suitable scans are run in parallel.
+    pub fn complete_tasks_with_slice(&mut self, memory: &[u8], base: Address) {
+        if let Some(comparison) = self.comparison.comparison.as_mut() {
+            comparison.complete_with_slice(memory, base);
+        }
+
+        if let Some(patch) = self.frame_data.pending_patch.as_mut() {
+            patch.complete_with_slice(memory, base);
+        }
+
+        if let Some(analysis) = self.analysis.analysis.as_mut() {
+            analysis.complete_with_slice(memory, base);
+        }
+
+        for search in self.search.search.iter_mut().chain(&mut self.search.other_regions) {
+            search.complete_with_slice(memory, base);
+        }
+        self.jump_to_first_search_match();
+
+        self.checksum.complete_with_slice(memory, base);
+
+        #[cfg(feature = "histogram")]
+        if let Some(histogram) = self.histogram.as_mut() {
+            histogram.complete_with_slice(memory, base);
+        }
+
+        if let Some(hashes) = self.hashes.as_mut() {
+            hashes.complete_with_slice(memory, base);
+        }
+
+        self.complete_interesting_byte_jump_with_slice(memory, base);
+
+        if self.options.show_region_summary {
+            if let Some(summary) = self.selected_region_summary() {
+                summary.complete_with_slice(memory, base);
+            }
+        }
+    }
+}
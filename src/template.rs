@@ -0,0 +1,239 @@
+//! Struct templates, which describe the typed fields of a structure in memory such as a save file.
+//!
+//! A [`StructTemplate`] registered with [`crate::MemoryEditor::add_template`] can be selected in the `Template`
+//! section of the UI, which decodes every field at a chosen base address. When a `write_fn` is provided the fields
+//! can be edited directly, the typed value is then encoded and written byte by byte.
+use std::ops::Range;
+
+use egui::{RichText, Ui};
+
+use crate::labels::fill;
+use crate::option_data::{DataFormatType, DataPreviewOptions, Endianness};
+use crate::{Address, MemoryEditor};
+
+/// A single typed field of a [`StructTemplate`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemplateField {
+    pub name: String,
+    /// The offset of the field from the start of the structure.
+    pub offset: usize,
+    pub format: DataFormatType,
+    pub endianness: Endianness,
+}
+
+impl TemplateField {
+    #[inline]
+    fn preview_options(&self) -> DataPreviewOptions {
+        DataPreviewOptions {
+            selected_endianness: self.endianness,
+            selected_data_format: self.format,
+        }
+    }
+
+    /// The addresses the field occupies when the structure starts at `base`.
+    #[inline]
+    pub fn addresses(&self, base: Address) -> Range<Address> {
+        let start = base.saturating_add(self.offset);
+        start..start.saturating_add(self.format.bytes_to_read())
+    }
+}
+
+/// A named collection of typed fields, describing a structure in memory.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StructTemplate {
+    pub name: String,
+    pub fields: Vec<TemplateField>,
+}
+
+impl StructTemplate {
+    pub fn new(name: impl Into<String>) -> Self {
+        StructTemplate {
+            name: name.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Add a field at `offset` bytes from the start of the structure.
+    #[must_use]
+    pub fn with_field(
+        mut self,
+        name: impl Into<String>,
+        offset: usize,
+        format: DataFormatType,
+        endianness: Endianness,
+    ) -> Self {
+        self.fields.push(TemplateField {
+            name: name.into(),
+            offset,
+            format,
+            endianness,
+        });
+        self
+    }
+
+    /// The size of the structure, up to the end of its last field.
+    pub fn size(&self) -> usize {
+        self.fields
+            .iter()
+            .map(|field| field.offset + field.format.bytes_to_read())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// The UI state of the `Template` section.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TemplateState {
+    pub selected: usize,
+    pub base_string: String,
+    /// The index of the field being edited, and its text.
+    pub editing: Option<(usize, String)>,
+    pub editing_request_focus: bool,
+    pub error: Option<String>,
+}
+
+impl MemoryEditor {
+    /// Register a template which can be selected in the `Template` section of the UI.
+    pub fn add_template(&mut self, template: StructTemplate) {
+        self.templates.push(template);
+    }
+
+    /// Returns all registered templates.
+    pub fn templates(&self) -> &[StructTemplate] {
+        &self.templates
+    }
+
+    /// Draw the `Template` section, decoding the fields of the selected template and allowing them to be edited.
+    pub(crate) fn draw_template<T: ?Sized>(
+        &mut self,
+        ui: &mut Ui,
+        current_address_range: &Range<Address>,
+        mem: &mut T,
+        read: &mut impl FnMut(&mut T, Address) -> Option<u8>,
+        write: &mut Option<impl FnMut(&mut T, Address, u8)>,
+    ) {
+        if self.templates.is_empty() {
+            return;
+        }
+
+        egui::CollapsingHeader::new(&self.options.labels.template_title)
+            .default_open(false)
+            .show(ui, |ui| {
+                let selection = self.frame_data.selection();
+                let state = &mut self.template;
+                let labels = &self.options.labels;
+                state.selected = state.selected.min(self.templates.len() - 1);
+
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("template_combo")
+                        .selected_text(self.templates[state.selected].name.clone())
+                        .show_ui(ui, |ui| {
+                            for (i, template) in self.templates.iter().enumerate() {
+                                ui.selectable_value(&mut state.selected, i, &template.name);
+                            }
+                        });
+
+                    ui.label(&labels.template_at);
+                    ui.add(
+                        egui::TextEdit::singleline(&mut state.base_string)
+                            .hint_text(&labels.address_hint)
+                            .desired_width(80.0),
+                    );
+
+                    if ui
+                        .add_enabled(selection.is_some(), egui::Button::new(&labels.use_selection))
+                        .on_hover_text(&labels.template_use_selection_hover)
+                        .clicked()
+                    {
+                        if let Some(selection) = &selection {
+                            state.base_string = format!("{:X}", selection.start);
+                        }
+                    }
+                });
+
+                let Some(base) = crate::utilities::parse_address(&state.base_string)
+                    .filter(|base| current_address_range.contains(base))
+                else {
+                    ui.label(fill(
+                        &labels.template_base_hint,
+                        &[("range", &format!("{:#X?}", current_address_range))],
+                    ));
+                    return;
+                };
+
+                let template = &self.templates[state.selected];
+                let mut write_request = None;
+
+                egui::Grid::new("template_grid").striped(true).show(ui, |ui| {
+                    for (i, field) in template.fields.iter().enumerate() {
+                        let addresses = field.addresses(base);
+
+                        ui.label(&field.name);
+                        ui.label(format!("{:#X}", addresses.start));
+                        ui.label(format!("{:?}", field.format));
+
+                        if !(current_address_range.contains(&addresses.start)
+                            && addresses.end <= current_address_range.end)
+                        {
+                            ui.colored_label(ui.visuals().warn_fg_color, &labels.out_of_range);
+                            ui.end_row();
+                            continue;
+                        }
+
+                        match &mut state.editing {
+                            Some((index, text)) if *index == i => {
+                                let response = ui.add(egui::TextEdit::singleline(text).desired_width(120.0));
+                                if std::mem::take(&mut state.editing_request_focus) {
+                                    response.request_focus();
+                                }
+
+                                if response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                                    match crate::utilities::value_string_to_bytes(field.preview_options(), text) {
+                                        Some(bytes) => {
+                                            write_request = Some((addresses.start, bytes));
+                                            state.error = None;
+                                        }
+                                        None => {
+                                            state.error = Some(fill(
+                                                &labels.invalid_value,
+                                                &[("value", &text.trim()), ("format", &format!("{:?}", field.format))],
+                                            ))
+                                        }
+                                    }
+                                    state.editing = None;
+                                } else if response.lost_focus() {
+                                    // Either escape was pressed or the user clicked elsewhere.
+                                    state.editing = None;
+                                }
+                            }
+                            _ => {
+                                let bytes: Vec<u8> = addresses.map(|address| read(mem, address).unwrap_or(0)).collect();
+                                let value = crate::utilities::bytes_to_value_string(field.preview_options(), &bytes)
+                                    .unwrap_or_else(|| self.options.none_display_value.clone());
+                                let text = RichText::new(&value).font(self.options.value_font(ui.style()));
+
+                                if write.is_some() {
+                                    if ui.link(text).on_hover_text(&labels.edit_field_hover).clicked() {
+                                        state.editing = Some((i, value));
+                                        state.editing_request_focus = true;
+                                    }
+                                } else {
+                                    ui.label(text);
+                                }
+                            }
+                        }
+
+                        ui.end_row();
+                    }
+                });
+
+                if let Some(error) = &state.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                if let (Some((start, bytes)), Some(write)) = (write_request, write.as_mut()) {
+                    self.apply_bytes(mem, read, write, start, &bytes);
+                }
+            });
+    }
+}
@@ -1,34 +1,305 @@
+//! The conversions between addresses, values and their text used throughout the editor, for hosts which show the same
+//! values elsewhere.
+//!
+//! ```
+//! use egui_memory_editor::option_data::{DataFormatType, DataPreviewOptions, Endianness};
+//! use egui_memory_editor::utilities::*;
+//!
+//! let data_preview = DataPreviewOptions {
+//!     selected_endianness: Endianness::Little,
+//!     selected_data_format: DataFormatType::U16,
+//! };
+//! let bytes = value_string_to_bytes(data_preview, "1000").unwrap();
+//!
+//! assert_eq!(bytes, [0xE8, 0x03]);
+//! assert_eq!(bytes_to_value_string(data_preview, &bytes).as_deref(), Some("1000"));
+//! assert_eq!(bytes_to_value_string(data_preview, &bytes[..1]), None);
+//! assert_eq!(format_address(parse_address("0xFF05").unwrap(), 6), "0x00FF05");
+//! ```
 use crate::option_data::{DataFormatType, DataPreviewOptions, Endianness};
+use crate::Address;
 
 /// Turn a provided slice into a decimal [`String`] representing it's value, interpretation is based on the provided
 /// [`crate::option_data::DataPreviewOptions`].
 ///
-/// The provided `bytes` slice is expected to have the appropriate amount of bytes, or else the function will panic.
-pub fn slice_to_decimal_string(data_preview: DataPreviewOptions, bytes: &[u8]) -> String {
+/// Returns `None` if `bytes` doesn't have exactly [`DataFormatType::bytes_to_read`] bytes.
+pub fn bytes_to_value_string(data_preview: DataPreviewOptions, bytes: &[u8]) -> Option<String> {
+    let text = match data_preview.selected_endianness {
+        Endianness::Middle => {
+            return bytes_to_value_string(
+                DataPreviewOptions {
+                    selected_endianness: Endianness::Big,
+                    ..data_preview
+                },
+                &swap_bytes_in_words(bytes),
+            )
+        }
+        Endianness::ByteSwapped => {
+            return bytes_to_value_string(
+                DataPreviewOptions {
+                    selected_endianness: Endianness::Little,
+                    ..data_preview
+                },
+                &swap_bytes_in_words(bytes),
+            )
+        }
+        Endianness::Big => match data_preview.selected_data_format {
+            DataFormatType::U8 => u8::from_be_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::U16 => u16::from_be_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::U32 => u32::from_be_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::U64 => u64::from_be_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::I8 => i8::from_be_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::I16 => i16::from_be_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::I32 => i32::from_be_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::I64 => i64::from_be_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::F32 => f32::from_be_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::F64 => f64::from_be_bytes(bytes.try_into().ok()?).to_string(),
+        },
+        Endianness::Little => match data_preview.selected_data_format {
+            DataFormatType::U8 => u8::from_le_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::U16 => u16::from_le_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::U32 => u32::from_le_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::U64 => u64::from_le_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::I8 => i8::from_le_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::I16 => i16::from_le_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::I32 => i32::from_le_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::I64 => i64::from_le_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::F32 => f32::from_le_bytes(bytes.try_into().ok()?).to_string(),
+            DataFormatType::F64 => f64::from_le_bytes(bytes.try_into().ok()?).to_string(),
+        },
+    };
+
+    Some(text)
+}
+
+/// Format an address as the editor does, in hex with a `0x` prefix and at least `digits` digits.
+pub fn format_address(address: Address, digits: usize) -> String {
+    format!("0x{:01$X}", address, digits)
+}
+
+/// Parse a hexadecimal address, with or without a `0x` prefix.
+pub fn parse_address(text: &str) -> Option<Address> {
+    let text = text.trim();
+    let digits = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text);
+
+    Address::from_str_radix(digits, 16).ok()
+}
+
+/// Parse a list of hexadecimal bytes, such as `DE AD BE EF` or `DEADBEEF`.
+///
+/// Returns `None` if the text contains anything else, or an odd amount of digits.
+pub fn parse_hex_bytes(text: &str) -> Option<Vec<u8>> {
+    let digits: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+
+    digits
+        .chunks(2)
+        .map(|pair| match pair {
+            [high, low] => Some((high.to_digit(16)? << 4 | low.to_digit(16)?) as u8),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parse bytes pasted from elsewhere, such as `00 11 22 33`, `0x00,0x11,0x22` or `DEADBEEF`.
+///
+/// Bytes can be separated by whitespace or commas and prefixed with `0x`, a lone digit is a byte of its own.
+/// Returns `None` if the text contains anything else, or no bytes at all.
+pub fn parse_pasted_bytes(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+
+    for token in text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+    {
+        let digits = token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"))
+            .unwrap_or(token);
+
+        match digits.len() {
+            0 => return None,
+            1 => bytes.push(digits.chars().next()?.to_digit(16)? as u8),
+            _ => bytes.extend(parse_hex_bytes(digits)?),
+        }
+    }
+
+    (!bytes.is_empty()).then_some(bytes)
+}
+
+/// Parse a pattern of hexadecimal bytes and `??` wildcards, such as `48 8B ?? ?? E8`, where a wildcard is `None`.
+///
+/// Returns `None` if the text contains anything else, or an odd amount of digits.
+pub fn parse_hex_pattern(text: &str) -> Option<Vec<Option<u8>>> {
+    let digits: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+
+    digits
+        .chunks(2)
+        .map(|pair| match pair {
+            ['?', '?'] => Some(None),
+            [high, low] => Some(Some((high.to_digit(16)? << 4 | low.to_digit(16)?) as u8)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Swap the two bytes of every 16-bit word, a trailing odd byte is left as is.
+///
+/// Turns PDP-endian data into big-endian, and byte-swapped data into little-endian.
+pub fn swap_bytes_in_words(bytes: &[u8]) -> Vec<u8> {
+    bytes.chunks(2).flat_map(|word| word.iter().rev().copied()).collect()
+}
+
+/// Parse a decimal `text` into the bytes of the value, the inverse of [`bytes_to_value_string`].
+///
+/// Returns `None` if the text isn't a valid value of the selected data format.
+pub fn value_string_to_bytes(data_preview: DataPreviewOptions, text: &str) -> Option<Vec<u8>> {
+    let text = text.trim();
+
     match data_preview.selected_endianness {
+        Endianness::Middle => value_string_to_bytes(
+            DataPreviewOptions {
+                selected_endianness: Endianness::Big,
+                ..data_preview
+            },
+            text,
+        )
+        .map(|bytes| swap_bytes_in_words(&bytes)),
+        Endianness::ByteSwapped => value_string_to_bytes(
+            DataPreviewOptions {
+                selected_endianness: Endianness::Little,
+                ..data_preview
+            },
+            text,
+        )
+        .map(|bytes| swap_bytes_in_words(&bytes)),
         Endianness::Big => match data_preview.selected_data_format {
-            DataFormatType::U8 => u8::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::U16 => u16::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::U32 => u32::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::U64 => u64::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I8 => i8::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I16 => i16::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I32 => i32::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I64 => i64::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::F32 => f32::from_be_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::F64 => f64::from_be_bytes(bytes.try_into().unwrap()).to_string(),
+            DataFormatType::U8 => text.parse::<u8>().ok().map(|v| v.to_be_bytes().to_vec()),
+            DataFormatType::U16 => text.parse::<u16>().ok().map(|v| v.to_be_bytes().to_vec()),
+            DataFormatType::U32 => text.parse::<u32>().ok().map(|v| v.to_be_bytes().to_vec()),
+            DataFormatType::U64 => text.parse::<u64>().ok().map(|v| v.to_be_bytes().to_vec()),
+            DataFormatType::I8 => text.parse::<i8>().ok().map(|v| v.to_be_bytes().to_vec()),
+            DataFormatType::I16 => text.parse::<i16>().ok().map(|v| v.to_be_bytes().to_vec()),
+            DataFormatType::I32 => text.parse::<i32>().ok().map(|v| v.to_be_bytes().to_vec()),
+            DataFormatType::I64 => text.parse::<i64>().ok().map(|v| v.to_be_bytes().to_vec()),
+            DataFormatType::F32 => text.parse::<f32>().ok().map(|v| v.to_be_bytes().to_vec()),
+            DataFormatType::F64 => text.parse::<f64>().ok().map(|v| v.to_be_bytes().to_vec()),
         },
         Endianness::Little => match data_preview.selected_data_format {
-            DataFormatType::U8 => u8::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::U16 => u16::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::U32 => u32::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::U64 => u64::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I8 => i8::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I16 => i16::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I32 => i32::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::I64 => i64::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::F32 => f32::from_le_bytes(bytes.try_into().unwrap()).to_string(),
-            DataFormatType::F64 => f64::from_le_bytes(bytes.try_into().unwrap()).to_string(),
+            DataFormatType::U8 => text.parse::<u8>().ok().map(|v| v.to_le_bytes().to_vec()),
+            DataFormatType::U16 => text.parse::<u16>().ok().map(|v| v.to_le_bytes().to_vec()),
+            DataFormatType::U32 => text.parse::<u32>().ok().map(|v| v.to_le_bytes().to_vec()),
+            DataFormatType::U64 => text.parse::<u64>().ok().map(|v| v.to_le_bytes().to_vec()),
+            DataFormatType::I8 => text.parse::<i8>().ok().map(|v| v.to_le_bytes().to_vec()),
+            DataFormatType::I16 => text.parse::<i16>().ok().map(|v| v.to_le_bytes().to_vec()),
+            DataFormatType::I32 => text.parse::<i32>().ok().map(|v| v.to_le_bytes().to_vec()),
+            DataFormatType::I64 => text.parse::<i64>().ok().map(|v| v.to_le_bytes().to_vec()),
+            DataFormatType::F32 => text.parse::<f32>().ok().map(|v| v.to_le_bytes().to_vec()),
+            DataFormatType::F64 => text.parse::<f64>().ok().map(|v| v.to_le_bytes().to_vec()),
         },
     }
 }
+
+/// Compare the values `a` and `b` are the bytes of, as interpreted by `data_preview`. Returns `None` if either is
+/// `NaN`.
+pub(crate) fn compare_values(data_preview: DataPreviewOptions, a: &[u8], b: &[u8]) -> Option<std::cmp::Ordering> {
+    let (a, b) = (
+        big_endian_value_bytes(data_preview, a)?,
+        big_endian_value_bytes(data_preview, b)?,
+    );
+
+    // Every signed format fits in an `i128`.
+    let signed = |bytes: &[u8]| {
+        let value = bytes.iter().fold(0u128, |value, &byte| value << 8 | byte as u128);
+        let unused_bits = 128 - bytes.len() as u32 * 8;
+        // Shifting the sign bit to the top and back extends it over the unused bits.
+        (value << unused_bits) as i128 >> unused_bits
+    };
+
+    match data_preview.selected_data_format {
+        DataFormatType::F32 => {
+            f32::from_be_bytes(a.try_into().ok()?).partial_cmp(&f32::from_be_bytes(b.try_into().ok()?))
+        }
+        DataFormatType::F64 => {
+            f64::from_be_bytes(a.try_into().ok()?).partial_cmp(&f64::from_be_bytes(b.try_into().ok()?))
+        }
+        DataFormatType::I8 | DataFormatType::I16 | DataFormatType::I32 | DataFormatType::I64 => {
+            Some(signed(&a).cmp(&signed(&b)))
+        }
+        // Big-endian bytes of the same length compare like the unsigned values they encode.
+        _ => Some(a.cmp(&b)),
+    }
+}
+
+/// Reorder the bytes of a value to big-endian, such that they can be decoded without regard for the endianness.
+/// Returns `None` if there aren't exactly [`DataFormatType::bytes_to_read`] bytes.
+fn big_endian_value_bytes(data_preview: DataPreviewOptions, bytes: &[u8]) -> Option<Vec<u8>> {
+    if bytes.len() != data_preview.selected_data_format.bytes_to_read() {
+        return None;
+    }
+
+    let mut bytes = match data_preview.selected_endianness {
+        Endianness::Middle | Endianness::ByteSwapped => swap_bytes_in_words(bytes),
+        Endianness::Big | Endianness::Little => bytes.to_vec(),
+    };
+    if matches!(
+        data_preview.selected_endianness,
+        Endianness::Little | Endianness::ByteSwapped
+    ) {
+        bytes.reverse();
+    }
+
+    Some(bytes)
+}
+
+/// The Shannon entropy in bits per byte, ranging from `0.0` for a single repeated value to `8.0` for random data.
+pub fn entropy(counts: &[usize; 256]) -> f64 {
+    let total: usize = counts.iter().sum();
+
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let probability = count as f64 / total as f64;
+            -probability * probability.log2()
+        })
+        .sum()
+}
+
+/// Calculate the `CRC-16/CCITT-FALSE` of the provided bytes, as used by many save file and ROM formats.
+pub fn crc16(bytes: &[u8]) -> u16 {
+    crc16_update(0xFFFF, bytes)
+}
+
+/// Continue the [`crc16`] of earlier bytes, such that `crc16_update(crc16(a), b)` is the CRC-16 of `a` followed by `b`.
+pub fn crc16_update(crc: u16, bytes: &[u8]) -> u16 {
+    bytes.iter().fold(crc, |crc, &byte| {
+        (0..8).fold(crc ^ ((byte as u16) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// Calculate the standard (`ISO-HDLC`) CRC-32 of the provided bytes, as used by `zip`, `png` and `BPS` patches.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    crc32_update(0, bytes)
+}
+
+/// Continue the [`crc32`] of earlier bytes, such that `crc32_update(crc32(a), b)` is the CRC-32 of `a` followed by `b`.
+pub fn crc32_update(crc: u32, bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!crc, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| {
+            if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
@@ -0,0 +1,135 @@
+//! Additional views onto the memory of a [`MemoryEditor`], with their own scroll position and selection.
+use std::ops::Range;
+
+use egui::{Context, Rect, Ui};
+
+use crate::ascii::AsciiRowCache;
+use crate::layout::LayoutMetrics;
+use crate::option_data::ViewState;
+use crate::refresh::RefreshCache;
+use crate::{Address, EditorResponse, MemoryEditor, VisibleRange};
+
+/// A second window onto the memory of a [`MemoryEditor`], such as one for the code and one for the data of a program.
+///
+/// The view shares the address ranges, options, edit history, pending writes, metrics and tools of the editor it's
+/// drawn with, but has its own region, scroll position, selection and window. Every view should have a different window name, as `egui`
+/// identifies windows by their title.
+///
+/// ```no_run
+/// # use egui_memory_editor::{MemoryEditor, MemoryEditorView};
+/// # let ctx = egui::Context::default();
+/// let mut editor = MemoryEditor::new().with_address_range("RAM", 0..0x10000);
+/// let mut data_view = MemoryEditorView::new("Data");
+/// let mut memory = vec![0u8; 0x10000];
+///
+/// // Every frame:
+/// editor.window_ui_slice(&ctx, &mut true, &mut memory);
+/// data_view.with(&mut editor, |editor| editor.window_ui_slice(&ctx, &mut true, &mut memory));
+/// ```
+#[derive(Clone)]
+pub struct MemoryEditorView {
+    window_name: String,
+    selected_address_range: String,
+    window_rect: Option<Rect>,
+    is_window_open: bool,
+    view: ViewState,
+    visible: VisibleRange,
+    predicted_visible_range: Range<Address>,
+    layout_metrics: LayoutMetrics,
+    ascii_rows: AsciiRowCache,
+    refresh: RefreshCache,
+}
+
+impl MemoryEditorView {
+    /// Create a view with its own window, initially showing the first address range of the editor.
+    pub fn new(window_name: impl Into<String>) -> Self {
+        MemoryEditorView {
+            window_name: window_name.into(),
+            selected_address_range: String::new(),
+            window_rect: None,
+            is_window_open: true,
+            view: Default::default(),
+            visible: Default::default(),
+            predicted_visible_range: Default::default(),
+            layout_metrics: Default::default(),
+            ascii_rows: Default::default(),
+            refresh: Default::default(),
+        }
+    }
+
+    /// Run `f` with the `editor` showing this view, for any of the editor's methods which depend on the view, such
+    /// as [`MemoryEditor::queue_goto`] or [`MemoryEditor::selection`].
+    pub fn with<R>(&mut self, editor: &mut MemoryEditor, f: impl FnOnce(&mut MemoryEditor) -> R) -> R {
+        self.swap(editor);
+        let result = f(editor);
+        self.swap(editor);
+        result
+    }
+
+    /// Create a window for this view, see [`MemoryEditor::window_ui`].
+    pub fn window_ui<T: ?Sized>(
+        &mut self,
+        editor: &mut MemoryEditor,
+        ctx: &Context,
+        is_open: &mut bool,
+        mem: &mut T,
+        read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
+        write_fn: impl FnMut(&mut T, Address, u8),
+    ) -> EditorResponse {
+        self.with(editor, |editor| editor.window_ui(ctx, is_open, mem, read_fn, write_fn))
+    }
+
+    /// Create a read-only window for this view, see [`MemoryEditor::window_ui_read_only`].
+    pub fn window_ui_read_only<T: ?Sized>(
+        &mut self,
+        editor: &mut MemoryEditor,
+        ctx: &Context,
+        is_open: &mut bool,
+        mem: &mut T,
+        read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
+    ) -> EditorResponse {
+        self.with(editor, |editor| editor.window_ui_read_only(ctx, is_open, mem, read_fn))
+    }
+
+    /// Draw this view in any container, see [`MemoryEditor::draw_editor_contents`].
+    ///
+    /// Use [`Ui::push_id`] when drawing several views in the same `Ui`, to keep their scroll positions apart.
+    pub fn draw_editor_contents<T: ?Sized>(
+        &mut self,
+        editor: &mut MemoryEditor,
+        ui: &mut Ui,
+        mem: &mut T,
+        read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
+        write_fn: impl FnMut(&mut T, Address, u8),
+    ) -> EditorResponse {
+        self.with(editor, |editor| editor.draw_editor_contents(ui, mem, read_fn, write_fn))
+    }
+
+    /// Draw this view read-only in any container, see [`MemoryEditor::draw_editor_contents_read_only`].
+    pub fn draw_editor_contents_read_only<T: ?Sized>(
+        &mut self,
+        editor: &mut MemoryEditor,
+        ui: &mut Ui,
+        mem: &mut T,
+        read_fn: impl FnMut(&mut T, Address) -> Option<u8>,
+    ) -> EditorResponse {
+        self.with(editor, |editor| editor.draw_editor_contents_read_only(ui, mem, read_fn))
+    }
+
+    /// Exchange the state of this view with the one the editor currently shows.
+    fn swap(&mut self, editor: &mut MemoryEditor) {
+        std::mem::swap(&mut self.window_name, &mut editor.window_name);
+        std::mem::swap(
+            &mut self.selected_address_range,
+            &mut editor.options.selected_address_range,
+        );
+        std::mem::swap(&mut self.window_rect, &mut editor.options.window_rect);
+        std::mem::swap(&mut self.is_window_open, &mut editor.options.is_window_open);
+        std::mem::swap(&mut self.view, &mut editor.frame_data.view);
+        std::mem::swap(&mut self.visible, &mut editor.visible);
+        std::mem::swap(&mut self.predicted_visible_range, &mut editor.predicted_visible_range);
+        std::mem::swap(&mut self.layout_metrics, &mut editor.layout_metrics);
+        std::mem::swap(&mut self.ascii_rows, &mut editor.ascii_rows);
+        std::mem::swap(&mut self.refresh, &mut editor.refresh);
+    }
+}
@@ -0,0 +1,2303 @@
+//! Integration tests which drive the editor through `egui_kittest`, interacting with it as a user would.
+use egui_kittest::kittest::Queryable;
+use egui_kittest::Harness;
+use egui_memory_editor::option_data::{DataFormatType, DataPreviewOptions, Endianness};
+use egui_memory_editor::recording::UserAction;
+use egui_memory_editor::{MemoryEditor, RejectedInput};
+
+struct State {
+    editor: MemoryEditor,
+    memory: Vec<u8>,
+}
+
+impl State {
+    /// An editor over `0x1000` zeroed bytes, except for a `5A` at `0x5` which can be found by its label.
+    fn new() -> Self {
+        let mut memory = vec![0; 0x1000];
+        memory[5] = 0x5A;
+
+        let mut editor = MemoryEditor::new().with_address_range("RAM", 0..0x1000);
+        // Hide the text inputs of the options, such that the only one left is the inline editor.
+        editor.options.is_options_collapsed = true;
+
+        State { editor, memory }
+    }
+}
+
+fn harness(state: State) -> Harness<'static, State> {
+    Harness::new_ui_state(
+        |ui, state: &mut State| {
+            state.editor.draw_editor_contents_slice(ui, &mut state.memory);
+        },
+        state,
+    )
+}
+
+#[test]
+fn queued_goto_scrolls_to_and_selects_the_address() {
+    let mut harness = harness(State::new());
+    harness.state_mut().editor.queue_goto(0xA50);
+    harness.run();
+
+    let editor = &harness.state().editor;
+    assert!(editor.visible_range().contains(&0xA50));
+    assert_eq!(editor.selection(), Some(0xA50..0xA51));
+}
+
+#[test]
+fn clicking_a_value_and_typing_writes_it() {
+    let mut harness = harness(State::new());
+    harness.run();
+
+    harness.get_by_label("5A").click();
+    harness.run();
+    harness.get_by_role(egui::accesskit::Role::TextInput).type_text("AB");
+    harness.run();
+
+    assert_eq!(harness.state().memory[5], 0xAB);
+    assert_eq!(harness.state().editor.edit_history().len(), 1);
+}
+
+#[test]
+fn read_only_lock_prevents_edits() {
+    let mut harness = harness(State::new());
+    harness.state_mut().editor.options.is_write_locked = true;
+    harness.run();
+
+    harness.get_by_label("5A").click();
+    harness.run();
+
+    assert!(harness.query_by_role(egui::accesskit::Role::TextInput).is_none());
+    assert_eq!(harness.state().memory[5], 0x5A);
+}
+
+#[test]
+fn replayed_writes_go_through_the_write_fn() {
+    let mut harness = harness(State::new());
+    harness.state_mut().editor.start_recording();
+    harness.state_mut().editor.replay_action(&UserAction::Write {
+        address: 0x10,
+        value: 0xFF,
+    });
+    harness.run();
+
+    assert_eq!(harness.state().memory[0x10], 0xFF);
+    let recording = harness.state_mut().editor.stop_recording().unwrap();
+    assert_eq!(
+        recording.actions.last().map(|action| &action.action),
+        Some(&UserAction::Write {
+            address: 0x10,
+            value: 0xFF
+        })
+    );
+}
+
+#[test]
+fn escape_closes_the_inline_editor() {
+    let mut harness = harness(State::new());
+    harness.run();
+
+    harness.get_by_label("5A").click();
+    harness.run();
+    harness.press_key(egui::Key::Escape);
+    harness.run();
+
+    assert!(harness.query_by_role(egui::accesskit::Role::TextInput).is_none());
+}
+
+#[test]
+fn right_to_left_places_the_addresses_after_the_values() {
+    let mut state = State::new();
+    state.editor.options.layout_direction = egui_memory_editor::option_data::LayoutDirection::RightToLeft;
+    let mut harness = harness(state);
+    harness.run();
+
+    let address = harness.get_by_label("0x000:").bounding_box().unwrap();
+    let value = harness.get_by_label("5A").bounding_box().unwrap();
+    assert!(address.x0 > value.x1);
+}
+
+#[test]
+fn proportional_fonts_keep_the_cells_aligned() {
+    let mut state = State::new();
+    state.editor.options.font_id = Some(egui::FontId::proportional(14.0));
+    let mut harness = harness(state);
+    harness.run();
+
+    let five_a = harness.get_by_label("5A").bounding_box().unwrap();
+    let zero = harness.get_all_by_label("00").nth(5).unwrap().bounding_box().unwrap();
+    assert!((five_a.width() - zero.width()).abs() < 0.01);
+}
+
+#[test]
+fn buffered_reads_display_and_edit_the_memory() {
+    let mut harness = Harness::new_ui_state(
+        |ui, state: &mut State| {
+            state.editor.draw_editor_contents_buffered(
+                ui,
+                &mut state.memory,
+                |memory, address, length| memory.get(address..(address + length).min(memory.len())),
+                |memory, address, value| memory[address] = value,
+            );
+        },
+        State::new(),
+    );
+    harness.run();
+
+    harness.get_by_label("5A").click();
+    harness.run();
+    harness.get_by_role(egui::accesskit::Role::TextInput).type_text("AB");
+    harness.run();
+
+    assert_eq!(harness.state().memory[5], 0xAB);
+    assert!(harness.query_by_label("AB").is_some());
+}
+
+#[test]
+fn ascii_rows_are_reused_until_their_bytes_change() {
+    let mut harness = harness(State::new());
+    harness.run();
+    assert!(harness.state().editor.metrics().cached_ascii_rows > 0);
+    assert!(harness.query_all_by_label("................").count() > 0);
+
+    harness.state_mut().memory[6] = b'A';
+    harness.run();
+    assert!(harness.query_all_by_label("................").count() > 0);
+}
+
+#[test]
+fn metrics_count_the_widgets_of_the_grid() {
+    let mut harness = harness(State::new());
+    harness.run();
+
+    let metrics = harness.state().editor.metrics();
+    assert!(metrics.widgets >= metrics.bytes_drawn);
+    assert_eq!(metrics.cache_hit_rate(), Some(0.0));
+}
+
+#[test]
+fn refresh_interval_shows_the_last_read_values() {
+    let mut state = State::new();
+    state.editor.options.refresh_interval = Some(std::time::Duration::from_secs(3600));
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.state_mut().memory[5] = 0xA5;
+    harness.run();
+    assert!(harness.query_by_label("5A").is_some());
+    assert!(harness.query_by_label("A5").is_none());
+}
+
+#[test]
+fn invalidated_ranges_are_read_again() {
+    let mut state = State::new();
+    state.editor.options.refresh_interval = Some(std::time::Duration::MAX);
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.state_mut().memory[5] = 0xA5;
+    harness.state_mut().editor.invalidate_range(0..0x10);
+    harness.run();
+    assert!(harness.query_by_label("A5").is_some());
+}
+
+#[test]
+fn ascii_rows_are_not_read_again_until_the_next_refresh() {
+    let mut state = State::new();
+    state.editor.options.refresh_interval = Some(std::time::Duration::MAX);
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.state_mut().memory[6] = b'A';
+    harness.step();
+    let metrics = harness.state().editor.metrics();
+    assert_eq!(metrics.reads, 0);
+    assert_eq!(metrics.cache_hit_rate(), Some(1.0));
+    assert!(harness.query_by_label(".....Z..........").is_some());
+
+    harness.state_mut().editor.invalidate_range(0x6..0x7);
+    harness.run();
+    assert!(harness.query_by_label(".....ZA.........").is_some());
+}
+
+#[test]
+fn typing_into_a_scattered_selection_writes_every_selected_byte() {
+    let mut state = State::new();
+    state.memory[0x10] = 0x11;
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.input_mut().modifiers = egui::Modifiers::COMMAND;
+    harness.get_by_label("5A").click();
+    harness.run();
+    harness.get_by_label("11").click();
+    harness.run();
+    harness.input_mut().modifiers = egui::Modifiers::NONE;
+    harness.get_by_role(egui::accesskit::Role::TextInput).type_text("CD");
+    harness.run();
+
+    let memory = &harness.state().memory;
+    assert_eq!((memory[5], memory[0x10], memory[0x6]), (0xCD, 0xCD, 0x00));
+}
+
+#[test]
+fn copying_a_scattered_selection_copies_every_selected_byte() {
+    let mut harness = harness(State::new());
+    harness.state_mut().editor.scattered_selection_mut().extend([0x5, 0x20]);
+    harness.run();
+
+    harness.input_mut().events.push(egui::Event::Copy);
+    harness.step();
+
+    assert_eq!(harness.output().platform_output.copied_text, "0x5: 5A\n0x20: 00");
+}
+
+#[test]
+fn select_all_selects_the_region_or_the_visible_rows() {
+    let mut harness = harness(State::new());
+    harness.run();
+
+    harness.input_mut().modifiers = egui::Modifiers::COMMAND;
+    harness.press_key(egui::Key::A);
+    harness.run();
+    assert_eq!(harness.state().editor.selection(), Some(0..0x1000));
+
+    harness.input_mut().modifiers = egui::Modifiers::COMMAND | egui::Modifiers::SHIFT;
+    harness.press_key(egui::Key::A);
+    harness.run();
+    let editor = &harness.state().editor;
+    assert_eq!(editor.selection().as_ref(), Some(editor.visible_range()));
+}
+
+#[test]
+fn switching_regions_keeps_the_selection_of_each_region() {
+    use egui::accesskit::Role;
+
+    let mut state = State::new();
+    state.editor.set_address_range("IO", 0x800..0x900);
+    state.editor.options.is_options_collapsed = false;
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.input_mut().modifiers = egui::Modifiers::COMMAND;
+    harness.press_key(egui::Key::A);
+    harness.run();
+    harness.input_mut().modifiers = egui::Modifiers::NONE;
+    assert!(harness.state_mut().editor.open_link("IO@850"));
+    harness.run();
+    assert_eq!(harness.state().editor.selection(), Some(0x850..0x851));
+
+    // The region selection is the first combo box, its label is the preceding `Region:` text.
+    harness.query_all_by_role(Role::ComboBox).next().unwrap().click();
+    harness.run();
+    harness.get_by_label("RAM").click();
+    harness.run();
+    assert_eq!(harness.state().editor.selection(), Some(0..0x1000));
+
+    // The popup stays open until clicking elsewhere.
+    harness.get_by_label("IO").click();
+    harness.run();
+    assert_eq!(harness.state().editor.selection(), Some(0x850..0x851));
+}
+
+#[test]
+fn options_and_grid_can_be_drawn_separately() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    let mut harness = Harness::new_ui_state(
+        |ui, state: &mut State| {
+            let State { editor, memory } = state;
+            ui.horizontal(|ui| {
+                ui.vertical(|ui| {
+                    editor.draw_grid_only(
+                        ui,
+                        memory,
+                        |mem, address| mem.get(address).copied(),
+                        |mem, address, value| mem[address] = value,
+                    );
+                });
+                ui.vertical(|ui| {
+                    editor.draw_options_only_read_only(ui, memory, |mem, address| mem.get(address).copied());
+                });
+            });
+        },
+        state,
+    );
+    harness.run();
+
+    assert_eq!(harness.query_all_by_label("Show ASCII").count(), 1);
+    harness.get_by_label("5A").click();
+    harness.run();
+    // The goto field of the options is a text input as well, the inline editor is drawn first.
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .next()
+        .unwrap()
+        .type_text("AB");
+    harness.run();
+
+    assert_eq!(harness.state().memory[5], 0xAB);
+}
+
+#[test]
+fn option_setters_clamp_invalid_values() {
+    let mut harness = harness(State::new());
+    harness.state_mut().editor.set_column_count(0);
+    harness.state_mut().editor.set_show_ascii(false);
+    harness.run();
+
+    let editor = &harness.state().editor;
+    assert_eq!(editor.options.column_count, 1);
+    assert_eq!(editor.layout_metrics().bytes_per_row, 1);
+    assert!(!editor.layout_metrics().is_ascii_shown);
+    assert!(!harness.state_mut().editor.set_selected_address_range("Missing"));
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn empty_address_ranges_show_a_warning_instead_of_panicking() {
+    let mut state = State::new();
+    state.editor = MemoryEditor::new().with_address_range("Reversed", 0x10..0x5);
+    let mut harness = Harness::new_ui_state(
+        |ui, state: &mut State| {
+            let result = state
+                .editor
+                .try_draw_editor_contents_read_only(ui, &mut state.memory, |mem, address| mem.get(address).copied());
+            assert!(matches!(
+                result,
+                Err(egui_memory_editor::MemoryEditorError::EmptyAddressRange { .. })
+            ));
+            state.editor.draw_editor_contents_slice(ui, &mut state.memory);
+        },
+        state,
+    );
+    harness.run();
+
+    harness.get_by_label("⚠ The memory editor can't be drawn: the address range `Reversed` (0x10..0x5) is empty");
+}
+
+#[test]
+fn windows_without_address_ranges_return_an_error() {
+    let mut harness = Harness::new_state(
+        |ctx, editor: &mut MemoryEditor| {
+            let result =
+                editor.try_window_ui_read_only(ctx, &mut true, &mut [0u8; 4], |mem, address| mem.get(address).copied());
+            assert_eq!(result, Err(egui_memory_editor::MemoryEditorError::NoAddressRanges));
+        },
+        MemoryEditor::new(),
+    );
+    harness.run();
+}
+
+#[test]
+fn initial_address_scrolls_without_selecting() {
+    let mut state = State::new();
+    state.editor = state.editor.with_initial_address(0xC00);
+    let mut harness = harness(state);
+    harness.run();
+
+    let editor = &harness.state().editor;
+    assert!(editor.visible_range().contains(&0xC00));
+    assert_eq!(editor.selection(), None);
+}
+
+#[test]
+fn window_geometry_and_open_state_are_stored_in_the_options() {
+    let mut harness = Harness::new_state(
+        |ctx, state: &mut (MemoryEditor, bool)| {
+            let (editor, is_open) = state;
+            editor.window_ui_slice_read_only(ctx, is_open, &[0; 0x100]);
+        },
+        (MemoryEditor::new(), true),
+    );
+    harness.run();
+
+    let options = &harness.state().0.options;
+    assert!(options.is_window_open);
+    assert!(options.window_rect.is_some_and(|rect| rect.width() > 0.0));
+
+    harness.state_mut().1 = false;
+    harness.run();
+    assert!(!harness.state().0.options.is_window_open);
+}
+
+#[test]
+fn restored_windows_keep_their_size() {
+    let mut options = MemoryEditor::new().options;
+    let mut rects = Vec::new();
+    for _ in 0..3 {
+        let mut editor = MemoryEditor::new().with_address_range("All", 0..0x100);
+        editor.options = options;
+        let mut harness = Harness::new_state(
+            |ctx, editor: &mut MemoryEditor| {
+                editor.window_ui_slice_read_only(ctx, &mut true, &[0; 0x100]);
+            },
+            editor,
+        );
+        harness.run();
+        options = harness.state().options.clone();
+        rects.extend(options.window_rect);
+    }
+
+    assert_eq!(rects.len(), 3);
+    assert!(rects.windows(2).all(|pair| pair[0] == pair[1]), "{rects:?}");
+}
+
+#[test]
+fn columns_beyond_the_screen_are_hidden_without_changing_the_options() {
+    let mut editor = MemoryEditor::new().with_address_range("All", 0..0x1000);
+    editor.options.column_count = 64;
+    let mut harness = Harness::builder().with_size(egui::vec2(400.0, 300.0)).build_state(
+        |ctx, editor: &mut MemoryEditor| {
+            editor.window_ui_slice_read_only(ctx, &mut true, &[0; 0x1000]);
+        },
+        editor,
+    );
+    harness.run();
+
+    let editor = &harness.state();
+    assert!(editor.layout_metrics().bytes_per_row < 64);
+    assert_eq!(editor.options.column_count, 64);
+}
+
+#[test]
+fn views_share_the_writes_awaiting_confirmation() {
+    use egui_memory_editor::MemoryEditorView;
+
+    let mut state = State::new();
+    state.editor.options.confirm_writes = true;
+    let read = &mut |memory: &mut Vec<u8>, address: usize| memory.get(address).copied();
+    let write = &mut |memory: &mut Vec<u8>, address: usize, value| memory[address] = value;
+    state.editor.apply_bytes(&mut state.memory, read, write, 0x4, &[0xDE]);
+
+    let mut harness = Harness::new_ui_state(
+        |ui, (state, view): &mut (State, MemoryEditorView)| {
+            view.with(&mut state.editor, |editor| {
+                editor.draw_editor_contents_slice(ui, &mut state.memory)
+            });
+        },
+        (state, MemoryEditorView::new("Data")),
+    );
+    harness.run();
+
+    assert!(harness.query_by_label("Write 0xDE to 0x4?").is_some());
+}
+
+#[test]
+fn confirming_a_batch_of_writes_lists_its_ranges() {
+    let mut state = State::new();
+    state.editor.options.confirm_writes = true;
+    let read = &mut |memory: &mut Vec<u8>, address: usize| memory.get(address).copied();
+    let write = &mut |memory: &mut Vec<u8>, address: usize, value| memory[address] = value;
+    state
+        .editor
+        .apply_bytes(&mut state.memory, read, write, 0x4, &[0xDE, 0xAD]);
+    let mut harness = harness(state);
+    harness.run();
+    assert!(harness.query_by_label("Write 2 bytes to 0x4..0x6?").is_some());
+
+    harness.get_by_label("Cancel").click();
+    harness.run();
+    let state = harness.state_mut();
+    for address in [0x4, 0x5, 0x10] {
+        state
+            .editor
+            .apply_bytes(&mut state.memory, read, write, address, &[0xAA]);
+    }
+    harness.run();
+    assert!(harness
+        .query_by_label("Write 3 bytes to 2 separate ranges between 0x4 and 0x10?")
+        .is_some());
+}
+
+#[test]
+fn views_share_the_editor_but_keep_their_own_selection() {
+    use egui_memory_editor::MemoryEditorView;
+
+    let mut harness = Harness::new_state(
+        |ctx, (state, view): &mut (State, MemoryEditorView)| {
+            state.editor.window_ui_slice(ctx, &mut true, &mut state.memory);
+            view.with(&mut state.editor, |editor| {
+                editor.window_ui_slice(ctx, &mut true, &mut state.memory)
+            });
+        },
+        (State::new(), MemoryEditorView::new("Data")),
+    );
+    harness.state_mut().0.editor.queue_goto(0x5);
+    harness.run();
+
+    let (state, view) = harness.state_mut();
+    view.with(&mut state.editor, |editor| editor.queue_goto(0xA50));
+    harness.run();
+
+    let (state, view) = harness.state_mut();
+    assert_eq!(state.editor.selection(), Some(0x5..0x6));
+    assert_eq!(
+        view.with(&mut state.editor, |editor| editor.selection()),
+        Some(0xA50..0xA51)
+    );
+    assert!(view.with(&mut state.editor, |editor| editor.visible_range().contains(&0xA50)));
+}
+
+#[test]
+fn dropped_files_are_reported_in_the_response() {
+    let mut state = State::new();
+    state.editor.options.accept_dropped_files = true;
+    let mut harness = Harness::new_ui_state(
+        |ui, (state, dropped): &mut (State, Vec<egui::DroppedFile>)| {
+            let response = state.editor.draw_editor_contents_slice(ui, &mut state.memory);
+            dropped.extend(response.dropped_files);
+        },
+        (state, Vec::new()),
+    );
+    let file = egui::DroppedFile {
+        name: "rom.bin".to_string(),
+        bytes: Some(vec![1, 2, 3].into()),
+        ..Default::default()
+    };
+    harness.input_mut().dropped_files.push(file.clone());
+    harness.run();
+
+    let dropped = &harness.state().1;
+    assert_eq!(dropped, &[file]);
+    assert_eq!(
+        &*egui_memory_editor::read_dropped_file(&dropped[0]).unwrap(),
+        &[1, 2, 3]
+    );
+}
+
+#[test]
+fn text_search_finds_the_decoded_text_ignoring_case() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    state.memory[0x100..0x105].copy_from_slice(b"Hello");
+    state.memory[0x200..0x205].copy_from_slice(b"hELLO");
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("🔍 Search").click();
+    harness.run();
+    // The query is the last text input of the options, the inline editor isn't open.
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("hello");
+    harness.get_by_label("Search").click();
+    harness.run();
+    assert!(harness.query_by_label("0 matches of `hello` in 0x0..0x1000").is_some());
+
+    harness.get_by_label("Ignore case").click();
+    harness.get_by_label("Search").click();
+    harness.run();
+    assert!(harness.query_by_label("2 matches of `hello` in 0x0..0x1000").is_some());
+    assert!(harness.query_by_label("0x100").is_some());
+    assert!(harness.query_by_label("0x200").is_some());
+    // The editor goes to the first match once the search finishes.
+    assert!(harness.query_all_by_label("Match 1 of 2").next().is_some());
+    assert_eq!(harness.state().editor.selection(), Some(0x100..0x101));
+
+    harness.get_by_label("⏷").click();
+    harness.run();
+    assert!(harness.query_all_by_label("Match 2 of 2").next().is_some());
+    assert!(harness.state().editor.visible_range().contains(&0x200));
+}
+
+#[test]
+#[cfg(feature = "regex")]
+fn regex_search_matches_the_decoded_text() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    state.memory[0x100..0x106].copy_from_slice(b"v1.2.3");
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("🔍 Search").click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text(r"v\d(\.\d)+");
+    harness.get_by_label("Regex").click();
+    harness.get_by_label("Search").click();
+    harness.run();
+
+    assert!(harness
+        .query_by_label(r"1 matches of `v\d(\.\d)+` in 0x0..0x1000")
+        .is_some());
+}
+
+#[test]
+#[cfg(feature = "regex")]
+fn regex_search_of_a_large_slice_finds_matches_across_windows() {
+    let mut memory = vec![0; 0x30000];
+    memory[0xFFFE..0x10004].copy_from_slice(b"v1.2.3");
+    memory[0x10FFE..0x11004].copy_from_slice(b"v4.5.6");
+    memory[0x2FFFA..].copy_from_slice(b"v7.8.9");
+    let mut editor = MemoryEditor::new().with_address_range("RAM", 0..0x30000);
+    editor.options.is_options_collapsed = false;
+    let mut harness = harness(State { editor, memory });
+    harness.run();
+
+    harness.get_by_label("🔍 Search").click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text(r"v\d(\.\d)+");
+    harness.get_by_label("Regex").click();
+    harness.get_by_label("Search").click();
+    harness.run();
+
+    assert!(harness
+        .query_by_label(r"3 matches of `v\d(\.\d)+` in 0x0..0x30000")
+        .is_some());
+    assert!(harness.query_by_label("0xFFFE").is_some());
+    assert!(harness.query_by_label("0x10FFE").is_some());
+    assert!(harness.query_by_label("0x2FFFA").is_some());
+}
+
+#[test]
+fn locations_list_markers_and_modified_bytes_with_filters() {
+    let mut harness = harness(State::new());
+    harness.state_mut().editor.set_marker(0x40, true);
+    harness.run();
+
+    harness.get_by_label("5A").click();
+    harness.run();
+    harness.get_by_role(egui::accesskit::Role::TextInput).type_text("AB");
+    harness.run();
+
+    harness.get_by_label("🛠 Options").click();
+    harness.run();
+    harness.get_by_label("📍 Locations").click();
+    harness.run();
+    assert!(harness.query_by_label("0x5").is_some());
+    assert!(harness.query_by_label("0x40").is_some());
+
+    harness
+        .query_all_by_role(egui::accesskit::Role::CheckBox)
+        .find(|node| node.label().as_deref() == Some("Marker"))
+        .unwrap()
+        .click();
+    harness.run();
+    assert!(harness.query_by_label("0x5").is_some());
+    assert!(harness.query_by_label("0x40").is_none());
+}
+
+#[test]
+fn interesting_byte_jump_skips_padding_and_runs() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    state.memory[0x300..0x302].copy_from_slice(&[0x11, 0x11]);
+    state.memory[0xA00] = 0x22;
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("⏭").click();
+    harness.run();
+    assert_eq!(harness.state().editor.selection(), Some(0x5..0x6));
+
+    harness.get_by_label("⏭").click();
+    harness.run();
+    assert_eq!(harness.state().editor.selection(), Some(0xA00..0xA01));
+    assert!(harness.state().editor.visible_range().contains(&0xA00));
+}
+
+#[test]
+fn memory_rows_use_the_formatting_of_the_editor() {
+    let editor = MemoryEditor::new();
+    let harness = Harness::new_ui(move |ui| {
+        let bytes = [0xDE, 0xAD, 0x41, 0x42];
+        ui.add(
+            egui_memory_editor::MemoryRow::from_slice(&editor.options, 0x8000, &bytes)
+                .highlight(0x8001..0x8002)
+                .annotation("Crash site"),
+        );
+    });
+
+    assert!(harness.query_by_label("0x8000:").is_some());
+    assert!(harness.query_by_label("AD").is_some());
+    assert!(harness.query_by_label("..AB").is_some());
+    assert!(harness.query_by_label("Crash site").is_some());
+}
+
+#[test]
+fn memory_rows_stop_at_the_end_of_the_address_space() {
+    let editor = MemoryEditor::new();
+    let harness = Harness::new_ui(move |ui| {
+        let bytes = [0xDE, 0xAD, 0x41, 0x42];
+        ui.add(egui_memory_editor::MemoryRow::from_slice(
+            &editor.options,
+            usize::MAX - 1,
+            &bytes,
+        ));
+    });
+
+    assert!(harness.query_by_label("AD").is_some());
+    assert!(harness.query_by_label("41").is_none());
+    assert!(harness.query_by_label("..").is_some());
+}
+
+#[test]
+fn hex_search_goes_to_the_first_match() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    state.memory[0x800..0x804].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("🔍 Search").click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("DE AD BE EF");
+    harness.get_by_label("Hex").click();
+    harness.get_by_label("Search").click();
+    harness.run();
+
+    assert!(harness.query_all_by_label("Match 1 of 1").next().is_some());
+    assert_eq!(harness.state().editor.selection(), Some(0x800..0x801));
+    assert!(harness.state().editor.visible_range().contains(&0x800));
+}
+
+#[test]
+fn hex_search_wildcards_match_any_byte() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    state.memory[0x300..0x305].copy_from_slice(&[0x48, 0x8B, 0x12, 0x34, 0xE8]);
+    state.memory[0x700..0x705].copy_from_slice(&[0x48, 0x8B, 0xFF, 0x00, 0xE8]);
+    state.memory[0x900..0x905].copy_from_slice(&[0x48, 0x8B, 0xFF, 0x00, 0xE9]);
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("🔍 Search").click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("48 8B ?? ?? E8");
+    harness.get_by_label("Hex").click();
+    harness.get_by_label("Search").click();
+    harness.run();
+
+    assert!(harness.query_all_by_label("Match 1 of 2").next().is_some());
+    assert!(harness.query_by_label("0x300").is_some());
+    assert!(harness.query_by_label("0x700").is_some());
+    assert!(harness.query_by_label("0x900").is_none());
+}
+
+#[test]
+fn data_inspector_shows_every_format() {
+    let harness = Harness::new_ui(|ui| {
+        ui.add(
+            egui_memory_editor::DataInspector::new(&[0xFE, 0xFF])
+                .endianness(egui_memory_editor::option_data::Endianness::Big),
+        );
+    });
+
+    assert!(harness.query_by_label("254").is_some());
+    assert!(harness.query_by_label("65279").is_some());
+    assert!(harness.query_by_label("-257").is_some());
+    // Two bytes aren't enough for the larger formats.
+    assert_eq!(harness.query_all_by_label("--").count(), 6);
+}
+
+#[test]
+fn enter_in_the_find_field_cycles_through_the_matches() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    state.memory[0x100..0x106].copy_from_slice(b"PLAYER");
+    state.memory[0x900..0x906].copy_from_slice(b"PLAYER");
+    let mut harness = harness(state);
+    harness.run();
+
+    // The goto field comes first, followed by the find field.
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .nth(1)
+        .unwrap()
+        .click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .nth(1)
+        .unwrap()
+        .type_text("PLAYER");
+    harness.run();
+    harness.press_key(egui::Key::Enter);
+    harness.run();
+    assert_eq!(harness.state().editor.selection(), Some(0x100..0x101));
+    assert!(harness.query_all_by_label("Match 1 of 2").next().is_some());
+
+    harness.press_key(egui::Key::Enter);
+    harness.run();
+    assert_eq!(harness.state().editor.selection(), Some(0x900..0x901));
+    assert!(harness.query_all_by_label("Match 2 of 2").next().is_some());
+}
+
+#[test]
+fn address_input_resolves_sums_and_offsets() {
+    let mut harness = Harness::new_ui_state(
+        |ui, (text, address): &mut (String, Option<usize>)| {
+            let input = egui_memory_editor::AddressInput::new(text, 0xFF00..0x10000).show(ui);
+            if input.is_submitted {
+                *address = input.address;
+            }
+        },
+        (String::new(), None),
+    );
+
+    // A successful submit replaces the text by the absolute address.
+    for (input, expected, text) in [
+        ("FF00 + 10 - 2", Some(0xFF0E), "FF0E"),
+        ("0x5", Some(0xFF05), "FF05"),
+        ("5 - 6", None, "5 - 6"),
+    ] {
+        harness.state_mut().0.clear();
+        harness.get_by_role(egui::accesskit::Role::TextInput).type_text(input);
+        harness.run();
+        harness.press_key(egui::Key::Enter);
+        harness.run();
+
+        assert_eq!(harness.state().1, expected, "{input}");
+        assert_eq!(harness.state().0, text);
+    }
+}
+
+#[test]
+fn goto_outside_the_region_shows_an_error_or_clamps() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    let mut harness = harness(state);
+    harness.run();
+
+    // The text is kept after a failed goto, such that it can be corrected.
+    let goto = |harness: &mut Harness<'_, State>, text: &str| {
+        let input = harness
+            .query_all_by_role(egui::accesskit::Role::TextInput)
+            .next()
+            .unwrap();
+        input.click();
+        harness.run();
+        harness
+            .query_all_by_role(egui::accesskit::Role::TextInput)
+            .next()
+            .unwrap()
+            .type_text(text);
+        harness.run();
+        harness.press_key(egui::Key::Enter);
+        harness.run();
+    };
+
+    goto(&mut harness, "2000");
+    assert!(harness
+        .query_by_label("Address 0x2000 is outside 0x0..0x1000")
+        .is_some());
+    assert_eq!(harness.state().editor.selection(), None);
+
+    harness.state_mut().editor.options.clamp_goto = true;
+    goto(&mut harness, "");
+    assert_eq!(harness.state().editor.selection(), Some(0xFFF..0x1000));
+}
+
+#[test]
+fn value_search_finds_the_encoded_value() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    state.editor.options.data_preview = DataPreviewOptions {
+        selected_endianness: Endianness::Little,
+        selected_data_format: DataFormatType::U16,
+    };
+    state.memory[0x400..0x402].copy_from_slice(&1000u16.to_le_bytes());
+    state.memory[0xA00..0xA02].copy_from_slice(&1000u16.to_le_bytes());
+    // The big endian encoding isn't a match.
+    state.memory[0x600..0x602].copy_from_slice(&1000u16.to_be_bytes());
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("⛃ Data Preview").click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("1000");
+    harness.get_by_label("Find value").click();
+    harness.run();
+
+    assert!(harness.query_all_by_label("Match 1 of 2").next().is_some());
+    assert_eq!(harness.state().editor.selection(), Some(0x400..0x401));
+
+    harness.get_by_label("Find value").click();
+    harness.run();
+    assert_eq!(harness.state().editor.selection(), Some(0xA00..0xA01));
+}
+
+#[test]
+fn editing_the_data_preview_writes_the_encoded_value() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    state.editor.options.data_preview = DataPreviewOptions {
+        selected_endianness: Endianness::Big,
+        selected_data_format: DataFormatType::I16,
+    };
+    state.memory[0x100..0x102].copy_from_slice(&[0x00, 0x07]);
+    let mut harness = harness(state);
+    harness.state_mut().editor.queue_goto(0x100);
+    harness.run();
+
+    harness.get_by_label("⛃ Data Preview").click();
+    harness.run();
+    harness.get_by_label("7").click();
+    harness.run();
+
+    let input = harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .find(|input| input.value().as_deref() == Some("7"))
+        .unwrap();
+    input.key_press(egui_kittest::kittest::Key::Backspace);
+    input.type_text("-2");
+    input.key_press(egui_kittest::kittest::Key::Enter);
+    harness.run();
+
+    assert_eq!(harness.state().memory[0x100..0x102], (-2i16).to_be_bytes());
+}
+
+#[test]
+fn replacing_search_matches_writes_the_replacement() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    for start in [0x100, 0x200, 0x300] {
+        state.memory[start..start + 5].copy_from_slice(b"HELLO");
+    }
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("🔍 Search").click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("HELLO");
+    harness.get_by_label("Search").click();
+    harness.run();
+
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("WORLD");
+    harness.run();
+    harness.get_by_label("Replace").click();
+    harness.run();
+
+    let memory = &harness.state().memory;
+    assert_eq!(
+        (&memory[0x100..0x105], &memory[0x200..0x205]),
+        (&b"WORLD"[..], &b"HELLO"[..])
+    );
+    assert!(harness.query_all_by_label("Match 1 of 2").next().is_some());
+
+    harness.get_by_label("Replace all").click();
+    harness.run();
+
+    let memory = &harness.state().memory;
+    assert_eq!(
+        (&memory[0x200..0x205], &memory[0x300..0x305]),
+        (&b"WORLD"[..], &b"WORLD"[..])
+    );
+}
+
+#[test]
+fn scroll_position_shows_the_first_visible_address() {
+    let mut state = State::new();
+    state.editor.options.show_scroll_position = true;
+    let mut harness = harness(state);
+    harness.run();
+    assert!(harness.query_by_label("0x0 (0%)").is_some());
+
+    harness.state_mut().editor.queue_goto(0x800);
+    harness.run();
+    let start = harness.state().editor.visible_range().start;
+    let percentage = start as f64 * 100.0 / 0x1000 as f64;
+    assert!(start > 0);
+    assert!(harness
+        .query_by_label(&format!("{start:#X} ({percentage:.0}%)"))
+        .is_some());
+}
+
+#[test]
+fn search_results_show_the_bytes_around_each_match() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    state.memory[0x100..0x109].copy_from_slice(b"SAY HI!!!");
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("🔍 Search").click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("HI");
+    harness.get_by_label("Search").click();
+    harness.run();
+
+    assert!(harness.query_by_label("Matches").is_some());
+    assert!(harness.query_by_label("53 41 59 20").is_some());
+    assert!(harness.query_by_label("48 49").is_some());
+    assert!(harness.query_by_label("21 21 21 00").is_some());
+    assert!(harness.query_by_label("SAY HI!!!.").is_some());
+}
+
+#[test]
+fn placeholders_within_filled_in_values_are_kept() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    state.memory[0x100..0x107].copy_from_slice(b"{range}");
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("🔍 Search").click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("{range}");
+    harness.get_by_label("Search").click();
+    harness.run();
+
+    assert!(harness
+        .query_by_label("1 matches of `{range}` in 0x0..0x1000")
+        .is_some());
+}
+
+#[test]
+fn search_results_at_the_end_of_the_address_space_are_shown() {
+    let mut editor = MemoryEditor::new().with_address_range("High", usize::MAX - 0x10..usize::MAX);
+    editor.options.is_options_collapsed = false;
+    let mut harness = Harness::new_ui_state(
+        |ui, editor: &mut MemoryEditor| {
+            editor.draw_editor_contents_read_only(ui, &mut (), |_, address| {
+                Some(if address >= usize::MAX - 2 { b'!' } else { 0 })
+            });
+        },
+        editor,
+    );
+    harness.run();
+
+    harness.get_by_label("🔍 Search").click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("!!");
+    harness.get_by_label("Search").click();
+    harness.run();
+
+    assert!(harness.query_by_label("Matches").is_some());
+    assert!(harness.query_by_label("21 21").is_some());
+}
+
+#[test]
+fn find_shortcuts_cycle_through_the_matches() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    for start in [0x100, 0x200, 0x300] {
+        state.memory[start..start + 4].copy_from_slice(b"GOAL");
+    }
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("🔍 Search").click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("GOAL");
+    harness.get_by_label("Search").click();
+    harness.run();
+    assert_eq!(harness.state().editor.selection(), Some(0x100..0x101));
+
+    harness.press_key(egui::Key::F3);
+    harness.run();
+    assert_eq!(harness.state().editor.selection(), Some(0x200..0x201));
+
+    for pressed in [true, false] {
+        harness.input_mut().events.push(egui::Event::Key {
+            key: egui::Key::F3,
+            physical_key: None,
+            pressed,
+            repeat: false,
+            modifiers: egui::Modifiers::SHIFT,
+        });
+    }
+    harness.run();
+    assert_eq!(harness.state().editor.selection(), Some(0x100..0x101));
+}
+
+#[test]
+fn searching_all_regions_lists_the_matches_of_every_region() {
+    let mut state = State::new();
+    state.editor = state.editor.with_address_range("VRAM", 0x800..0x1000);
+    state.editor.options.is_options_collapsed = false;
+    state.memory[0x100..0x104].copy_from_slice(b"TILE");
+    state.memory[0x900..0x904].copy_from_slice(b"TILE");
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("🔍 Search").click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("TILE");
+    harness.get_by_label("All regions").click();
+    harness.get_by_label("Search").click();
+    harness.run();
+
+    assert!(harness.query_all_by_label("Match 1 of 2").next().is_some());
+    harness.get_by_label("VRAM: 1 matches").click();
+    harness.run();
+    harness.query_all_by_label("0x900").last().unwrap().click();
+    harness.run();
+    harness.run();
+
+    let editor = &harness.state().editor;
+    assert_eq!(editor.goto_link(), "VRAM@0x900");
+    assert_eq!(editor.selection(), Some(0x900..0x901));
+}
+
+#[test]
+fn hex_search_of_a_large_slice_finds_matches_across_chunks() {
+    let mut memory = vec![0; 0x30000];
+    memory[0xFFFE..0x10002].copy_from_slice(&[0xCA, 0xFE, 0xBA, 0xBE]);
+    memory[0x2FFFC..].copy_from_slice(&[0xCA, 0xFE, 0xBA, 0xBE]);
+    let editor = MemoryEditor::new().with_address_range("RAM", 0..0x30000);
+    let mut harness = harness(State { editor, memory });
+    harness.run();
+
+    harness.get_by_label("🔍 Search").click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("CA FE ?? BE");
+    harness.get_by_label("Hex").click();
+    harness.get_by_label("Search").click();
+    harness.run();
+
+    assert!(harness.query_all_by_label("Match 1 of 2").next().is_some());
+    assert!(harness.query_by_label("0xFFFE").is_some());
+    assert!(harness.query_by_label("0x2FFFC").is_some());
+}
+
+#[test]
+fn the_context_menu_of_an_address_copies_its_row() {
+    let mut harness = harness(State::new());
+    harness.run();
+
+    let bounds = harness.get_by_label("0x000:").bounding_box().unwrap();
+    let pos = egui::pos2(bounds.x0 as f32 + 2.0, bounds.y0 as f32 + 2.0);
+    harness.input_mut().events.push(egui::Event::PointerMoved(pos));
+    for pressed in [true, false] {
+        harness.input_mut().events.push(egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Secondary,
+            pressed,
+            modifiers: egui::Modifiers::NONE,
+        });
+    }
+    harness.run();
+
+    harness.get_by_label("Copy row").click();
+    harness.step();
+
+    assert_eq!(
+        harness.output().platform_output.copied_text,
+        "0x000: 00 00 00 00 00 5A 00 00  00 00 00 00 00 00 00 00 | .....Z.........."
+    );
+
+    harness.state_mut().editor.options.layout_direction = egui_memory_editor::option_data::LayoutDirection::RightToLeft;
+    harness.run();
+    right_click(&mut harness, "0x000:");
+    harness.get_by_label("Copy row").click();
+    harness.step();
+
+    assert_eq!(
+        harness.output().platform_output.copied_text,
+        ".....Z.......... | 00 00 00 00 00 5A 00 00  00 00 00 00 00 00 00 00 0x000:"
+    );
+}
+
+#[test]
+fn fill_diff_can_be_enabled_from_the_compare_section() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("🔍 Compare").click();
+    harness.run();
+    harness.get_by_label("Highlight bytes differing from").click();
+    harness.run();
+    assert_eq!(harness.state().editor.options.fill_diff_value, Some(0x00));
+
+    harness.get_by_label("FF").click();
+    harness.run();
+    assert_eq!(harness.state().editor.options.fill_diff_value, Some(0xFF));
+
+    harness.get_by_label("Highlight bytes differing from").click();
+    harness.run();
+    assert_eq!(harness.state().editor.options.fill_diff_value, None);
+}
+
+#[test]
+fn rescanning_a_value_search_keeps_the_matches_which_changed_that_way() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    state.editor.options.data_preview = DataPreviewOptions {
+        selected_endianness: Endianness::Little,
+        selected_data_format: DataFormatType::U16,
+    };
+    for address in [0x200, 0x400, 0xA00] {
+        state.memory[address..address + 2].copy_from_slice(&1000u16.to_le_bytes());
+    }
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("⛃ Data Preview").click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("1000");
+    harness.get_by_label("Find value").click();
+    harness.run();
+
+    let memory = &mut harness.state_mut().memory;
+    memory[0x400..0x402].copy_from_slice(&1200u16.to_le_bytes());
+    memory[0xA00..0xA02].copy_from_slice(&900u16.to_le_bytes());
+    harness.get_by_label("Changed").click();
+    harness.run();
+    assert!(harness.query_by_label("0x200").is_none());
+    assert!(harness.query_by_label("0x400").is_some());
+    assert!(harness.query_by_label("0xA00").is_some());
+
+    // Later rescans compare against the values of the previous one.
+    harness.state_mut().memory[0xA00..0xA02].copy_from_slice(&950u16.to_le_bytes());
+    harness.get_by_label("Increased").click();
+    harness.run();
+    assert!(harness.query_by_label("0x400").is_none());
+    assert!(harness.query_by_label("0xA00").is_some());
+}
+
+#[test]
+fn rescans_of_a_buffered_search_compare_signed_values() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    state.editor.options.data_preview = DataPreviewOptions {
+        selected_endianness: Endianness::Little,
+        selected_data_format: DataFormatType::I16,
+    };
+    state.editor.set_task_bytes_per_frame(64);
+    for address in [0x200, 0x400] {
+        state.memory[address..address + 2].copy_from_slice(&(-1i16).to_le_bytes());
+    }
+    let mut harness = Harness::new_ui_state(
+        |ui, state: &mut State| {
+            state.editor.draw_editor_contents_buffered(
+                ui,
+                &mut state.memory,
+                |memory, address, length| memory.get(address..(address + length).min(memory.len())),
+                |memory, address, value| memory[address] = value,
+            );
+        },
+        state,
+    );
+    harness.run();
+
+    harness.get_by_label("⛃ Data Preview").click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("-1");
+    harness.get_by_label("Find value").click();
+    for _ in 0..0x1000 / 64 + 2 {
+        harness.step();
+    }
+    assert!(harness.query_by_label("0x200").is_some());
+
+    let memory = &mut harness.state_mut().memory;
+    memory[0x200..0x202].copy_from_slice(&(-2i16).to_le_bytes());
+    memory[0x400..0x402].copy_from_slice(&1i16.to_le_bytes());
+    harness.get_by_label("Decreased").click();
+    harness.step();
+    harness.step();
+    harness.step();
+    assert!(harness.query_by_label("0x200").is_some());
+    assert!(harness.query_by_label("0x400").is_none());
+}
+
+#[test]
+fn dragging_across_values_selects_the_range() {
+    let mut state = State::new();
+    state.memory[0x2] = 0x11;
+    state.memory[0x19] = 0x22;
+    let mut harness = harness(state);
+    harness.run();
+
+    let centre = |harness: &Harness<'_, State>, label: &str| {
+        let bounds = harness.get_by_label(label).bounding_box().unwrap();
+        egui::pos2(
+            (bounds.x0 + bounds.x1) as f32 / 2.0,
+            (bounds.y0 + bounds.y1) as f32 / 2.0,
+        )
+    };
+    let (start, end) = (centre(&harness, "11"), centre(&harness, "22"));
+    let button = |pos, pressed| egui::Event::PointerButton {
+        pos,
+        button: egui::PointerButton::Primary,
+        pressed,
+        modifiers: egui::Modifiers::NONE,
+    };
+
+    harness.input_mut().events.push(egui::Event::PointerMoved(start));
+    harness.input_mut().events.push(button(start, true));
+    harness.run();
+    for step in 1..=4 {
+        let pos = start + (end - start) * step as f32 / 4.0;
+        harness.input_mut().events.push(egui::Event::PointerMoved(pos));
+        harness.run();
+    }
+    harness.input_mut().events.push(button(end, false));
+    harness.run();
+
+    assert_eq!(harness.state().editor.selection(), Some(0x2..0x1A));
+}
+
+#[test]
+fn derived_columns_show_the_text_of_every_row() {
+    let mut state = State::new();
+    state.editor = state.editor.with_derived_column("Sum", |start, read| {
+        let sum: u32 = (start..start + 16).filter_map(&mut *read).map(u32::from).sum();
+        format!("Sum {sum:X} at {start:X}")
+    });
+    state.memory[0x11] = 0x20;
+    let mut harness = harness(state);
+    harness.run();
+
+    assert!(harness.query_by_label("Sum 5A at 0").is_some());
+    assert!(harness.query_by_label("Sum 20 at 10").is_some());
+}
+
+#[test]
+fn writes_beyond_the_budget_of_a_frame_are_queued() {
+    let mut state = State::new();
+    state.memory[0x10] = 0x11;
+    state.memory[0x20] = 0x22;
+    state.editor.options.writes_per_frame = Some(1);
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.input_mut().modifiers = egui::Modifiers::COMMAND;
+    for label in ["5A", "11", "22"] {
+        harness.get_by_label(label).click();
+        harness.run();
+    }
+    harness.input_mut().modifiers = egui::Modifiers::NONE;
+    harness.get_by_role(egui::accesskit::Role::TextInput).type_text("CD");
+    harness.step();
+    let written = |harness: &Harness<'_, State>| [0x5, 0x10, 0x20].map(|address| harness.state().memory[address]);
+    assert_eq!(written(&harness), [0xCD, 0x11, 0x22]);
+
+    harness.step();
+    assert_eq!(written(&harness), [0xCD, 0xCD, 0x22]);
+    assert!(harness.query_by_label("1 writes queued").is_some());
+
+    harness.run();
+    assert_eq!(written(&harness), [0xCD, 0xCD, 0xCD]);
+    assert!(harness.query_by_label("1 writes queued").is_none());
+}
+
+#[test]
+fn a_write_budget_of_zero_still_performs_writes() {
+    let mut state = State::new();
+    state.editor.options.writes_per_frame = Some(0);
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("5A").click();
+    harness.run();
+    harness.get_by_role(egui::accesskit::Role::TextInput).type_text("CD");
+    harness.run();
+
+    assert_eq!(harness.state().memory[0x5], 0xCD);
+    assert!(harness.query_by_label("1 writes queued").is_none());
+}
+
+#[test]
+fn shift_clicking_a_value_extends_the_selection() {
+    let mut state = State::new();
+    state.memory[0x19] = 0x22;
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("5A").click();
+    harness.run();
+    harness.input_mut().modifiers = egui::Modifiers::SHIFT;
+    harness.get_by_label("22").click();
+    harness.run();
+
+    assert_eq!(harness.state().editor.selection(), Some(0x5..0x1A));
+}
+
+#[test]
+fn shift_clicking_a_character_extends_the_selection() {
+    let mut state = State::new();
+    state.memory[0x19] = b'Q';
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("5A").click();
+    harness.run();
+    // Rows outside the selection are a single label, so click where the tenth character is drawn.
+    let bounds = harness.get_by_label(".........Q......").bounding_box().unwrap();
+    let x = bounds.x0 + (bounds.x1 - bounds.x0) * 9.5 / 16.0;
+    let pos = egui::pos2(x as f32, (bounds.y0 + bounds.y1) as f32 / 2.0);
+    harness.input_mut().events.push(egui::Event::PointerMoved(pos));
+    for pressed in [true, false] {
+        harness.input_mut().events.push(egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: egui::Modifiers::SHIFT,
+        });
+    }
+    harness.input_mut().modifiers = egui::Modifiers::SHIFT;
+    harness.run();
+
+    assert_eq!(harness.state().editor.selection(), Some(0x5..0x1A));
+}
+
+#[test]
+fn only_selected_ascii_rows_are_drawn_per_character() {
+    let mut harness = harness(State::new());
+    harness.run();
+
+    harness.get_by_label("5A").click();
+    harness.run();
+
+    assert!(harness.query_by_label("Z").is_some());
+    assert!(harness.query_all_by_label("................").count() > 0);
+}
+
+#[test]
+fn bytes_applied_by_the_host_are_recorded_like_edits() {
+    let mut state = State::new();
+    let read = &mut |memory: &mut Vec<u8>, address: usize| memory.get(address).copied();
+    let write = &mut |memory: &mut Vec<u8>, address: usize, value| memory[address] = value;
+    state
+        .editor
+        .apply_bytes(&mut state.memory, read, write, 0x4, &[0xDE, 0xAD]);
+
+    assert_eq!(state.memory[0x4..0x6], [0xDE, 0xAD]);
+    let history = state.editor.edit_history().writes();
+    assert_eq!(history.len(), 2);
+    assert_eq!(
+        (history[1].address, history[1].previous, history[1].value),
+        (0x5, Some(0x5A), 0xAD)
+    );
+}
+
+/// Right click the widget with `label`, opening its context menu.
+fn right_click(harness: &mut Harness<'_, State>, label: &str) {
+    let bounds = harness.get_by_label(label).bounding_box().unwrap();
+    let pos = egui::pos2(bounds.x0 as f32 + 2.0, bounds.y0 as f32 + 2.0);
+    harness.input_mut().events.push(egui::Event::PointerMoved(pos));
+    for pressed in [true, false] {
+        harness.input_mut().events.push(egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Secondary,
+            pressed,
+            modifiers: egui::Modifiers::NONE,
+        });
+    }
+    harness.run();
+}
+
+#[test]
+fn copying_a_range_copies_space_separated_hex() {
+    let mut state = State::new();
+    state.memory[0x4..0x8].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("DE").click();
+    harness.run();
+    harness.input_mut().modifiers = egui::Modifiers::SHIFT;
+    harness.get_by_label("EF").click();
+    harness.run();
+    harness.input_mut().modifiers = egui::Modifiers::NONE;
+    harness.input_mut().events.push(egui::Event::Copy);
+    harness.step();
+    assert_eq!(harness.output().platform_output.copied_text, "DE AD BE EF");
+
+    right_click(&mut harness, "AD");
+    harness.get_by_label("Copy as hex").click();
+    harness.step();
+    assert_eq!(harness.output().platform_output.copied_text, "DE AD BE EF");
+}
+
+#[test]
+fn undo_reverts_every_byte_of_the_last_operation() {
+    let mut state = State::new();
+    let read = &mut |memory: &mut Vec<u8>, address: usize| memory.get(address).copied();
+    let write = &mut |memory: &mut Vec<u8>, address: usize, value| memory[address] = value;
+    state.editor.apply_bytes(&mut state.memory, read, write, 0x10, &[0x11]);
+    state
+        .editor
+        .apply_bytes(&mut state.memory, read, write, 0x4, &[0xDE, 0xAD, 0xBE, 0xEF]);
+    assert_eq!(state.editor.edit_history().groups().count(), 2);
+    let mut harness = harness(state);
+    harness.run();
+
+    for pressed in [true, false] {
+        harness.input_mut().events.push(egui::Event::Key {
+            key: egui::Key::Z,
+            physical_key: None,
+            pressed,
+            repeat: false,
+            modifiers: egui::Modifiers::COMMAND,
+        });
+    }
+    harness.run();
+
+    let state = harness.state();
+    assert_eq!(state.memory[0x4..0x8], [0x00, 0x5A, 0x00, 0x00]);
+    assert_eq!(state.memory[0x10], 0x11);
+    // The revert is an operation of its own.
+    assert_eq!(state.editor.edit_history().groups().count(), 2);
+    assert_eq!(state.editor.edit_history().changes().get(&0x5), Some(&0x5A));
+}
+
+#[test]
+fn switching_profiles_keeps_editing_the_selected_value() {
+    let mut state = State::new();
+    state.editor.options.cell_format = egui_memory_editor::option_data::CellFormat::Nibble;
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("A").click();
+    harness.run();
+    assert!(harness.query_by_role(egui::accesskit::Role::TextInput).is_some());
+
+    let mut profile =
+        egui_memory_editor::option_data::LayoutProfile::from_options("Bytes", &harness.state().editor.options);
+    profile.cell_format = egui_memory_editor::option_data::CellFormat::Byte;
+    harness.state_mut().editor.apply_layout_profile(&profile);
+    harness.run();
+    assert!(harness.query_by_role(egui::accesskit::Role::TextInput).is_some());
+}
+
+#[test]
+fn undo_asks_for_confirmation_when_writes_are_confirmed() {
+    let mut state = State::new();
+    let read = &mut |memory: &mut Vec<u8>, address: usize| memory.get(address).copied();
+    let write = &mut |memory: &mut Vec<u8>, address: usize, value| memory[address] = value;
+    state
+        .editor
+        .apply_bytes(&mut state.memory, read, write, 0x4, &[0xDE, 0xAD]);
+    state.editor.options.confirm_writes = true;
+    let mut harness = harness(state);
+    harness.run();
+
+    for pressed in [true, false] {
+        harness.input_mut().events.push(egui::Event::Key {
+            key: egui::Key::Z,
+            physical_key: None,
+            pressed,
+            repeat: false,
+            modifiers: egui::Modifiers::COMMAND,
+        });
+    }
+    harness.run();
+    assert_eq!(harness.state().memory[0x4..0x6], [0xDE, 0xAD]);
+    assert!(harness.query_by_label("Write 2 bytes to 0x4..0x6?").is_some());
+
+    harness.get_by_label("Write").click();
+    harness.run();
+    assert_eq!(harness.state().memory[0x4..0x6], [0x00, 0x5A]);
+}
+
+#[test]
+fn replacements_are_cut_off_at_the_end_of_the_region() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    let end = state.memory.len();
+    state.memory[end - 2..].copy_from_slice(b"HI");
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("🔍 Search").click();
+    harness.run();
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("HI");
+    harness.get_by_label("Search").click();
+    harness.run();
+
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("HELLO");
+    harness.run();
+    harness.get_by_label("Replace all").click();
+    harness.run();
+
+    let memory = &harness.state().memory;
+    assert_eq!(&memory[end - 2..], b"HE");
+    assert!(harness
+        .query_by_label("1 of the replaced matches were cut off at the end of the searched range")
+        .is_some());
+}
+
+#[test]
+fn copying_a_range_as_text_replaces_non_printable_bytes() {
+    let mut state = State::new();
+    state.memory[0x4..0x8].copy_from_slice(&[b'H', b'i', 0x01, b'!']);
+    state.editor.options.copy_text_placeholder = '_';
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("48").click();
+    harness.run();
+    harness.input_mut().modifiers = egui::Modifiers::SHIFT;
+    harness.get_by_label("21").click();
+    harness.run();
+    harness.input_mut().modifiers = egui::Modifiers::NONE;
+
+    right_click(&mut harness, "69");
+    harness.get_by_label("Copy as text").click();
+    harness.step();
+    assert_eq!(harness.output().platform_output.copied_text, "Hi_!");
+}
+
+#[test]
+fn rejected_input_is_passed_to_the_callback() {
+    let rejections = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut state = State::new();
+    let callback_rejections = rejections.clone();
+    state.editor = state
+        .editor
+        .with_edit_permission(|address, _| address != 0x10)
+        .with_rejected_input_callback(move |rejection| callback_rejections.lock().unwrap().push(rejection));
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("5A").click();
+    harness.run();
+    harness.get_by_role(egui::accesskit::Role::TextInput).type_text("G");
+    harness.run();
+    assert_eq!(harness.state().memory[5], 0x5A);
+
+    let state = harness.state_mut();
+    let read = &mut |memory: &mut Vec<u8>, address: usize| memory.get(address).copied();
+    let write = &mut |memory: &mut Vec<u8>, address: usize, value| memory[address] = value;
+    state.editor.apply_bytes(&mut state.memory, read, write, 0x10, &[0x11]);
+    assert_eq!(state.memory[0x10], 0x00);
+
+    assert_eq!(
+        *rejections.lock().unwrap(),
+        [
+            RejectedInput::InvalidCharacter {
+                address: 0x5,
+                character: 'G'
+            },
+            RejectedInput::NotEditable { address: 0x10 },
+        ]
+    );
+}
+
+#[test]
+fn the_edit_permission_can_read_the_memory_and_applies_to_patches() {
+    let mut state = State::new();
+    state.editor = state
+        .editor
+        // The last byte locks the memory, and the byte before it is never editable.
+        .with_edit_permission(|address, read| address != 0xFFE && read(0xFFF) == Some(0));
+    state.memory[0xFFF] = 1;
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("5A").click();
+    harness.run();
+    assert!(harness.query_by_role(egui::accesskit::Role::TextInput).is_none());
+
+    harness.state_mut().memory[0xFFF] = 0;
+    harness.get_by_label("🛠 Options").click();
+    let patch = egui_memory_editor::patch::Patch::from_changes([(0x5, 0xAB), (0xFFE, 0xCD)].into());
+    harness.state_mut().editor.set_pending_patch(patch, 0);
+    harness.run();
+    harness.get_by_label("Apply").click();
+    harness.run();
+
+    assert_eq!(harness.state().memory[0x5..0x6], [0xAB]);
+    assert_eq!(harness.state().memory[0xFFE], 0x00);
+    assert!(harness
+        .query_by_label("1 writes of the applied patch were skipped, as their addresses aren't editable")
+        .is_some());
+}
+
+#[test]
+fn sparse_patches_are_previewed_by_their_changes_rather_than_their_span() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    state.editor.set_task_bytes_per_frame(2);
+    let mut harness = Harness::new_ui_state(
+        |ui, state: &mut State| {
+            state.editor.draw_editor_contents_buffered(
+                ui,
+                &mut state.memory,
+                |memory, address, length| memory.get(address..(address + length).min(memory.len())),
+                |memory, address, value| memory[address] = value,
+            );
+        },
+        state,
+    );
+    harness.run();
+
+    let patch = egui_memory_editor::patch::Patch::from_changes([(0x0, 0x11), (0xFFF, 0x22)].into());
+    harness.state_mut().editor.set_pending_patch(patch, 0);
+    harness.step();
+    harness.step();
+
+    assert!(harness
+        .query_by_label("Writes 2 bytes, of which 2 differ from the current memory")
+        .is_some());
+    assert!(harness.query_all_by_label("................").count() > 0);
+}
+
+#[test]
+fn keypad_digits_are_typed_into_the_edited_value() {
+    let mut state = State::new();
+    state.editor.options.show_keypad = true;
+    let mut harness = harness(state);
+    harness.run();
+    assert!(harness.query_by_label("C").is_none());
+
+    harness.get_by_label("5A").click();
+    harness.run();
+    // The keypad is only sized in its first frame.
+    harness.step();
+    // Press the keypad with the pointer, which takes the focus from the edited value.
+    for label in ["C", "D"] {
+        let bounds = harness.get_by_label(label).bounding_box().unwrap();
+        let pos = egui::pos2(
+            (bounds.x0 + bounds.x1) as f32 / 2.0,
+            (bounds.y0 + bounds.y1) as f32 / 2.0,
+        );
+        harness.input_mut().events.push(egui::Event::PointerMoved(pos));
+        harness.run();
+        for pressed in [true, false] {
+            harness.input_mut().events.push(egui::Event::PointerButton {
+                pos,
+                button: egui::PointerButton::Primary,
+                pressed,
+                modifiers: egui::Modifiers::NONE,
+            });
+            harness.run();
+        }
+    }
+
+    assert_eq!(harness.state().memory[5], 0xCD);
+}
+
+#[test]
+fn pasting_hex_writes_it_at_the_selection() {
+    let mut harness = harness(State::new());
+    harness.state_mut().editor.queue_goto(0x10);
+    harness.run();
+
+    harness
+        .input_mut()
+        .events
+        .push(egui::Event::Paste("0xDE,0xAD, 0xBE 0xEF".to_string()));
+    harness.run();
+    assert_eq!(harness.state().memory[0x10..0x14], [0xDE, 0xAD, 0xBE, 0xEF]);
+    assert_eq!(harness.state().editor.selection(), Some(0x10..0x14));
+
+    // Pastes which run past the end of the region are rejected as a whole.
+    harness.state_mut().editor.queue_goto(0xFFF);
+    harness.run();
+    harness.input_mut().events.push(egui::Event::Paste("11 22".to_string()));
+    harness.run();
+    assert_eq!(harness.state().memory[0xFFF], 0x00);
+}
+
+#[test]
+fn pastes_beyond_the_end_of_the_address_space_are_rejected() {
+    let mut editor = MemoryEditor::new().with_address_range("High", usize::MAX - 0x10..usize::MAX);
+    editor.options.is_options_collapsed = true;
+    editor.queue_goto(usize::MAX - 1);
+    let mut harness = Harness::new_ui_state(
+        |ui, (editor, writes): &mut (MemoryEditor, usize)| {
+            editor.draw_editor_contents(ui, writes, |_, _| Some(0), |writes, _, _| *writes += 1);
+        },
+        (editor, 0),
+    );
+    harness.run();
+
+    harness
+        .input_mut()
+        .events
+        .push(egui::Event::Paste("11 22 33".to_string()));
+    harness.run();
+    assert_eq!(harness.state().1, 0);
+}
+
+#[test]
+fn the_context_menu_of_an_address_bookmarks_and_rebases_its_row() {
+    let mut harness = harness(State::new());
+    harness.run();
+
+    right_click(&mut harness, "0x010:");
+    harness.get_by_label("Copy address").click();
+    harness.step();
+    assert_eq!(harness.output().platform_output.copied_text, "0x010");
+
+    right_click(&mut harness, "0x010:");
+    harness.get_by_label("Bookmark row").click();
+    harness.run();
+    assert!(harness.state().editor.bookmarks().contains(&0x10));
+
+    right_click(&mut harness, "0x010:");
+    harness.get_by_label("Set as display base").click();
+    harness.run();
+    assert_eq!(harness.state().editor.options.display_base, Some(0x10));
+    assert!(harness.query_by_label("+0x000:").is_some());
+    assert!(harness.query_by_label("-0x010:").is_some());
+
+    right_click(&mut harness, "+0x000:");
+    harness.get_by_label("Clear display base").click();
+    harness.run();
+    assert_eq!(harness.state().editor.options.display_base, None);
+    assert!(harness.query_by_label("0x010:").is_some());
+}
+
+#[test]
+fn any_address_can_be_entered_as_the_display_base() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    let mut harness = harness(state);
+    harness.run();
+
+    let submit = |harness: &mut Harness<'_, State>, text: &str| {
+        harness
+            .query_all_by_role(egui::accesskit::Role::TextInput)
+            .nth(2)
+            .unwrap()
+            .type_text(text);
+        harness.run();
+        harness.press_key(egui::Key::Enter);
+        harness.run();
+    };
+
+    // Not a row start, nor within the region.
+    submit(&mut harness, "8");
+    assert_eq!(harness.state().editor.options.display_base, Some(0x8));
+    assert!(harness.query_by_label("-0x008:").is_some());
+    assert!(harness.query_by_label("+0x008:").is_some());
+
+    harness.state_mut().editor.options.display_base = None;
+    harness.run();
+    submit(&mut harness, "0x2000");
+    assert_eq!(harness.state().editor.options.display_base, Some(0x2000));
+    assert!(harness.query_by_label("-0x2000:").is_some());
+
+    harness.state_mut().editor.options.display_base = None;
+    harness.run();
+    submit(&mut harness, &format!("{:X}", usize::MAX));
+    assert_eq!(harness.state().editor.options.display_base, Some(usize::MAX));
+}
+
+#[test]
+fn checksums_are_validated_and_must_fit_within_the_region() {
+    let mut state = State::new();
+    state.memory[0x10..0x19].copy_from_slice(b"123456789");
+    state.editor.options.is_options_collapsed = false;
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("🔢 Checksum").click();
+    harness.run();
+    for (input, text) in ["10", "18", "20"].into_iter().enumerate() {
+        let inputs: Vec<_> = harness.query_all_by_role(egui::accesskit::Role::TextInput).collect();
+        inputs[inputs.len() - 3 + input].focus();
+        harness.run();
+        let inputs: Vec<_> = harness.query_all_by_role(egui::accesskit::Role::TextInput).collect();
+        inputs[inputs.len() - 3 + input].type_text(text);
+        harness.run();
+    }
+    harness.get_by_label("Validate").click();
+    harness.run();
+    assert!(harness.query_by_label("Expected: DD").is_some());
+    assert!(harness.query_by_label("✖ The checksum doesn't match").is_some());
+
+    harness.get_by_label("Fix checksum").click();
+    harness.run();
+    assert_eq!(harness.state().memory[0x20], 0xDD);
+    assert!(harness.query_by_label("✔ The checksum is valid").is_some());
+
+    // The end of the checksum would overflow the address space.
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .focus();
+    harness.run();
+    for _ in 0..2 {
+        harness.press_key(egui::Key::Backspace);
+    }
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .last()
+        .unwrap()
+        .type_text("FFFFFFFFFFFFFFFF");
+    harness.run();
+    harness.get_by_label("Validate").click();
+    harness.run();
+    assert!(harness
+        .query_by_label("The checksum at 0xFFFFFFFFFFFFFFFF is out of range, it should fit within 0x0..0x1000")
+        .is_some());
+    assert!(harness.query_by_label("Expected: DD").is_none());
+}
+
+#[test]
+#[cfg(feature = "histogram")]
+fn the_histogram_counts_the_selection_and_follows_changes() {
+    let mut state = State::new();
+    state.memory[0x10..0x19].copy_from_slice(b"123456789");
+    state.editor.options.is_options_collapsed = false;
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("31").click();
+    harness.run();
+    harness.input_mut().modifiers = egui::Modifiers::SHIFT;
+    harness.get_by_label("39").click();
+    harness.run();
+    harness.input_mut().modifiers = egui::Modifiers::NONE;
+
+    harness.get_by_label("📈 Histogram").click();
+    harness.run();
+    assert!(harness
+        .query_by_label("0x9 bytes of the selection (0x10..0x19), entropy: 3.17 bits per byte")
+        .is_some());
+
+    harness.state_mut().memory[0x10..0x19].copy_from_slice(b"121212121");
+    harness.run();
+    assert!(harness
+        .query_by_label("0x9 bytes of the selection (0x10..0x19), entropy: 0.99 bits per byte")
+        .is_some());
+}
+
+#[test]
+fn hashes_of_the_selection_are_calculated_on_request() {
+    let mut state = State::new();
+    state.memory[0x10..0x19].copy_from_slice(b"123456789");
+    state.editor.options.is_options_collapsed = false;
+    let mut harness = harness(state);
+    harness.run();
+
+    harness.get_by_label("31").click();
+    harness.run();
+    harness.input_mut().modifiers = egui::Modifiers::SHIFT;
+    harness.get_by_label("39").click();
+    harness.run();
+    harness.input_mut().modifiers = egui::Modifiers::NONE;
+
+    harness.get_by_label("#️⃣ Hashes").click();
+    harness.run();
+    harness.get_by_label("Hash selection").click();
+    harness.run();
+
+    assert!(harness.query_by_label("Hashes of 0x9 bytes at 0x10..0x19").is_some());
+    assert!(harness.query_by_label("29B1").is_some());
+    assert!(harness.query_by_label("CBF43926").is_some());
+    #[cfg(feature = "hashes")]
+    {
+        assert!(harness.query_by_label("25F9E794323B453885F5181F1B624D0B").is_some());
+        assert!(harness
+            .query_by_label("F7C3BC1D808E04732ADF679965CCC34CA7AE3441")
+            .is_some());
+    }
+}
+
+#[test]
+fn hashes_are_calculated_over_several_frames() {
+    let mut state = State::new();
+    state.memory[0x10..0x19].copy_from_slice(b"123456789");
+    state.editor.options.is_options_collapsed = false;
+    state.editor.set_task_bytes_per_frame(2);
+    let mut harness = Harness::new_ui_state(
+        |ui, state: &mut State| {
+            state.editor.draw_editor_contents_buffered(
+                ui,
+                &mut state.memory,
+                |memory, address, length| memory.get(address..(address + length).min(memory.len())),
+                |memory, address, value| memory[address] = value,
+            );
+        },
+        state,
+    );
+    harness.run();
+
+    harness.get_by_label("31").click();
+    harness.run();
+    harness.input_mut().modifiers = egui::Modifiers::SHIFT;
+    harness.get_by_label("39").click();
+    harness.run();
+    harness.input_mut().modifiers = egui::Modifiers::NONE;
+
+    harness.get_by_label("#️⃣ Hashes").click();
+    harness.run();
+    harness.get_by_label("Hash selection").click();
+    harness.step();
+    assert!(harness.query_by_label("CBF43926").is_none());
+
+    for _ in 0..8 {
+        harness.step();
+    }
+    assert!(harness.query_by_label("29B1").is_some());
+    assert!(harness.query_by_label("CBF43926").is_some());
+}
+
+#[test]
+fn the_context_menu_of_a_value_copies_its_address() {
+    let mut harness = harness(State::new());
+    harness.run();
+
+    right_click(&mut harness, "5A");
+    harness.get_by_label("Copy address").click();
+    harness.step();
+
+    assert_eq!(harness.output().platform_output.copied_text, "0x005");
+    assert_eq!(harness.state().editor.selection(), Some(0x5..0x6));
+
+    harness.state_mut().editor.options.display_base = Some(0x10);
+    harness.run();
+    right_click(&mut harness, "5A");
+    harness.get_by_label("Copy address").click();
+    harness.step();
+    assert_eq!(harness.output().platform_output.copied_text, "-0x00B");
+}
+
+#[test]
+fn quick_marks_are_set_and_jumped_to_with_the_digit_keys() {
+    let mut harness = harness(State::new());
+    harness.run();
+
+    let press = |harness: &mut Harness<'_, State>, key: egui::Key, modifiers: egui::Modifiers| {
+        for pressed in [true, false] {
+            harness.input_mut().events.push(egui::Event::Key {
+                key,
+                physical_key: Some(egui::Key::Num1),
+                pressed,
+                repeat: false,
+                modifiers,
+            });
+        }
+        harness.run();
+    };
+
+    harness.state_mut().editor.queue_goto(0x5);
+    harness.run();
+    // Shift turns the digit into a symbol on most layouts, such as `+` on a Swiss one, leaving only the physical key.
+    press(
+        &mut harness,
+        egui::Key::Plus,
+        egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+    );
+    assert_eq!(harness.state().editor.quick_mark(1), Some(("RAM", 0x5)));
+
+    harness.state_mut().editor.queue_goto(0x800);
+    harness.run();
+    press(&mut harness, egui::Key::Num1, egui::Modifiers::COMMAND);
+    assert_eq!(harness.state().editor.selection(), Some(0x5..0x6));
+    assert_eq!(harness.state().editor.quick_mark(2), None);
+}
+
+#[test]
+fn quick_mark_shortcuts_are_ignored_while_typing_in_a_text_field() {
+    let mut state = State::new();
+    state.editor.options.is_options_collapsed = false;
+    let mut harness = harness(state);
+    harness.state_mut().editor.queue_goto(0x5);
+    harness.run();
+
+    harness
+        .query_all_by_role(egui::accesskit::Role::TextInput)
+        .next()
+        .unwrap()
+        .focus();
+    harness.run();
+    for pressed in [true, false] {
+        harness.input_mut().events.push(egui::Event::Key {
+            key: egui::Key::Num1,
+            physical_key: Some(egui::Key::Num1),
+            pressed,
+            repeat: false,
+            modifiers: egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+        });
+    }
+    harness.run();
+
+    assert_eq!(harness.state().editor.quick_mark(1), None);
+}
+
+#[test]
+fn alt_dragging_selects_the_same_columns_in_every_row() {
+    let mut state = State::new();
+    state.memory[0x2] = 0x11;
+    state.memory[0x13] = 0x33;
+    state.memory[0x23] = 0x22;
+    let mut harness = harness(state);
+    harness.run();
+
+    let centre = |harness: &Harness<'_, State>, label: &str| {
+        let bounds = harness.get_by_label(label).bounding_box().unwrap();
+        egui::pos2(
+            (bounds.x0 + bounds.x1) as f32 / 2.0,
+            (bounds.y0 + bounds.y1) as f32 / 2.0,
+        )
+    };
+    let (start, end) = (centre(&harness, "11"), centre(&harness, "22"));
+    let button = |pos, pressed| egui::Event::PointerButton {
+        pos,
+        button: egui::PointerButton::Primary,
+        pressed,
+        modifiers: egui::Modifiers::ALT,
+    };
+
+    harness.input_mut().modifiers = egui::Modifiers::ALT;
+    harness.input_mut().events.push(egui::Event::PointerMoved(start));
+    harness.input_mut().events.push(button(start, true));
+    harness.run();
+    for step in 1..=4 {
+        let pos = start + (end - start) * step as f32 / 4.0;
+        harness.input_mut().events.push(egui::Event::PointerMoved(pos));
+        harness.run();
+    }
+    harness.input_mut().events.push(button(end, false));
+    harness.run();
+    harness.input_mut().modifiers = egui::Modifiers::NONE;
+
+    let selected: Vec<usize> = harness.state().editor.scattered_selection().iter().copied().collect();
+    assert_eq!(selected, vec![0x2, 0x3, 0x12, 0x13, 0x22, 0x23]);
+
+    right_click(&mut harness, "33");
+    harness.get_by_label("Copy as hex").click();
+    harness.step();
+    assert_eq!(harness.output().platform_output.copied_text, "11 00\n00 33\n00 22");
+}
+
+#[test]
+fn selections_beyond_the_copy_limit_show_a_warning_instead() {
+    let mut harness = harness(State::new());
+    harness.state_mut().editor.options.max_copy_bytes = 0x100;
+    harness.run();
+
+    harness.input_mut().modifiers = egui::Modifiers::COMMAND;
+    harness.press_key(egui::Key::A);
+    harness.run();
+    harness.input_mut().modifiers = egui::Modifiers::NONE;
+    harness.input_mut().events.push(egui::Event::Copy);
+    harness.step();
+
+    assert_eq!(harness.output().platform_output.copied_text, "");
+    harness.run();
+    assert!(harness
+        .query_by_label("The selection of 0x1000 bytes is too large to copy, the limit is 0x100")
+        .is_some());
+
+    // Changing the selection clears the warning.
+    harness.get_by_label("5A").click();
+    harness.run();
+    assert!(harness
+        .query_by_label("The selection of 0x1000 bytes is too large to copy, the limit is 0x100")
+        .is_none());
+}
+
+#[test]
+#[cfg(feature = "patches")]
+fn edits_round_trip_through_ips_and_bps_patches() {
+    use egui_memory_editor::patch::Patch;
+
+    let mut harness = harness(State::new());
+    harness.run();
+    harness.get_by_label("5A").click();
+    harness.run();
+    harness.get_by_role(egui::accesskit::Role::TextInput).type_text("AB");
+    harness.run();
+
+    let original = State::new().memory;
+    let history = harness.state().editor.edit_history();
+    let expected = std::collections::BTreeMap::from([(5, 0xAB)]);
+
+    let ips = Patch::from_ips(&history.to_ips(0).unwrap()).unwrap();
+    assert_eq!(ips.changes(), &expected);
+    let bps = Patch::from_bps(&history.to_bps(&original, 0).unwrap(), &original).unwrap();
+    assert_eq!(bps.changes(), &expected);
+}
+
+#[test]
+#[cfg(feature = "patches")]
+fn malformed_patches_are_rejected() {
+    use egui_memory_editor::patch::{Patch, PatchError};
+    use egui_memory_editor::utilities::crc32;
+
+    assert_eq!(Patch::from_ips(b"NOTAPATCH"), Err(PatchError::InvalidHeader));
+    // A record of 4 bytes with only 2 present.
+    assert_eq!(
+        Patch::from_ips(b"PATCH\x00\x00\x10\x00\x04\xAA\xBB"),
+        Err(PatchError::UnexpectedEnd)
+    );
+
+    // A valid patch with its checksums intact, but whose size doesn't fit in a `usize`.
+    let source = [0u8; 4];
+    let mut bps = b"BPS1".to_vec();
+    bps.extend([0x7F; 12]);
+    bps.extend(crc32(&source).to_le_bytes());
+    bps.extend(0u32.to_le_bytes());
+    bps.extend(crc32(&bps).to_le_bytes());
+    assert_eq!(Patch::from_bps(&bps, &source), Err(PatchError::NumberTooLarge));
+
+    // A target read beyond the declared target size of 1 byte.
+    let mut bps = b"BPS1".to_vec();
+    bps.extend([0x84, 0x81, 0x80, (7 << 2 | 1) | 0x80]);
+    bps.extend([0xFF; 8]);
+    bps.extend(crc32(&source).to_le_bytes());
+    bps.extend(0u32.to_le_bytes());
+    bps.extend(crc32(&bps).to_le_bytes());
+    assert_eq!(Patch::from_bps(&bps, &source), Err(PatchError::OutOfBounds));
+}